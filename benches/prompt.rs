@@ -0,0 +1,113 @@
+//! Rough timings of rendering a prompt, run with `cargo bench --bench prompt`.
+//!
+//! This doesn't use a benchmarking framework, so that it builds without extra dependencies:
+//! every case is run for a fixed number of iterations, and the mean time is printed along
+//! with the number of allocations, which is steadier than timings across machines.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use starship::context::Context;
+use starship::formatter::StringFormatter;
+use starship::print;
+
+const ITERATIONS: u32 = 2000;
+
+/// Counts the allocations made through the system allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Modules which don't run any commands, so that the timings aren't dominated by
+/// spawning processes
+const CONFIG: &str = r#"
+format = "$directory$git_branch$env_var$cmd_duration$line_break$jobs$character"
+
+[env_var]
+variable = "HOME"
+
+[cmd_duration]
+min_time = 0
+"#;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    // Warm up caches before timing
+    for _ in 0..ITERATIONS / 10 {
+        f();
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let mean = start.elapsed() / ITERATIONS;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS as usize;
+    println!(
+        "{:<24} {:>10.2?} / iter {:>8} allocations / iter",
+        name, mean, allocations
+    );
+}
+
+fn format_module() {
+    let symbol = "🦀 ";
+    let version = String::from("v1.47.0");
+    let segments = StringFormatter::new("via [$symbol($version )]($style)")
+        .and_then(|formatter| {
+            formatter
+                .map_meta(|variable, _| match variable {
+                    "symbol" => Some(symbol),
+                    _ => None,
+                })
+                .map_style(|variable| match variable {
+                    "style" => Some(Ok("bold red")),
+                    _ => None,
+                })
+                .map(|variable| match variable {
+                    "version" => Some(Ok(&version)),
+                    _ => None,
+                })
+                .parse(None, None)
+        })
+        .unwrap();
+    assert_eq!(segments.len(), 4);
+}
+
+fn main() {
+    let dir = env::temp_dir().join("starship-bench");
+    fs::create_dir_all(&dir).unwrap();
+    let config_path = dir.join("starship.toml");
+    fs::write(&config_path, CONFIG).unwrap();
+    env::set_var("STARSHIP_CONFIG", &config_path);
+
+    bench("format_module", format_module);
+
+    let context = Context::new_with_dir(clap::ArgMatches::new(), &dir);
+    bench("get_prompt", || {
+        let prompt = print::get_prompt(&context);
+        assert!(!prompt.is_empty());
+    });
+
+    bench("get_prompt_new_context", || {
+        let context = Context::new_with_dir(clap::ArgMatches::new(), &dir);
+        let prompt = print::get_prompt(&context);
+        assert!(!prompt.is_empty());
+    });
+}
@@ -1,4 +1,5 @@
 use crate::configs::StarshipRootConfig;
+use crate::context::Context;
 use crate::utils;
 use ansi_term::{Color, Style};
 
@@ -59,7 +60,7 @@ impl<'a> ModuleConfig<'a> for &'a str {
 
 impl<'a> ModuleConfig<'a> for Style {
     fn from_config(config: &Value) -> Option<Self> {
-        parse_style_string(config.as_str()?)
+        parse_style_string(config.as_str()?, None)
     }
 }
 
@@ -176,6 +177,26 @@ where
     }
 }
 
+/// A table of values of type `T`, which unlike a `HashMap` keeps its entries in the
+/// order of the configuration, for options where the first matching entry wins.
+#[derive(Clone, Default)]
+pub struct OrderedMap<T>(pub Vec<(String, T)>);
+
+impl<'a, T> ModuleConfig<'a> for OrderedMap<T>
+where
+    T: ModuleConfig<'a>,
+{
+    fn from_config(config: &'a Value) -> Option<Self> {
+        let entries = config
+            .as_table()?
+            .iter()
+            .map(|(key, value)| Some((key.clone(), T::from_config(value)?)))
+            .collect::<Option<Vec<(String, T)>>>()?;
+
+        Some(OrderedMap(entries))
+    }
+}
+
 /// Root config of starship.
 pub struct StarshipConfig {
     pub config: Option<Value>,
@@ -184,14 +205,30 @@ pub struct StarshipConfig {
 impl StarshipConfig {
     /// Initialize the Config struct
     pub fn initialize() -> Self {
-        if let Some(file_data) = Self::config_from_file() {
-            StarshipConfig {
-                config: Some(file_data),
-            }
-        } else {
-            StarshipConfig {
-                config: Some(Value::Table(toml::value::Table::new())),
-            }
+        Self::from_value(Self::config_from_file())
+    }
+
+    /// Load the config in the file at `path`, instead of the user's configuration file
+    pub fn from_path(path: &str) -> Option<Self> {
+        Self::parse_config_file(path).map(|config| Self::from_value(Some(config)))
+    }
+
+    fn from_value(config: Option<Value>) -> Self {
+        let mut config = config.unwrap_or_else(|| Value::Table(toml::value::Table::new()));
+
+        resolve_config_references(&mut config);
+        apply_state_symbols(&mut config);
+
+        let is_screen_reader = config
+            .get("screen_reader")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if is_screen_reader {
+            apply_screen_reader_formats(&mut config);
+        }
+
+        StarshipConfig {
+            config: Some(config),
         }
     }
 
@@ -210,7 +247,12 @@ impl StarshipConfig {
             config_path_str
         };
 
-        let toml_content = match utils::read_file(&file_path) {
+        Self::parse_config_file(&file_path)
+    }
+
+    /// Read and parse the starship configuration file at `file_path`
+    fn parse_config_file(file_path: &str) -> Option<Value> {
+        let toml_content = match utils::read_file(file_path) {
             Ok(content) => {
                 log::trace!("Config file content: \n{}", &content);
                 Some(content)
@@ -315,6 +357,20 @@ impl StarshipConfig {
         self.get_config(&["custom"])?.as_table()
     }
 
+    /// Get the table of an instance of the env_var module (`[env_var.<name>]`) by its name
+    pub fn get_env_var_module_config(&self, name: &str) -> Option<&Value> {
+        self.get_config(&["env_var", name])
+            .filter(|module_config| module_config.is_table())
+    }
+
+    /// Get the tables of all the instances of the env_var module, if any
+    pub fn get_env_var_modules(&self) -> Vec<(&String, &Value)> {
+        self.get_config(&["env_var"])
+            .and_then(Value::as_table)
+            .map(|table| table.iter().filter(|(_, value)| value.is_table()).collect())
+            .unwrap_or_default()
+    }
+
     pub fn get_root_config(&self) -> StarshipRootConfig {
         if let Some(root_config) = &self.config {
             StarshipRootConfig::load(root_config)
@@ -322,6 +378,225 @@ impl StarshipConfig {
             StarshipRootConfig::new()
         }
     }
+
+    /// Get the colors of the palette selected with the root `palette` option.
+    ///
+    /// A palette defined under `[palettes]` takes precedence over a built-in palette
+    /// with the same name, and only overrides the colors it defines.
+    pub fn get_palette(&self) -> HashMap<String, String> {
+        let root_config = self.get_root_config();
+        let name = match root_config.palette {
+            Some(name) => name,
+            None => return HashMap::new(),
+        };
+
+        let mut palette: HashMap<String, String> = builtin_palette(name)
+            .unwrap_or_default()
+            .iter()
+            .map(|(color_name, color)| ((*color_name).to_string(), (*color).to_string()))
+            .collect();
+
+        match root_config.palettes.get(name) {
+            Some(user_palette) => palette.extend(
+                user_palette
+                    .iter()
+                    .map(|(color_name, color)| (color_name.to_lowercase(), (*color).to_string())),
+            ),
+            None if palette.is_empty() => log::warn!("Could not find palette \"{}\"", name),
+            None => {}
+        }
+
+        palette
+    }
+}
+
+/// Prefix of a reference to another value of the configuration, as in `${config:palette.accent}`
+const CONFIG_REFERENCE_PREFIX: &str = "${config:";
+
+/// How many references are followed from a value, to stop reference cycles
+const MAX_REFERENCE_DEPTH: usize = 8;
+
+/// Replaces the references to other values of the configuration in all of its strings
+fn resolve_config_references(config: &mut Value) {
+    let root = config.clone();
+    resolve_references_in(config, &root);
+}
+
+fn resolve_references_in(value: &mut Value, root: &Value) {
+    match value {
+        Value::String(text) if text.contains(CONFIG_REFERENCE_PREFIX) => {
+            *text = expand_references(text, root, 0);
+        }
+        Value::Array(values) => values
+            .iter_mut()
+            .for_each(|value| resolve_references_in(value, root)),
+        Value::Table(table) => table
+            .iter_mut()
+            .for_each(|(_, value)| resolve_references_in(value, root)),
+        _ => {}
+    }
+}
+
+/// Expands the references in `text`, leaving the ones which can't be resolved as they are
+fn expand_references(text: &str, root: &Value, depth: usize) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find(CONFIG_REFERENCE_PREFIX) {
+        let after_prefix = &rest[start + CONFIG_REFERENCE_PREFIX.len()..];
+        let end = match after_prefix.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        let reference = &rest[start..start + CONFIG_REFERENCE_PREFIX.len() + end + 1];
+        let path = &after_prefix[..end];
+
+        expanded.push_str(&rest[..start]);
+        match lookup_reference(path, root, depth) {
+            Some(value) => expanded.push_str(&value),
+            None => {
+                log::warn!("Could not resolve the config reference \"{}\"", reference);
+                expanded.push_str(reference);
+            }
+        }
+        rest = &rest[start + reference.len()..];
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+fn lookup_reference(path: &str, root: &Value, depth: usize) -> Option<String> {
+    if depth >= MAX_REFERENCE_DEPTH {
+        return None;
+    }
+
+    let value = path
+        .split('.')
+        .try_fold(root, |value, key| value.get(key.trim()))?;
+    match value {
+        Value::String(text) => Some(expand_references(text, root, depth + 1)),
+        Value::Integer(number) => Some(number.to_string()),
+        Value::Float(number) => Some(number.to_string()),
+        Value::Boolean(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+/// Spoken module formats, replacing the configured ones in screen reader mode
+const SCREEN_READER_CONFIG: &str = include_str!("configs/screen_reader.toml");
+
+/// Overlay the spoken module formats onto `config`, keeping the options they don't set
+fn apply_screen_reader_formats(config: &mut Value) {
+    let overlay = match toml::from_str::<Value>(SCREEN_READER_CONFIG) {
+        Ok(Value::Table(overlay)) => overlay,
+        _ => {
+            log::error!("Unable to parse the screen reader configuration");
+            return;
+        }
+    };
+    let config = match config.as_table_mut() {
+        Some(config) => config,
+        None => return,
+    };
+
+    for (module, formats) in overlay {
+        match (config.get_mut(&module), formats) {
+            (Some(Value::Table(module_config)), Value::Table(formats)) => {
+                module_config.extend(formats)
+            }
+            (_, formats) => {
+                config.insert(module, formats);
+            }
+        }
+    }
+}
+
+/// Module options taking their default from the root `state_symbols` table, as
+/// `(state, module, option, format)`, where `{}` in `format` is replaced by the symbol
+const STATE_SYMBOL_OPTIONS: &[(&str, &str, &str, &str)] = &[
+    (
+        "success",
+        "character",
+        "success_symbol",
+        "[{}](bold success)",
+    ),
+    ("error", "character", "error_symbol", "[{}](bold error)"),
+    (
+        "error",
+        "character",
+        "cmdlet_error_symbol",
+        "[{}](bold warning)",
+    ),
+    ("positive", "battery", "charging_symbol", "{}"),
+    ("positive", "git_status", "ahead", "{}"),
+    ("negative", "battery", "discharging_symbol", "{}"),
+    ("negative", "git_status", "behind", "{}"),
+];
+
+/// Uses the symbols of the root `state_symbols` table as the default symbols of modules.
+/// Symbols set in the configuration of a module are left untouched.
+fn apply_state_symbols(config: &mut Value) {
+    let state_symbols = match config.get("state_symbols").and_then(Value::as_table) {
+        Some(state_symbols) => state_symbols.clone(),
+        None => return,
+    };
+    let config = match config.as_table_mut() {
+        Some(config) => config,
+        None => return,
+    };
+
+    for (state, module, option, format) in STATE_SYMBOL_OPTIONS {
+        let symbol = match state_symbols.get(*state).and_then(Value::as_str) {
+            Some(symbol) => symbol,
+            None => continue,
+        };
+        let module_config = config
+            .entry(*module)
+            .or_insert_with(|| Value::Table(toml::value::Table::new()));
+        if let Some(module_config) = module_config.as_table_mut() {
+            module_config
+                .entry(*option)
+                .or_insert_with(|| Value::String(format.replace("{}", &escape_format(symbol))));
+        }
+    }
+}
+
+/// Escapes the characters with a special meaning in format strings
+fn escape_format(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if let '[' | ']' | '(' | ')' | '\\' | '$' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Semantic colors, used when the selected palette doesn't define them
+const DEFAULT_PALETTE: &[(&str, &str)] = &[
+    ("success", "green"),
+    ("error", "red"),
+    ("warning", "yellow"),
+    ("info", "blue"),
+];
+
+/// Semantic colors from the Okabe-Ito palette, which stay distinguishable under the
+/// common forms of color blindness
+const COLOR_BLIND_PALETTE: &[(&str, &str)] = &[
+    ("success", "#0072b2"),
+    ("error", "#d55e00"),
+    ("warning", "#f0e442"),
+    ("info", "#56b4e9"),
+];
+
+/// Get a palette shipped with starship by its name
+fn builtin_palette(name: &str) -> Option<&'static [(&'static str, &'static str)]> {
+    match name {
+        "color_blind" => Some(COLOR_BLIND_PALETTE),
+        _ => None,
+    }
 }
 
 /** Parse a style string which represents an ansi style. Valid tokens in the style
@@ -332,11 +607,25 @@ impl StarshipConfig {
  - 'bold'
  - 'italic'
  - '<color>'        (see the parse_color_string doc for valid color strings)
+
+ Color names are looked up in the palette of `context` first, if one is given.
+ No style is returned when `context` is in screen reader mode.
 */
-pub fn parse_style_string(style_string: &str) -> Option<ansi_term::Style> {
-    style_string
-        .split_whitespace()
-        .fold(Some(ansi_term::Style::new()), |maybe_style, token| {
+pub fn parse_style_string(
+    style_string: &str,
+    context: Option<&Context>,
+) -> Option<ansi_term::Style> {
+    // Screen readers would read out escape codes, so nothing gets styled
+    if let Some(true) = context.map(Context::is_screen_reader) {
+        return None;
+    }
+
+    let palette = context.map(Context::get_palette);
+    let min_contrast = context.map_or(0.0, Context::min_contrast);
+
+    let style = style_string.split_whitespace().fold(
+        Some(ansi_term::Style::new()),
+        |maybe_style, token| {
             maybe_style.and_then(|style| {
                 let token = token.to_lowercase();
 
@@ -358,7 +647,7 @@ pub fn parse_style_string(style_string: &str) -> Option<ansi_term::Style> {
                     "none" => None,
 
                     // Try to see if this token parses as a valid color string
-                    color_string => parse_color_string(color_string).map(|ansi_color| {
+                    color_string => parse_color_string(color_string, palette).map(|ansi_color| {
                         if col_fg {
                             style.fg(ansi_color)
                         } else {
@@ -367,16 +656,150 @@ pub fn parse_style_string(style_string: &str) -> Option<ansi_term::Style> {
                     }),
                 }
             })
+        },
+    )?;
+
+    if min_contrast > 0.0 {
+        Some(ensure_contrast(style, min_contrast))
+    } else {
+        Some(style)
+    }
+}
+
+/// Lighten or darken the foreground of `style` until its contrast ratio with the
+/// background is at least `min_contrast`, as defined by WCAG (from 1 to 21).
+/// Styles without both a foreground and a background color are returned as-is.
+fn ensure_contrast(style: Style, min_contrast: f64) -> Style {
+    let (fg, bg) = match (style.foreground.map(to_rgb), style.background.map(to_rgb)) {
+        (Some(fg), Some(bg)) => (fg, bg),
+        _ => return style,
+    };
+    if contrast_ratio(fg, bg) >= min_contrast {
+        return style;
+    }
+
+    // Move towards white on dark backgrounds, and towards black on light ones
+    let target = if contrast_ratio((255, 255, 255), bg) >= contrast_ratio((0, 0, 0), bg) {
+        (255, 255, 255)
+    } else {
+        (0, 0, 0)
+    };
+    let mix = |from: u8, to: u8, amount: f64| {
+        (f64::from(from) + (f64::from(to) - f64::from(from)) * amount).round() as u8
+    };
+    let adjusted = (1..=10)
+        .map(|step| {
+            let amount = f64::from(step) / 10.0;
+            (
+                mix(fg.0, target.0, amount),
+                mix(fg.1, target.1, amount),
+                mix(fg.2, target.2, amount),
+            )
         })
+        .find(|color| contrast_ratio(*color, bg) >= min_contrast)
+        .unwrap_or(target);
+
+    log::trace!(
+        "Adjusted foreground {:?} to {:?} for a contrast of {}",
+        fg,
+        adjusted,
+        min_contrast
+    );
+    style.fg(Color::RGB(adjusted.0, adjusted.1, adjusted.2))
+}
+
+/// The contrast ratio between two colors, as defined by WCAG
+fn contrast_ratio(a: (u8, u8, u8), b: (u8, u8, u8)) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+fn relative_luminance((r, g, b): (u8, u8, u8)) -> f64 {
+    let linear = |channel: u8| {
+        let channel = f64::from(channel) / 255.0;
+        if channel <= 0.039_28 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// The RGB value of a color, using the default xterm colors for ANSI colors
+fn to_rgb(color: Color) -> (u8, u8, u8) {
+    const ANSI_COLORS: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    match color {
+        Color::Black => ANSI_COLORS[0],
+        Color::Red => ANSI_COLORS[1],
+        Color::Green => ANSI_COLORS[2],
+        Color::Yellow => ANSI_COLORS[3],
+        Color::Blue => ANSI_COLORS[4],
+        Color::Purple => ANSI_COLORS[5],
+        Color::Cyan => ANSI_COLORS[6],
+        Color::White => ANSI_COLORS[7],
+        Color::Fixed(n @ 0..=15) => ANSI_COLORS[n as usize],
+        Color::Fixed(n @ 16..=231) => {
+            let n = (n - 16) as usize;
+            (
+                CUBE_LEVELS[n / 36],
+                CUBE_LEVELS[n / 6 % 6],
+                CUBE_LEVELS[n % 6],
+            )
+        }
+        Color::Fixed(n) => {
+            let gray = 8 + 10 * (n - 232);
+            (gray, gray, gray)
+        }
+        Color::RGB(r, g, b) => (r, g, b),
+    }
 }
 
 /** Parse a string that represents a color setting, returning None if this fails
- There are three valid color formats:
+ There are four valid color formats:
   - #RRGGBB      (a hash followed by an RGB hex)
   - u8           (a number from 0-255, representing an ANSI color)
   - colstring    (one of the 16 predefined color strings)
+  - name         (a color defined in the palette, or a semantic color such as `success`)
 */
-fn parse_color_string(color_string: &str) -> Option<ansi_term::Color> {
+fn parse_color_string(
+    color_string: &str,
+    palette: Option<&HashMap<String, String>>,
+) -> Option<ansi_term::Color> {
+    // Palette colors may only refer to the semantic colors, not to other palette colors
+    let palette_color = palette
+        .and_then(|palette| palette.get(color_string).map(String::as_str))
+        .or_else(|| {
+            DEFAULT_PALETTE
+                .iter()
+                .find(|(name, _)| *name == color_string)
+                .map(|(_, color)| *color)
+        });
+    if let Some(palette_color) = palette_color {
+        log::trace!("Read palette color {}: {}", color_string, palette_color);
+        return parse_color_string(palette_color, None);
+    }
+
     // Parse RGB hex values
     log::trace!("Parsing color_string: {}", color_string);
     if color_string.starts_with('#') {
@@ -597,6 +1020,22 @@ mod tests {
         assert_eq!(<Vec<&str>>::from_config(&config).unwrap(), vec!["S"]);
     }
 
+    #[test]
+    fn test_from_ordered_map() {
+        let config = toml::toml! {
+            "z" = "last letter"
+            "a" = "first letter"
+        };
+        let map = <OrderedMap<&str>>::from_config(&config).unwrap();
+        assert_eq!(
+            map.0,
+            vec![
+                ("z".to_string(), "last letter"),
+                ("a".to_string(), "first letter")
+            ]
+        );
+    }
+
     #[test]
     fn test_from_option() {
         let config: Value = Value::String(String::from("S"));
@@ -663,4 +1102,228 @@ mod tests {
             Style::new().fg(Color::Fixed(125)).on(Color::Fixed(127))
         );
     }
+
+    #[test]
+    fn table_get_styles_semantic_colors() {
+        let config = Value::from("bold success");
+        assert_eq!(<Style>::from_config(&config).unwrap(), Color::Green.bold());
+
+        let config = Value::from("fg:warning bg:error");
+        assert_eq!(
+            <Style>::from_config(&config).unwrap(),
+            Style::new().fg(Color::Yellow).on(Color::Red)
+        );
+    }
+
+    #[test]
+    fn get_palette_from_config() {
+        let config = StarshipConfig {
+            config: Some(toml::toml! {
+                palette = "mine"
+                [palettes.mine]
+                success = "#0000ff"
+                Mustard = "#af8700"
+            }),
+        };
+        let palette = config.get_palette();
+
+        assert_eq!(
+            parse_color_string("success", Some(&palette)),
+            Some(Color::RGB(0, 0, 255))
+        );
+        assert_eq!(
+            parse_color_string("mustard", Some(&palette)),
+            Some(Color::RGB(175, 135, 0))
+        );
+        // Semantic colors that aren't redefined keep their default
+        assert_eq!(
+            parse_color_string("error", Some(&palette)),
+            Some(Color::Red)
+        );
+    }
+
+    #[test]
+    fn get_builtin_color_blind_palette() {
+        let config = StarshipConfig {
+            config: Some(toml::toml! {
+                palette = "color_blind"
+                [palettes.color_blind]
+                info = "cyan"
+            }),
+        };
+        let palette = config.get_palette();
+
+        assert_eq!(
+            parse_color_string("error", Some(&palette)),
+            Some(Color::RGB(213, 94, 0))
+        );
+        assert_eq!(
+            parse_color_string("info", Some(&palette)),
+            Some(Color::Cyan)
+        );
+    }
+
+    #[test]
+    fn get_palette_without_selection() {
+        let config = StarshipConfig {
+            config: Some(toml::toml! {
+                [palettes.mine]
+                success = "#0000ff"
+            }),
+        };
+
+        assert!(config.get_palette().is_empty());
+    }
+
+    #[test]
+    fn contrast_ratio_of_colors() {
+        assert!((contrast_ratio((0, 0, 0), (255, 255, 255)) - 21.0).abs() < 0.01);
+        assert!((contrast_ratio((255, 0, 0), (255, 0, 0)) - 1.0).abs() < 0.01);
+        assert_eq!(to_rgb(Color::Fixed(16)), (0, 0, 0));
+        assert_eq!(to_rgb(Color::Fixed(202)), (255, 95, 0));
+        assert_eq!(to_rgb(Color::Fixed(244)), (128, 128, 128));
+        assert_eq!(to_rgb(Color::Blue), (0, 0, 238));
+    }
+
+    #[test]
+    fn ensure_contrast_adjusts_foreground() {
+        // Dark blue on black is lightened
+        let style = Style::new().fg(Color::Blue).on(Color::Black).bold();
+        let adjusted = ensure_contrast(style, 4.5);
+        let fg = to_rgb(adjusted.foreground.unwrap());
+        assert!(contrast_ratio(fg, (0, 0, 0)) >= 4.5);
+        assert!(relative_luminance(fg) > relative_luminance((0, 0, 238)));
+        assert!(adjusted.is_bold);
+
+        // Yellow on white is darkened
+        let style = Style::new().fg(Color::Yellow).on(Color::Fixed(15));
+        let adjusted = ensure_contrast(style, 4.5);
+        let fg = to_rgb(adjusted.foreground.unwrap());
+        assert!(contrast_ratio(fg, (255, 255, 255)) >= 4.5);
+        assert!(relative_luminance(fg) < relative_luminance((205, 205, 0)));
+    }
+
+    #[test]
+    fn ensure_contrast_keeps_readable_or_partial_styles() {
+        let style = Style::new().fg(Color::White).on(Color::Black);
+        assert_eq!(ensure_contrast(style, 4.5), style);
+
+        let style = Style::new().fg(Color::Blue);
+        assert_eq!(ensure_contrast(style, 4.5), style);
+    }
+
+    #[test]
+    fn screen_reader_formats_override_module_formats() {
+        let mut config = toml::toml! {
+            [git_branch]
+            format = "on [$symbol$branch]($style) "
+            truncation_length = 4
+        };
+        apply_screen_reader_formats(&mut config);
+
+        let git_branch = config.get("git_branch").unwrap();
+        assert_eq!(
+            git_branch.get("format").and_then(Value::as_str),
+            Some("branch $branch, ")
+        );
+        assert_eq!(
+            git_branch
+                .get("truncation_length")
+                .and_then(Value::as_integer),
+            Some(4)
+        );
+        assert!(config.get("git_status").is_some());
+    }
+
+    #[test]
+    fn state_symbols_are_module_defaults() {
+        let mut config = toml::toml! {
+            [state_symbols]
+            success = ">"
+            error = "x"
+            positive = "+"
+            negative = "$"
+
+            [git_status]
+            ahead = "up"
+        };
+        apply_state_symbols(&mut config);
+
+        let get = |module: &str, option: &str| {
+            config
+                .get(module)
+                .and_then(|module| module.get(option))
+                .and_then(Value::as_str)
+                .map(str::to_owned)
+        };
+        assert_eq!(
+            get("character", "success_symbol"),
+            Some("[>](bold success)".to_owned())
+        );
+        assert_eq!(
+            get("character", "error_symbol"),
+            Some("[x](bold error)".to_owned())
+        );
+        assert_eq!(get("git_status", "ahead"), Some("up".to_owned()));
+        assert_eq!(get("git_status", "behind"), Some("\\$".to_owned()));
+        assert_eq!(get("battery", "charging_symbol"), Some("+".to_owned()));
+        assert_eq!(get("battery", "discharging_symbol"), Some("\\$".to_owned()));
+    }
+
+    #[test]
+    fn config_references_are_resolved() {
+        let mut config = toml::toml! {
+            [palette]
+            accent = "#ff8800"
+            symbol = "${config:base.symbol}!"
+
+            [base]
+            symbol = "*"
+            width = 12
+
+            [rust]
+            format = "[${config:palette.symbol} $version](bold ${config:palette.accent}) "
+            symbols = ["${config:base.width}", "${config:missing.key}"]
+        };
+        resolve_config_references(&mut config);
+
+        assert_eq!(
+            config["rust"]["format"].as_str(),
+            Some("[*! $version](bold #ff8800) ")
+        );
+        assert_eq!(config["rust"]["symbols"][0].as_str(), Some("12"));
+        assert_eq!(
+            config["rust"]["symbols"][1].as_str(),
+            Some("${config:missing.key}")
+        );
+    }
+
+    #[test]
+    fn config_reference_cycles_are_left_unresolved() {
+        let mut config = toml::toml! {
+            [a]
+            value = "<${config:b.value}>"
+
+            [b]
+            value = "${config:a.value}"
+        };
+        resolve_config_references(&mut config);
+
+        assert_eq!(
+            config["a"]["value"].as_str(),
+            Some("<<<<<${config:b.value}>>>>>")
+        );
+    }
+
+    #[test]
+    fn state_symbols_absent() {
+        let mut config = toml::toml! {
+            [character]
+            success_symbol = ">"
+        };
+        let expected = config.clone();
+        apply_state_symbols(&mut config);
+
+        assert_eq!(config, expected);
+    }
 }
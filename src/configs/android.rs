@@ -0,0 +1,28 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct AndroidConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for AndroidConfig<'a> {
+    fn new() -> Self {
+        AndroidConfig {
+            format: "via [$symbol(API $compile_sdk)( NDK $ndk)]($style) ",
+            symbol: "🤖 ",
+            style: "bold green",
+            detect_extensions: vec![],
+            detect_files: vec!["AndroidManifest.xml"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -9,6 +9,8 @@ pub struct AwsConfig<'a> {
     pub style: &'a str,
     pub disabled: bool,
     pub region_aliases: HashMap<String, &'a str>,
+    pub profile_aliases: HashMap<String, &'a str>,
+    pub require_credentials: bool,
 }
 
 impl<'a> RootModuleConfig<'a> for AwsConfig<'a> {
@@ -19,6 +21,8 @@ impl<'a> RootModuleConfig<'a> for AwsConfig<'a> {
             style: "bold yellow",
             disabled: false,
             region_aliases: HashMap::new(),
+            profile_aliases: HashMap::new(),
+            require_credentials: false,
         }
     }
 }
@@ -0,0 +1,24 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
+
+#[derive(Clone, ModuleConfig)]
+pub struct AzureConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+    pub subscription_aliases: HashMap<String, &'a str>,
+}
+
+impl<'a> RootModuleConfig<'a> for AzureConfig<'a> {
+    fn new() -> Self {
+        AzureConfig {
+            format: "on [$symbol($subscription)]($style) ",
+            symbol: "ﴃ ",
+            style: "blue bold",
+            disabled: false,
+            subscription_aliases: HashMap::new(),
+        }
+    }
+}
@@ -26,14 +26,91 @@ impl<'a> RootModuleConfig<'a> for BatteryConfig<'a> {
             display: vec![BatteryDisplayConfig {
                 threshold: 10,
                 style: "red bold",
+                charging_symbol: None,
+                discharging_symbol: None,
             }],
             disabled: false,
         }
     }
 }
 
-#[derive(Clone, ModuleConfig)]
+/// A rule of `[[battery.display]]`, which applies when the charge is at most `threshold`
+#[derive(Clone)]
 pub struct BatteryDisplayConfig<'a> {
     pub threshold: i64,
     pub style: &'a str,
+    pub charging_symbol: Option<&'a str>,
+    pub discharging_symbol: Option<&'a str>,
+}
+
+impl<'a> ModuleConfig<'a> for BatteryDisplayConfig<'a> {
+    fn from_config(config: &'a toml::Value) -> Option<Self> {
+        let config = config.as_table()?;
+        // The symbols are optional, and default to the symbols of the module
+        let get_symbol = |key| config.get(key).and_then(<&str>::from_config);
+
+        Some(BatteryDisplayConfig {
+            threshold: config.get("threshold").and_then(i64::from_config)?,
+            style: config.get("style").and_then(<&str>::from_config)?,
+            charging_symbol: get_symbol("charging_symbol"),
+            discharging_symbol: get_symbol("discharging_symbol"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_symbols() {
+        let config = toml::toml! {
+            [[display]]
+            threshold = 30
+            style = "yellow"
+            charging_symbol = "⚡️ "
+            discharging_symbol = "💀 "
+        };
+        let config = BatteryConfig::load(&config);
+
+        assert_eq!(config.display.len(), 1);
+        let display = &config.display[0];
+        assert_eq!(display.threshold, 30);
+        assert_eq!(display.style, "yellow");
+        assert_eq!(display.charging_symbol, Some("⚡️ "));
+        assert_eq!(display.discharging_symbol, Some("💀 "));
+    }
+
+    #[test]
+    fn display_without_symbols() {
+        let config = toml::toml! {
+            [[display]]
+            threshold = 10
+            style = "bold red"
+
+            [[display]]
+            threshold = 30
+            style = "bold yellow"
+            discharging_symbol = "💦 "
+        };
+        let config = BatteryConfig::load(&config);
+
+        assert_eq!(config.display.len(), 2);
+        assert_eq!(config.display[0].threshold, 10);
+        assert_eq!(config.display[0].style, "bold red");
+        assert_eq!(config.display[0].charging_symbol, None);
+        assert_eq!(config.display[0].discharging_symbol, None);
+        assert_eq!(config.display[1].charging_symbol, None);
+        assert_eq!(config.display[1].discharging_symbol, Some("💦 "));
+    }
+
+    #[test]
+    fn display_without_threshold() {
+        let config = toml::toml! {
+            style = "bold red"
+            charging_symbol = "⚡️ "
+        };
+
+        assert!(BatteryDisplayConfig::from_config(&config).is_none());
+    }
 }
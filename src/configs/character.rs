@@ -7,7 +7,11 @@ pub struct CharacterConfig<'a> {
     pub format: &'a str,
     pub success_symbol: &'a str,
     pub error_symbol: &'a str,
+    pub cmdlet_error_symbol: &'a str,
     pub vicmd_symbol: &'a str,
+    pub root_symbol: &'a str,
+    pub ssh_symbol: &'a str,
+    pub ssh_root_symbol: &'a str,
     pub disabled: bool,
 }
 
@@ -15,9 +19,13 @@ impl<'a> RootModuleConfig<'a> for CharacterConfig<'a> {
     fn new() -> Self {
         CharacterConfig {
             format: "$symbol ",
-            success_symbol: "[❯](bold green)",
-            error_symbol: "[❯](bold red)",
-            vicmd_symbol: "[❮](bold green)",
+            success_symbol: "[❯](bold success)",
+            error_symbol: "[❯](bold error)",
+            cmdlet_error_symbol: "[❯](bold warning)",
+            vicmd_symbol: "[❮](bold success)",
+            root_symbol: "",
+            ssh_symbol: "",
+            ssh_root_symbol: "",
             disabled: false,
         }
     }
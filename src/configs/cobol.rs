@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct CobolConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for CobolConfig<'a> {
+    fn new() -> Self {
+        CobolConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "⚙️ ",
+            style: "bold blue",
+            detect_extensions: vec!["cob", "COB", "cbl", "CBL"],
+            detect_files: vec![],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct ContainerConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for ContainerConfig<'a> {
+    fn new() -> Self {
+        ContainerConfig {
+            format: "[$symbol \\[$name\\]]($style) ",
+            symbol: "⬢",
+            style: "red bold dimmed",
+            disabled: false,
+        }
+    }
+}
@@ -11,12 +11,25 @@ pub struct Extensions<'a>(pub Vec<&'a str>);
 #[derive(Clone, Default, PartialEq)]
 pub struct Directories<'a>(pub Vec<&'a str>);
 
+/// How line breaks in the output of a custom command are rendered
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Multiline {
+    /// Join all lines with `multiline_separator`
+    Join,
+    /// Only keep the first line
+    FirstLine,
+    /// Keep the line breaks as they are
+    Keep,
+}
+
 #[derive(Clone, ModuleConfig)]
 pub struct CustomConfig<'a> {
     pub format: &'a str,
     pub symbol: &'a str,
     pub command: &'a str,
     pub when: Option<&'a str>,
+    pub when_exit_codes: Vec<i64>,
+    pub use_when_output: bool,
     pub shell: VecOr<&'a str>,
     pub description: &'a str,
     pub style: &'a str,
@@ -24,6 +37,10 @@ pub struct CustomConfig<'a> {
     pub files: Files<'a>,
     pub extensions: Extensions<'a>,
     pub directories: Directories<'a>,
+    pub multiline: Multiline,
+    pub multiline_separator: &'a str,
+    pub sandbox: bool,
+    pub sandbox_timeout: u64,
 }
 
 impl<'a> RootModuleConfig<'a> for CustomConfig<'a> {
@@ -33,6 +50,8 @@ impl<'a> RootModuleConfig<'a> for CustomConfig<'a> {
             symbol: "",
             command: "",
             when: None,
+            when_exit_codes: vec![0],
+            use_when_output: false,
             shell: VecOr::default(),
             description: "<custom config>",
             style: "green bold",
@@ -40,6 +59,24 @@ impl<'a> RootModuleConfig<'a> for CustomConfig<'a> {
             files: Files::default(),
             extensions: Extensions::default(),
             directories: Directories::default(),
+            multiline: Multiline::Join,
+            multiline_separator: " ",
+            sandbox: false,
+            sandbox_timeout: 500,
+        }
+    }
+}
+
+impl<'a> ModuleConfig<'a> for Multiline {
+    fn from_config(config: &'a toml::Value) -> Option<Self> {
+        match config.as_str()? {
+            "join" => Some(Multiline::Join),
+            "first_line" => Some(Multiline::FirstLine),
+            "keep" => Some(Multiline::Keep),
+            _ => {
+                log::debug!("Unexpected multiline mode {:?}", config);
+                None
+            }
         }
     }
 }
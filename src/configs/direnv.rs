@@ -0,0 +1,32 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct DirenvConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub allowed_symbol: &'a str,
+    pub not_allowed_symbol: &'a str,
+    pub denied_symbol: &'a str,
+    pub loaded_symbol: &'a str,
+    pub unloaded_symbol: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for DirenvConfig<'a> {
+    fn new() -> Self {
+        DirenvConfig {
+            format: "[$symbol$loaded/$allowed]($style) ",
+            symbol: "direnv ",
+            style: "bold bright-yellow",
+            allowed_symbol: "allowed",
+            not_allowed_symbol: "not allowed",
+            denied_symbol: "denied",
+            loaded_symbol: "loaded",
+            unloaded_symbol: "not loaded",
+            disabled: true,
+        }
+    }
+}
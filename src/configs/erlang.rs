@@ -5,8 +5,12 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct ErlangConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +18,12 @@ impl<'a> RootModuleConfig<'a> for ErlangConfig<'a> {
     fn new() -> Self {
         ErlangConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "${raw}",
             symbol: "🖧 ",
             style: "bold red",
+            detect_extensions: vec![],
+            detect_files: vec!["rebar.config", "erlang.mk"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct FennelConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for FennelConfig<'a> {
+    fn new() -> Self {
+        FennelConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "🧅 ",
+            style: "bold green",
+            detect_extensions: vec!["fnl"],
+            detect_files: vec![],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct FlutterConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for FlutterConfig<'a> {
+    fn new() -> Self {
+        FlutterConfig {
+            format: "via [$symbol$version( \\($channel\\))]($style) ",
+            version_format: "v${raw}",
+            symbol: "🐦 ",
+            style: "bold blue",
+            detect_extensions: vec![],
+            detect_files: vec![".metadata"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
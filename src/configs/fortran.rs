@@ -0,0 +1,32 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct FortranConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub compiler_binary: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for FortranConfig<'a> {
+    fn new() -> Self {
+        FortranConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "🅵 ",
+            style: "bold purple",
+            compiler_binary: "gfortran",
+            detect_extensions: vec!["f90", "F90", "f95", "F95", "f03", "F03", "f08", "F08"],
+            detect_files: vec!["fpm.toml"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -16,6 +16,8 @@ pub struct GitStatusConfig<'a> {
     pub modified: &'a str,
     pub staged: &'a str,
     pub untracked: &'a str,
+    pub summary: &'a str,
+    pub summarize_above: i64,
     pub disabled: bool,
 }
 
@@ -23,7 +25,7 @@ impl<'a> RootModuleConfig<'a> for GitStatusConfig<'a> {
     fn new() -> Self {
         GitStatusConfig {
             format: "([\\[$all_status$ahead_behind\\]]($style) )",
-            style: "red bold",
+            style: "error bold",
             stashed: "\\$",
             ahead: "⇡",
             behind: "⇣",
@@ -34,6 +36,8 @@ impl<'a> RootModuleConfig<'a> for GitStatusConfig<'a> {
             modified: "!",
             staged: "+",
             untracked: "?",
+            summary: "~$count files",
+            summarize_above: 0,
             disabled: false,
         }
     }
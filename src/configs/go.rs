@@ -5,8 +5,13 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct GoConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub not_capable_style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +19,20 @@ impl<'a> RootModuleConfig<'a> for GoConfig<'a> {
     fn new() -> Self {
         GoConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
             symbol: "🐹 ",
             style: "bold cyan",
+            not_capable_style: "bold red",
+            detect_extensions: vec!["go"],
+            detect_files: vec![
+                "go.mod",
+                "go.sum",
+                "glide.yaml",
+                "Gopkg.yml",
+                "Gopkg.lock",
+                ".go-version",
+            ],
+            detect_folders: vec!["Godeps"],
             disabled: false,
         }
     }
@@ -0,0 +1,22 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct GuixShellConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for GuixShellConfig<'a> {
+    fn new() -> Self {
+        GuixShellConfig {
+            format: "via [$symbol]($style) ",
+            symbol: "🐃 ",
+            style: "yellow bold",
+            disabled: false,
+        }
+    }
+}
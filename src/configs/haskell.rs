@@ -0,0 +1,34 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct HaskellConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub stack_symbol: &'a str,
+    pub cabal_symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for HaskellConfig<'a> {
+    fn new() -> Self {
+        HaskellConfig {
+            format: "via [$symbol$version( \\($tool\\))]($style) ",
+            version_format: "v${raw}",
+            symbol: "λ ",
+            stack_symbol: "λ ",
+            cabal_symbol: "λ ",
+            style: "bold red",
+            detect_extensions: vec!["hs", "lhs", "cabal"],
+            detect_files: vec!["stack.yaml", "cabal.project"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct HaxeConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for HaxeConfig<'a> {
+    fn new() -> Self {
+        HaxeConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "⌘ ",
+            style: "bold fg:202",
+            detect_extensions: vec!["hx"],
+            detect_files: vec!["haxe.json", "hxformat.json", ".haxerc"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
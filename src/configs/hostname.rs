@@ -5,6 +5,7 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct HostnameConfig<'a> {
     pub ssh_only: bool,
+    pub ssh_symbol: &'a str,
     pub trim_at: &'a str,
     pub format: &'a str,
     pub style: &'a str,
@@ -15,8 +16,9 @@ impl<'a> RootModuleConfig<'a> for HostnameConfig<'a> {
     fn new() -> Self {
         HostnameConfig {
             ssh_only: true,
+            ssh_symbol: "",
             trim_at: ".",
-            format: "on [$hostname]($style) ",
+            format: "on [$ssh_symbol$hostname]($style) ",
             style: "green dimmed bold",
             disabled: false,
         }
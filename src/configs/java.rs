@@ -4,8 +4,12 @@ use starship_module_config_derive::ModuleConfig;
 
 #[derive(Clone, ModuleConfig)]
 pub struct JavaConfig<'a> {
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
     pub format: &'a str,
+    pub version_format: &'a str,
     pub style: &'a str,
     pub symbol: &'a str,
 }
@@ -14,6 +18,17 @@ impl<'a> RootModuleConfig<'a> for JavaConfig<'a> {
     fn new() -> Self {
         JavaConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            detect_extensions: vec!["java", "class", "jar", "gradle"],
+            detect_files: vec![
+                "pom.xml",
+                "build.gradle.kts",
+                "build.sbt",
+                ".java-version",
+                "deps.edn",
+                "project.clj",
+            ],
+            detect_folders: vec![],
             disabled: false,
             style: "red dimmed",
             symbol: "☕ ",
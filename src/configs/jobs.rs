@@ -4,7 +4,9 @@ use starship_module_config_derive::ModuleConfig;
 
 #[derive(Clone, ModuleConfig)]
 pub struct JobsConfig<'a> {
-    pub threshold: i64,
+    pub threshold: Option<i64>,
+    pub symbol_threshold: i64,
+    pub number_threshold: i64,
     pub format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
@@ -14,7 +16,9 @@ pub struct JobsConfig<'a> {
 impl<'a> RootModuleConfig<'a> for JobsConfig<'a> {
     fn new() -> Self {
         JobsConfig {
-            threshold: 1,
+            threshold: None,
+            symbol_threshold: 1,
+            number_threshold: 2,
             format: "[$symbol$number]($style) ",
             symbol: "✦",
             style: "bold blue",
@@ -1,7 +1,6 @@
-use crate::config::{ModuleConfig, RootModuleConfig};
+use crate::config::{ModuleConfig, OrderedMap, RootModuleConfig};
 
 use starship_module_config_derive::ModuleConfig;
-use std::collections::HashMap;
 
 #[derive(Clone, ModuleConfig)]
 pub struct KubernetesConfig<'a> {
@@ -9,7 +8,10 @@ pub struct KubernetesConfig<'a> {
     pub format: &'a str,
     pub style: &'a str,
     pub disabled: bool,
-    pub context_aliases: HashMap<String, &'a str>,
+    pub context_aliases: OrderedMap<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub detect_extensions: Vec<&'a str>,
 }
 
 impl<'a> RootModuleConfig<'a> for KubernetesConfig<'a> {
@@ -19,7 +21,10 @@ impl<'a> RootModuleConfig<'a> for KubernetesConfig<'a> {
             format: "on [$symbol$context( \\($namespace\\))]($style) ",
             style: "cyan bold",
             disabled: true,
-            context_aliases: HashMap::new(),
+            context_aliases: OrderedMap::default(),
+            detect_files: vec![],
+            detect_folders: vec![],
+            detect_extensions: vec![],
         }
     }
 }
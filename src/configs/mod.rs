@@ -1,23 +1,41 @@
+pub mod android;
 pub mod aws;
+pub mod azure;
 pub mod battery;
+pub mod bazel;
+pub mod buf;
 pub mod character;
 pub mod cmake;
 pub mod cmd_duration;
+pub mod cobol;
 pub mod conda;
+pub mod container;
 pub mod crystal;
 pub mod custom;
+pub mod daml;
+pub mod dart;
 pub mod directory;
+pub mod direnv;
 pub mod docker_context;
 pub mod dotnet;
 pub mod elixir;
 pub mod elm;
 pub mod env_var;
 pub mod erlang;
+pub mod fennel;
+pub mod flutter;
+pub mod fortran;
+pub mod gcloud;
 pub mod git_branch;
 pub mod git_commit;
 pub mod git_state;
 pub mod git_status;
+pub mod gleam;
 pub mod go;
+pub mod gradle;
+pub mod guix_shell;
+pub mod haskell;
+pub mod haxe;
 pub mod hg_branch;
 pub mod hostname;
 pub mod java;
@@ -25,21 +43,33 @@ pub mod jobs;
 pub mod julia;
 pub mod kubernetes;
 pub mod memory_usage;
+pub mod meson;
+pub mod mojo;
 pub mod nim;
 pub mod nix_shell;
 pub mod nodejs;
 pub mod ocaml;
+pub mod openstack;
 pub mod package;
 pub mod php;
+pub mod project;
 pub mod purescript;
 pub mod python;
+pub mod raku;
+pub mod red;
+pub mod rlang;
 pub mod ruby;
 pub mod rust;
 pub mod singularity;
+pub mod solidity;
+pub mod spack;
 mod starship_root;
 pub mod terraform;
 pub mod time;
+pub mod typst;
 pub mod username;
+pub mod vagrant;
+pub mod vlang;
 pub mod zig;
 
 pub use starship_root::*;
@@ -5,8 +5,12 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct NimConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +18,12 @@ impl<'a> RootModuleConfig<'a> for NimConfig<'a> {
     fn new() -> Self {
         NimConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
             symbol: "👑 ",
             style: "yellow bold",
+            detect_extensions: vec!["nim", "nims", "nimble"],
+            detect_files: vec!["nim.cfg"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
@@ -5,17 +5,25 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct OCamlConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
 impl<'a> RootModuleConfig<'a> for OCamlConfig<'a> {
     fn new() -> Self {
         OCamlConfig {
-            format: "via [$symbol$version]($style) ",
+            format: "via [$symbol$version( \\($switch_name\\))]($style) ",
+            version_format: "v${raw}",
             symbol: "🐫 ",
             style: "bold yellow",
+            detect_extensions: vec!["opam", "ml", "mli", "re", "rei"],
+            detect_files: vec!["dune", "dune-project", "jbuild", "jbuild-ignore", ".merlin"],
+            detect_folders: vec!["_opam", "esy.lock"],
             disabled: false,
         }
     }
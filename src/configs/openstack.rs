@@ -0,0 +1,21 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct OpenStackConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for OpenStackConfig<'a> {
+    fn new() -> Self {
+        OpenStackConfig {
+            format: "on [$symbol$cloud(\\($project\\))]($style) ",
+            symbol: "☁️  ",
+            style: "bold yellow",
+            disabled: false,
+        }
+    }
+}
@@ -7,6 +7,10 @@ pub struct PhpConfig<'a> {
     pub symbol: &'a str,
     pub style: &'a str,
     pub format: &'a str,
+    pub version_format: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -16,6 +20,10 @@ impl<'a> RootModuleConfig<'a> for PhpConfig<'a> {
             symbol: "🐘 ",
             style: "147 bold",
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            detect_extensions: vec!["php"],
+            detect_files: vec!["composer.json", ".php-version"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
@@ -0,0 +1,22 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct ProjectConfig<'a> {
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for ProjectConfig<'a> {
+    fn new() -> Self {
+        ProjectConfig {
+            format: "in [$symbol$name]($style) ",
+            symbol: "⌂ ",
+            style: "bold white",
+            disabled: true,
+        }
+    }
+}
@@ -5,8 +5,12 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct PureScriptConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +18,12 @@ impl<'a> RootModuleConfig<'a> for PureScriptConfig<'a> {
     fn new() -> Self {
         PureScriptConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
             symbol: "<=> ",
             style: "bold white",
+            detect_extensions: vec!["purs"],
+            detect_files: vec!["spago.dhall", "spago.yaml"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
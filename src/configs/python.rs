@@ -1,15 +1,20 @@
-use crate::config::{ModuleConfig, RootModuleConfig};
+use crate::config::{ModuleConfig, RootModuleConfig, VecOr};
 
 use starship_module_config_derive::ModuleConfig;
 
 #[derive(Clone, ModuleConfig)]
 pub struct PythonConfig<'a> {
     pub pyenv_version_name: bool,
-    pub python_binary: &'a str,
+    pub pyenv_prefix: &'a str,
+    pub python_binary: VecOr<&'a str>,
     pub scan_for_pyfiles: bool,
     pub format: &'a str,
+    pub version_format: &'a str,
     pub style: &'a str,
     pub symbol: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -17,11 +22,24 @@ impl<'a> RootModuleConfig<'a> for PythonConfig<'a> {
     fn new() -> Self {
         PythonConfig {
             pyenv_version_name: false,
-            python_binary: "python",
+            pyenv_prefix: "pyenv ",
+            python_binary: VecOr(vec!["python", "python3", "python2"]),
             scan_for_pyfiles: true,
-            format: "via [$symbol$version( \\($virtualenv\\))]($style) ",
+            format: "via [$symbol$pyenv_prefix$version( \\($virtualenv\\))]($style) ",
+            version_format: "v${raw}",
             style: "yellow bold",
             symbol: "🐍 ",
+            detect_extensions: vec!["py"],
+            detect_files: vec![
+                "requirements.txt",
+                ".python-version",
+                "pyproject.toml",
+                "Pipfile",
+                "tox.ini",
+                "setup.py",
+                "__init__.py",
+            ],
+            detect_folders: vec![],
             disabled: false,
         }
     }
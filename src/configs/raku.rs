@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct RakuConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for RakuConfig<'a> {
+    fn new() -> Self {
+        RakuConfig {
+            format: "via [$symbol$version-$vm_version]($style) ",
+            version_format: "v${raw}",
+            symbol: "🦋 ",
+            style: "149 bold",
+            detect_extensions: vec!["p6", "pm6", "pod6", "raku", "rakumod"],
+            detect_files: vec!["META6.json"],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct RLangConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for RLangConfig<'a> {
+    fn new() -> Self {
+        RLangConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "📐 ",
+            style: "blue bold",
+            detect_extensions: vec!["R", "r", "Rmd", "rmd", "Rproj"],
+            detect_files: vec!["DESCRIPTION", ".Rprofile"],
+            detect_folders: vec![".Rproj.user"],
+            disabled: false,
+        }
+    }
+}
@@ -5,8 +5,12 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct RubyConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +18,12 @@ impl<'a> RootModuleConfig<'a> for RubyConfig<'a> {
     fn new() -> Self {
         RubyConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
             symbol: "💎 ",
             style: "bold red",
+            detect_extensions: vec!["rb"],
+            detect_files: vec!["Gemfile", ".ruby-version"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
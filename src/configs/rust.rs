@@ -5,8 +5,13 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct RustConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub nightly_style: Option<&'a str>,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +19,13 @@ impl<'a> RootModuleConfig<'a> for RustConfig<'a> {
     fn new() -> Self {
         RustConfig {
             format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
             symbol: "🦀 ",
             style: "bold red",
+            nightly_style: None,
+            detect_extensions: vec!["rs"],
+            detect_files: vec!["Cargo.toml"],
+            detect_folders: vec![],
             disabled: false,
         }
     }
@@ -0,0 +1,32 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct SolidityConfig<'a> {
+    pub format: &'a str,
+    pub version_format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub compiler: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for SolidityConfig<'a> {
+    fn new() -> Self {
+        SolidityConfig {
+            format: "via [$symbol$version]($style) ",
+            version_format: "v${raw}",
+            symbol: "S ",
+            style: "bold blue",
+            compiler: "solc",
+            detect_extensions: vec!["sol"],
+            detect_files: vec![],
+            detect_folders: vec![],
+            disabled: false,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::config::{ModuleConfig, RootModuleConfig};
+
+use starship_module_config_derive::ModuleConfig;
+
+#[derive(Clone, ModuleConfig)]
+pub struct SpackConfig<'a> {
+    pub truncation_length: usize,
+    pub format: &'a str,
+    pub symbol: &'a str,
+    pub style: &'a str,
+    pub disabled: bool,
+}
+
+impl<'a> RootModuleConfig<'a> for SpackConfig<'a> {
+    fn new() -> Self {
+        SpackConfig {
+            truncation_length: 1,
+            format: "via [$symbol$environment]($style) ",
+            symbol: "🅢 ",
+            style: "blue bold",
+            disabled: false,
+        }
+    }
+}
@@ -1,20 +1,62 @@
 use crate::config::{ModuleConfig, RootModuleConfig};
 
 use starship_module_config_derive::ModuleConfig;
+use std::collections::HashMap;
+
+/// A named set of colors, mapping color names to color strings
+pub type Palette<'a> = HashMap<String, &'a str>;
+
+/// The texts decorating a group of modules (`$[name](module module ...)`) in `format`
+#[derive(Clone, Default)]
+pub struct GroupConfig<'a> {
+    pub prefix: &'a str,
+    pub separator: &'a str,
+    pub suffix: &'a str,
+}
+
+impl<'a> ModuleConfig<'a> for GroupConfig<'a> {
+    fn from_config(config: &'a toml::Value) -> Option<Self> {
+        let config = config.as_table()?;
+        let get = |key| config.get(key).and_then(<&str>::from_config).unwrap_or("");
+
+        Some(GroupConfig {
+            prefix: get("prefix"),
+            separator: get("separator"),
+            suffix: get("suffix"),
+        })
+    }
+}
 
 #[derive(Clone, ModuleConfig)]
 pub struct StarshipRootConfig<'a> {
     pub format: &'a str,
     pub scan_timeout: u64,
+    pub scan_repo_root: bool,
+    pub follow_symlinks: bool,
+    pub use_tool_versions: bool,
+    pub palette: Option<&'a str>,
+    pub palettes: HashMap<String, Palette<'a>>,
+    pub screen_reader: bool,
+    pub state_symbols: HashMap<String, &'a str>,
+    pub collapse_empty_lines: bool,
+    pub error_style_overlay: Option<&'a str>,
+    pub error_style_modules: Vec<&'a str>,
+    pub groups: HashMap<String, GroupConfig<'a>>,
+    pub aliases: HashMap<String, &'a str>,
+    pub min_contrast: f64,
+    pub graphics_protocol: &'a str,
+    pub symbol_images: HashMap<String, &'a str>,
+    pub ambiguous_width: usize,
 }
 
 // List of default prompt order
 // NOTE: If this const value is changed then Default prompt order subheading inside
 // prompt heading of config docs needs to be updated according to changes made here.
-pub const PROMPT_ORDER: [&str; 43] = [
+pub const PROMPT_ORDER: [&str; 73] = [
     "username",
     "hostname",
     "singularity",
+    "container",
     "kubernetes",
     "directory",
     "git_branch",
@@ -24,31 +66,60 @@ pub const PROMPT_ORDER: [&str; 43] = [
     "hg_branch",
     "docker_context",
     "package",
+    "project",
     // ↓ Toolchain version modules ↓
     // (Let's keep these sorted alphabetically)
+    "android",
+    "bazel",
+    "buf",
     "cmake",
+    "cobol",
+    "daml",
+    "dart",
     "dotnet",
     "elixir",
     "elm",
     "erlang",
+    "fennel",
+    "fortran",
+    "flutter",
+    "gleam",
     "golang",
+    "gradle",
+    "haskell",
+    "haxe",
     "java",
     "julia",
+    "meson",
+    "mojo",
     "nim",
     "nodejs",
     "ocaml",
     "php",
     "purescript",
     "python",
+    "raku",
+    "red",
+    "rlang",
     "ruby",
     "rust",
+    "solidity",
     "terraform",
+    "typst",
+    "vagrant",
+    "vlang",
     "zig",
     // ↑ Toolchain version modules ↑
     "nix_shell",
+    "guix_shell",
     "conda",
+    "spack",
     "memory_usage",
     "aws",
+    "gcloud",
+    "azure",
+    "openstack",
+    "direnv",
     "env_var",
     "crystal",
     "cmd_duration",
@@ -66,6 +137,22 @@ impl<'a> RootModuleConfig<'a> for StarshipRootConfig<'a> {
         StarshipRootConfig {
             format: "\n$all",
             scan_timeout: 30,
+            scan_repo_root: false,
+            follow_symlinks: true,
+            use_tool_versions: true,
+            palette: None,
+            palettes: HashMap::new(),
+            screen_reader: false,
+            state_symbols: HashMap::new(),
+            collapse_empty_lines: false,
+            error_style_overlay: None,
+            error_style_modules: Vec::new(),
+            groups: HashMap::new(),
+            aliases: HashMap::new(),
+            min_contrast: 0.0,
+            graphics_protocol: "none",
+            symbol_images: HashMap::new(),
+            ambiguous_width: 1,
         }
     }
 }
@@ -5,8 +5,12 @@ use starship_module_config_derive::ModuleConfig;
 #[derive(Clone, ModuleConfig)]
 pub struct TerraformConfig<'a> {
     pub format: &'a str,
+    pub version_format: &'a str,
     pub symbol: &'a str,
     pub style: &'a str,
+    pub detect_extensions: Vec<&'a str>,
+    pub detect_files: Vec<&'a str>,
+    pub detect_folders: Vec<&'a str>,
     pub disabled: bool,
 }
 
@@ -14,8 +18,12 @@ impl<'a> RootModuleConfig<'a> for TerraformConfig<'a> {
     fn new() -> Self {
         TerraformConfig {
             format: "via [$symbol$workspace]($style) ",
+            version_format: "v${raw}",
             symbol: "💠 ",
             style: "bold 105",
+            detect_extensions: vec!["tf"],
+            detect_files: vec![],
+            detect_folders: vec![".terraform"],
             disabled: false,
         }
     }
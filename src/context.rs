@@ -1,15 +1,20 @@
 use crate::config::StarshipConfig;
+use crate::metrics::Metrics;
 use crate::module::Module;
 
 use crate::modules;
+use crate::tool_versions::ToolVersions;
+use crate::utils::{self, CommandOutput};
 use clap::ArgMatches;
-use git2::{ErrorCode::UnbornBranch, Repository, RepositoryState};
+use git2::{ErrorCode::UnbornBranch, Repository, RepositoryOpenFlags, RepositoryState};
 use once_cell::sync::OnceCell;
 use std::collections::{HashMap, HashSet};
 use std::env;
+use std::ffi::OsStr;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::string::String;
+use std::sync::Mutex;
 use std::time::{Duration, SystemTime};
 
 /// Context contains data or common methods that may be used by multiple modules.
@@ -25,14 +30,41 @@ pub struct Context<'a> {
     /// A struct containing directory contents in a lookup-optimised format.
     dir_contents: OnceCell<DirContents>,
 
+    /// The contents of the root of the repository, if it isn't the current directory.
+    repo_root_contents: OnceCell<Option<DirContents>>,
+
     /// Properties to provide to modules.
     pub properties: HashMap<&'a str, String>,
 
     /// Private field to store Git information for modules who need it
     repo: OnceCell<Repo>,
 
+    /// The colors of the palette selected in the configuration
+    palette: OnceCell<HashMap<String, String>>,
+
+    /// Whether the root `screen_reader` option is set, read when first needed
+    screen_reader: OnceCell<bool>,
+
+    /// The root `min_contrast` option, read when first needed
+    min_contrast: OnceCell<f64>,
+
+    /// The validated root `ambiguous_width` option, read when first needed
+    ambiguous_width: OnceCell<usize>,
+
+    /// Cache of the programs looked up in `$PATH`, and whether they were found
+    programs: Mutex<HashMap<String, bool>>,
+
+    /// The user id of the user running starship, looked up when first needed
+    uid: OnceCell<Option<u32>>,
+
+    /// The versions pinned in `.tool-versions` or `mise.toml` files, read when first needed
+    tool_versions: OnceCell<ToolVersions>,
+
     /// The shell the user is assumed to be running
     pub shell: Shell,
+
+    /// Module timings and cache counters, written out with `--metrics-file`
+    pub metrics: Metrics,
 }
 
 impl<'a> Context<'a> {
@@ -58,8 +90,14 @@ impl<'a> Context<'a> {
     where
         T: Into<PathBuf>,
     {
-        let config = StarshipConfig::initialize();
+        Context::new_with_config(arguments, dir, StarshipConfig::initialize())
+    }
 
+    /// Create a new instance of Context for the provided directory and configuration
+    pub fn new_with_config<T>(arguments: ArgMatches, dir: T, config: StarshipConfig) -> Context
+    where
+        T: Into<PathBuf>,
+    {
         // Unwrap the clap arguments into a simple hashtable
         // we only care about single arguments at this point, there isn't a
         // use-case for a list of arguments yet.
@@ -80,8 +118,17 @@ impl<'a> Context<'a> {
             properties,
             current_dir,
             dir_contents: OnceCell::new(),
+            repo_root_contents: OnceCell::new(),
             repo: OnceCell::new(),
+            palette: OnceCell::new(),
+            screen_reader: OnceCell::new(),
+            min_contrast: OnceCell::new(),
+            ambiguous_width: OnceCell::new(),
+            programs: Mutex::new(HashMap::new()),
+            uid: OnceCell::new(),
+            tool_versions: OnceCell::new(),
             shell,
+            metrics: Metrics::default(),
         }
     }
 
@@ -126,20 +173,42 @@ impl<'a> Context<'a> {
     pub fn try_begin_scan(&'a self) -> Option<ScanDir<'a>> {
         Some(ScanDir {
             dir_contents: self.dir_contents().ok()?,
+            repo_root_contents: None,
             files: &[],
             folders: &[],
             extensions: &[],
         })
     }
 
+    /// Returns a new ScanDir for the detection of a module, which also checks the root
+    /// of the repository if `scan_repo_root` is enabled for the module
+    pub fn try_begin_module_scan(&'a self, name: &str) -> Option<ScanDir<'a>> {
+        let mut scan = self.try_begin_scan()?;
+        if self.is_repo_root_scanned(name) {
+            scan.repo_root_contents = self.repo_root_contents();
+        }
+        Some(scan)
+    }
+
+    /// Whether the detection of a module checks the root of the repository, from the
+    /// `scan_repo_root` option of the module, falling back to the root option
+    fn is_repo_root_scanned(&self, name: &str) -> bool {
+        self.config
+            .get_module_config(name)
+            .and_then(|table| table.as_table()?.get("scan_repo_root")?.as_bool())
+            .unwrap_or_else(|| self.config.get_root_config().scan_repo_root)
+    }
+
     /// Will lazily get repo root and branch when a module requests it.
     pub fn get_repo(&self) -> Result<&Repo, std::io::Error> {
+        self.metrics.record_cache("repo", self.repo.get().is_some());
         self.repo
             .get_or_try_init(|| -> Result<Repo, std::io::Error> {
                 let repository = if env::var("GIT_DIR").is_ok() {
                     Repository::open_from_env().ok()
                 } else {
-                    Repository::discover(&self.current_dir).ok()
+                    let follow_symlinks = self.config.get_root_config().follow_symlinks;
+                    discover_repository(&self.current_dir, follow_symlinks)
                 };
                 let branch = repository
                     .as_ref()
@@ -148,22 +217,176 @@ impl<'a> Context<'a> {
                     .as_ref()
                     .and_then(|repo| repo.workdir().map(Path::to_path_buf));
                 let state = repository.as_ref().map(|repo| repo.state());
+                let remote_url = repository.as_ref().and_then(|repo| {
+                    let remote = repo.find_remote("origin").ok()?;
+                    remote.url().map(String::from)
+                });
 
                 Ok(Repo {
                     branch,
                     root,
                     state,
+                    remote_url,
                 })
             })
     }
 
+    /// The version of a tool pinned in the `.tool-versions` or `mise.toml` of the current
+    /// directory or its parents, under any of the names in `tools`. Always `None` if
+    /// `use_tool_versions` is disabled for the module, falling back to the root option.
+    pub fn get_pinned_version(&self, name: &str, tools: &[&str]) -> Option<&str> {
+        let enabled = self
+            .config
+            .get_module_config(name)
+            .and_then(|table| table.as_table()?.get("use_tool_versions")?.as_bool())
+            .unwrap_or_else(|| self.config.get_root_config().use_tool_versions);
+        if !enabled {
+            return None;
+        }
+
+        self.metrics
+            .record_cache("tool_versions", self.tool_versions.get().is_some());
+        self.tool_versions
+            .get_or_init(|| ToolVersions::from_dir(&self.current_dir))
+            .get(tools)
+    }
+
+    /// Will lazily resolve the palette selected with the root `palette` option.
+    pub fn get_palette(&self) -> &HashMap<String, String> {
+        self.palette.get_or_init(|| self.config.get_palette())
+    }
+
+    /// Whether the prompt should be rendered for a screen reader, without any styling
+    pub fn is_screen_reader(&self) -> bool {
+        *self
+            .screen_reader
+            .get_or_init(|| self.config.get_root_config().screen_reader)
+    }
+
+    /// The contrast ratio styles are adjusted to have at least, from the root
+    /// `min_contrast` option
+    pub fn min_contrast(&self) -> f64 {
+        *self
+            .min_contrast
+            .get_or_init(|| self.config.get_root_config().min_contrast)
+    }
+
+    /// Whether starship is running as the root user (UID 0)
+    pub fn is_root(&self) -> bool {
+        let uid = self.uid.get_or_init(|| {
+            utils::exec_cmd("id", &["-u"])?
+                .stdout
+                .trim()
+                .parse::<u32>()
+                .ok()
+        });
+        *uid == Some(0)
+    }
+
+    /// The outcome of the previously run command, from `--status` and `--cmd-success`.
+    /// PowerShell reports failed cmdlets through `$?` only, while `$LASTEXITCODE` keeps
+    /// the exit-code of the last native command, so `$?` takes precedence.
+    pub fn command_status(&self) -> CommandStatus {
+        let cmd_success = self.properties.get("cmd_success").and_then(|success| {
+            match success.to_lowercase().as_str() {
+                "true" => Some(true),
+                "false" => Some(false),
+                _ => None,
+            }
+        });
+        let exit_code_success = self
+            .properties
+            .get("status_code")
+            .map_or(true, |status_code| status_code == "0");
+
+        match (cmd_success, exit_code_success) {
+            (Some(true), _) | (None, true) => CommandStatus::Success,
+            (Some(false), true) => CommandStatus::CmdletError,
+            (_, false) => CommandStatus::Error,
+        }
+    }
+
+    /// Whether the prompt is rendered in an SSH session (`$SSH_CONNECTION` or `$SSH_TTY`
+    /// is set)
+    pub fn is_ssh_session(&self) -> bool {
+        env::var_os("SSH_CONNECTION").is_some() || env::var_os("SSH_TTY").is_some()
+    }
+
+    /// How many columns characters of ambiguous East Asian width take, from the root
+    /// `ambiguous_width` option
+    pub fn ambiguous_width(&self) -> usize {
+        *self
+            .ambiguous_width
+            .get_or_init(|| match self.config.get_root_config().ambiguous_width {
+                width @ 1..=2 => width,
+                width => {
+                    log::warn!("Invalid ambiguous_width {}, expected 1 or 2", width);
+                    1
+                }
+            })
+    }
+
+    /// Whether `program` can be found in `$PATH`. Lookups are cached, so that modules
+    /// looking for the same program don't scan `$PATH` again.
+    pub fn is_program_available(&self, program: &str) -> bool {
+        if let Some(available) = self.programs.lock().unwrap().get(program) {
+            self.metrics.record_cache("programs", true);
+            return *available;
+        }
+        self.metrics.record_cache("programs", false);
+
+        let available = utils::is_program_available(program);
+        self.programs
+            .lock()
+            .unwrap()
+            .insert(program.to_string(), available);
+        available
+    }
+
+    /// Execute a command like `utils::exec_cmd`, without trying to spawn programs
+    /// which aren't installed.
+    pub fn exec_cmd(&self, cmd: &str, args: &[&str]) -> Option<CommandOutput> {
+        if !self.is_program_available(cmd) {
+            log::trace!("Program {:?} was not found in $PATH", cmd);
+            return None;
+        }
+        utils::exec_cmd(cmd, args)
+    }
+
     pub fn dir_contents(&self) -> Result<&DirContents, std::io::Error> {
+        self.metrics
+            .record_cache("dir_contents", self.dir_contents.get().is_some());
         self.dir_contents.get_or_try_init(|| {
-            let timeout = Duration::from_millis(self.config.get_root_config().scan_timeout);
-            DirContents::from_path_with_timeout(&self.current_dir, timeout)
+            let root_config = self.config.get_root_config();
+            let timeout = Duration::from_millis(root_config.scan_timeout);
+            DirContents::from_path_with_timeout(
+                &self.current_dir,
+                timeout,
+                root_config.follow_symlinks,
+            )
         })
     }
 
+    /// The contents of the root of the repository containing the current directory.
+    /// `None` outside of a repository, or when the current directory is the root.
+    fn repo_root_contents(&self) -> Option<&DirContents> {
+        self.metrics.record_cache(
+            "repo_root_contents",
+            self.repo_root_contents.get().is_some(),
+        );
+        self.repo_root_contents
+            .get_or_init(|| {
+                let root = self.get_repo().ok()?.root.as_ref()?;
+                if root == &self.current_dir {
+                    return None;
+                }
+                let root_config = self.config.get_root_config();
+                let timeout = Duration::from_millis(root_config.scan_timeout);
+                DirContents::from_path_with_timeout(root, timeout, root_config.follow_symlinks).ok()
+            })
+            .as_ref()
+    }
+
     fn get_shell() -> Shell {
         let shell = std::env::var("STARSHIP_SHELL").unwrap_or_default();
         match shell.as_str() {
@@ -192,10 +415,16 @@ pub struct DirContents {
 impl DirContents {
     #[cfg(test)]
     fn from_path(base: &PathBuf) -> Result<Self, std::io::Error> {
-        Self::from_path_with_timeout(base, Duration::from_secs(30))
+        Self::from_path_with_timeout(base, Duration::from_secs(30), true)
     }
 
-    fn from_path_with_timeout(base: &PathBuf, timeout: Duration) -> Result<Self, std::io::Error> {
+    /// Reads the entries of `base`. Unless `follow_symlinks` is set, symlinks are counted as
+    /// files without looking at their targets.
+    fn from_path_with_timeout(
+        base: &PathBuf,
+        timeout: Duration,
+        follow_symlinks: bool,
+    ) -> Result<Self, std::io::Error> {
         let start = SystemTime::now();
 
         let mut folders: HashSet<PathBuf> = HashSet::new();
@@ -208,7 +437,15 @@ impl DirContents {
             .filter_map(Result::ok)
             .for_each(|entry| {
                 let path = PathBuf::from(entry.path().strip_prefix(base).unwrap());
-                if entry.path().is_dir() {
+                let is_dir = if follow_symlinks {
+                    entry.path().is_dir()
+                } else {
+                    entry
+                        .file_type()
+                        .map(|file_type| file_type.is_dir())
+                        .unwrap_or(false)
+                };
+                if is_dir {
                     folders.insert(path);
                 } else {
                     if !path.to_string_lossy().starts_with('.') {
@@ -239,10 +476,6 @@ impl DirContents {
         self.files.iter()
     }
 
-    pub fn has_file(&self, path: &str) -> bool {
-        self.files.contains(Path::new(path))
-    }
-
     pub fn has_file_name(&self, name: &str) -> bool {
         self.file_names.contains(name)
     }
@@ -279,12 +512,46 @@ pub struct Repo {
 
     /// State
     pub state: Option<RepositoryState>,
+
+    /// The URL of the `origin` remote, if there is one
+    pub remote_url: Option<String>,
+}
+
+impl Repo {
+    /// The name of the repository, taken from the URL of the `origin` remote,
+    /// falling back to the name of the top-level directory
+    pub fn name(&self) -> Option<String> {
+        self.remote_url
+            .as_deref()
+            .and_then(repo_name_from_url)
+            .or_else(|| {
+                let root = self.root.as_ref()?;
+                Some(root.file_name()?.to_string_lossy().into_owned())
+            })
+    }
+}
+
+/// Extract the repository name from a remote URL, e.g. `starship` from
+/// `git@github.com:starship/starship.git`
+fn repo_name_from_url(url: &str) -> Option<String> {
+    let name = url
+        .trim_end_matches('/')
+        .rsplit(&['/', ':', '\\'][..])
+        .next()?;
+    let name = name.trim_end_matches(".git");
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
 }
 
 // A struct of Criteria which will be used to verify current PathBuf is
 // of X language, criteria can be set via the builder pattern
 pub struct ScanDir<'a> {
     dir_contents: &'a DirContents,
+    repo_root_contents: Option<&'a DirContents>,
     files: &'a [&'a str],
     folders: &'a [&'a str],
     extensions: &'a [&'a str],
@@ -308,11 +575,66 @@ impl<'a> ScanDir<'a> {
 
     /// based on the current Pathbuf check to see
     /// if any of this criteria match or exist and returning a boolean
+    ///
+    /// Criteria starting with `!` are negative: if any of them match, the directory is
+    /// not a match even if other criteria are.
     pub fn is_match(&self) -> bool {
-        self.dir_contents.has_any_extension(self.extensions)
-            || self.dir_contents.has_any_folder(self.folders)
-            || self.dir_contents.has_any_file_name(self.files)
+        let (files, excluded_files) = split_negative_patterns(self.files);
+        let (extensions, excluded_extensions) = split_negative_patterns(self.extensions);
+        let (folders, excluded_folders) = split_negative_patterns(self.folders);
+
+        let contents: Vec<&DirContents> = std::iter::once(self.dir_contents)
+            .chain(self.repo_root_contents)
+            .collect();
+        let is_excluded = contents.iter().any(|dir_contents| {
+            dir_contents.has_any_extension(&excluded_extensions)
+                || dir_contents.has_any_folder(&excluded_folders)
+                || dir_contents.has_any_file_name(&excluded_files)
+        });
+
+        !is_excluded
+            && contents.iter().any(|dir_contents| {
+                dir_contents.has_any_extension(&extensions)
+                    || dir_contents.has_any_folder(&folders)
+                    || dir_contents.has_any_file_name(&files)
+            })
+    }
+}
+
+/// Splits detection criteria into the positive ones and the negative ones, which start
+/// with `!`, e.g. `["package.json", "!deno.json"]` into `["package.json"]` and `["deno.json"]`
+fn split_negative_patterns<'a>(patterns: &[&'a str]) -> (Vec<&'a str>, Vec<&'a str>) {
+    let mut positive = Vec::new();
+    let mut negative = Vec::new();
+    for pattern in patterns {
+        match pattern.strip_prefix('!') {
+            Some(pattern) => negative.push(pattern),
+            None => positive.push(*pattern),
+        }
     }
+    (positive, negative)
+}
+
+/// Finds the repository containing `dir`. Unless `follow_symlinks` is set, the search stops
+/// at the first symlinked directory, without opening the repository it points into.
+fn discover_repository(dir: &Path, follow_symlinks: bool) -> Option<Repository> {
+    if follow_symlinks {
+        return Repository::discover(dir).ok();
+    }
+
+    for ancestor in dir.ancestors() {
+        let is_symlink = fs::symlink_metadata(ancestor)
+            .map(|metadata| metadata.file_type().is_symlink())
+            .unwrap_or(true);
+        if is_symlink {
+            return None;
+        }
+        let flags = RepositoryOpenFlags::NO_SEARCH;
+        if let Ok(repository) = Repository::open_ext(ancestor, flags, &[] as &[&OsStr]) {
+            return Some(repository);
+        }
+    }
+    None
 }
 
 fn get_current_branch(repository: &Repository) -> Option<String> {
@@ -345,6 +667,15 @@ fn get_current_branch(repository: &Repository) -> Option<String> {
     shorthand.map(std::string::ToString::to_string)
 }
 
+/// The outcome of the previously run command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandStatus {
+    Success,
+    Error,
+    /// A PowerShell cmdlet failed, without changing the exit-code
+    CmdletError,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Shell {
     Bash,
@@ -379,6 +710,7 @@ mod tests {
         assert_eq!(
             ScanDir {
                 dir_contents: &empty_dc,
+                repo_root_contents: None,
                 files: &["package.json"],
                 extensions: &["js"],
                 folders: &["node_modules"],
@@ -393,6 +725,7 @@ mod tests {
         assert_eq!(
             ScanDir {
                 dir_contents: &rust_dc,
+                repo_root_contents: None,
                 files: &["package.json"],
                 extensions: &["js"],
                 folders: &["node_modules"],
@@ -407,6 +740,7 @@ mod tests {
         assert_eq!(
             ScanDir {
                 dir_contents: &java_dc,
+                repo_root_contents: None,
                 files: &["package.json"],
                 extensions: &["js"],
                 folders: &["node_modules"],
@@ -421,6 +755,7 @@ mod tests {
         assert_eq!(
             ScanDir {
                 dir_contents: &node_dc,
+                repo_root_contents: None,
                 files: &["package.json"],
                 extensions: &["js"],
                 folders: &["node_modules"],
@@ -430,6 +765,189 @@ mod tests {
         );
         node.close()?;
 
+        let mojo = testdir(&["README.md", "main.🔥"])?;
+        let mojo_dc = DirContents::from_path(&PathBuf::from(mojo.path()))?;
+        assert_eq!(
+            ScanDir {
+                dir_contents: &mojo_dc,
+                repo_root_contents: None,
+                files: &[],
+                extensions: &["mojo", "🔥"],
+                folders: &[],
+            }
+            .is_match(),
+            true
+        );
+        mojo.close()?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_dir_with_repo_root() -> Result<(), Box<dyn std::error::Error>> {
+        let root = testdir(&["Cargo.toml", "src/deep/nested/mod.rs"])?;
+        let root_dc = DirContents::from_path(&PathBuf::from(root.path()))?;
+        let nested_dc = DirContents::from_path(&root.path().join("src/deep"))?;
+
+        let scan = |repo_root_contents| ScanDir {
+            dir_contents: &nested_dc,
+            repo_root_contents,
+            files: &["Cargo.toml"],
+            extensions: &[],
+            folders: &[],
+        };
+        assert_eq!(scan(None).is_match(), false);
+        assert_eq!(scan(Some(&root_dc)).is_match(), true);
+
+        root.close()?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_scan_dir_with_negative_patterns() -> Result<(), Box<dyn std::error::Error>> {
+        let node = testdir(&["package.json", "index.ts"])?;
+        let node_dc = DirContents::from_path(&PathBuf::from(node.path()))?;
+        let deno = testdir(&["package.json", "deno.json", "index.ts"])?;
+        let deno_dc = DirContents::from_path(&PathBuf::from(deno.path()))?;
+
+        let scan = |dir_contents| ScanDir {
+            dir_contents,
+            repo_root_contents: None,
+            files: &["package.json", "!deno.json"],
+            extensions: &["ts"],
+            folders: &["!.deno"],
+        };
+        assert_eq!(scan(&node_dc).is_match(), true);
+        assert_eq!(scan(&deno_dc).is_match(), false);
+
+        // A negative criterion alone never matches
+        let excluded_only = ScanDir {
+            dir_contents: &node_dc,
+            repo_root_contents: None,
+            files: &["!deno.json"],
+            extensions: &[],
+            folders: &[],
+        };
+        assert_eq!(excluded_only.is_match(), false);
+
+        node.close()?;
+        deno.close()?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_dir_contents_follow_symlinks() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = testdir(&["target/index.js"])?;
+        std::os::unix::fs::symlink(dir.path().join("target"), dir.path().join("node_modules"))?;
+        let base = PathBuf::from(dir.path());
+        let timeout = Duration::from_secs(30);
+
+        let followed = DirContents::from_path_with_timeout(&base, timeout, true)?;
+        assert!(followed.has_folder("node_modules"));
+
+        let not_followed = DirContents::from_path_with_timeout(&base, timeout, false)?;
+        assert!(!not_followed.has_folder("node_modules"));
+        assert!(not_followed.has_file_name("node_modules"));
+
+        dir.close()?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_discover_repository_through_symlink() -> Result<(), Box<dyn std::error::Error>> {
+        let repo_dir = tempfile::tempdir()?;
+        Repository::init(repo_dir.path())?;
+        fs::create_dir(repo_dir.path().join("src"))?;
+        let link_dir = tempfile::tempdir()?;
+        let link = link_dir.path().join("project");
+        std::os::unix::fs::symlink(repo_dir.path(), &link)?;
+
+        assert!(discover_repository(&link.join("src"), true).is_some());
+        assert!(discover_repository(&link.join("src"), false).is_none());
+        assert!(discover_repository(&repo_dir.path().join("src"), false).is_some());
+
+        repo_dir.close()?;
+        link_dir.close()?;
         Ok(())
     }
+
+    #[test]
+    fn test_command_status() {
+        let status = |status_code: Option<&str>, cmd_success: Option<&str>| {
+            let mut context = Context::new_with_dir(ArgMatches::default(), ".");
+            if let Some(status_code) = status_code {
+                context
+                    .properties
+                    .insert("status_code", status_code.to_string());
+            }
+            if let Some(cmd_success) = cmd_success {
+                context
+                    .properties
+                    .insert("cmd_success", cmd_success.to_string());
+            }
+            context.command_status()
+        };
+
+        assert_eq!(status(None, None), CommandStatus::Success);
+        assert_eq!(status(Some("0"), None), CommandStatus::Success);
+        assert_eq!(status(Some("1"), None), CommandStatus::Error);
+        assert_eq!(status(Some("1"), Some("True")), CommandStatus::Success);
+        assert_eq!(status(Some("0"), Some("False")), CommandStatus::CmdletError);
+        assert_eq!(status(Some("1"), Some("false")), CommandStatus::Error);
+        assert_eq!(status(Some("1"), Some("unknown")), CommandStatus::Error);
+    }
+
+    #[test]
+    fn test_split_negative_patterns() {
+        assert_eq!(
+            split_negative_patterns(&["package.json", "!deno.json", "!deno.jsonc"]),
+            (vec!["package.json"], vec!["deno.json", "deno.jsonc"])
+        );
+        assert_eq!(
+            split_negative_patterns(&[]),
+            (Vec::<&str>::new(), Vec::<&str>::new())
+        );
+    }
+
+    #[test]
+    fn test_repo_name_from_url() {
+        let name = |url| repo_name_from_url(url);
+        assert_eq!(
+            name("https://github.com/starship/starship.git"),
+            Some("starship".to_string())
+        );
+        assert_eq!(
+            name("git@github.com:starship/starship.git"),
+            Some("starship".to_string())
+        );
+        assert_eq!(
+            name("ssh://git@example.com/dotfiles/"),
+            Some("dotfiles".to_string())
+        );
+        assert_eq!(name("/srv/git/project.git"), Some("project".to_string()));
+        assert_eq!(
+            name("https://example.com/"),
+            Some("example.com".to_string())
+        );
+        assert_eq!(name(".git"), None);
+    }
+
+    #[test]
+    fn test_repo_name_falls_back_to_root() {
+        let repo = Repo {
+            branch: None,
+            root: Some(PathBuf::from("/home/user/code/starship")),
+            state: None,
+            remote_url: None,
+        };
+        assert_eq!(repo.name(), Some("starship".to_string()));
+
+        let repo = Repo {
+            remote_url: Some("git@github.com:starship/starship-fork.git".to_string()),
+            ..repo
+        };
+        assert_eq!(repo.name(), Some("starship-fork".to_string()));
+    }
 }
@@ -0,0 +1,230 @@
+use clap::ArgMatches;
+use std::env;
+use std::path::PathBuf;
+use std::process;
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::StarshipConfig;
+use crate::context::Context;
+use crate::print;
+
+/// The situations both configurations are rendered in, as their name and the
+/// properties normally passed to `starship prompt`
+const SCENARIOS: &[(&str, &[(&str, &str)])] = &[
+    ("success", &[("status_code", "0")]),
+    ("error", &[("status_code", "1")]),
+    (
+        "slow command",
+        &[("status_code", "0"), ("cmd_duration", "12000")],
+    ),
+    ("background jobs", &[("status_code", "0"), ("jobs", "2")]),
+];
+
+/// A line of the diff between two prompts
+#[derive(Debug, PartialEq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// A row of the side by side diff, as the old line, a marker and the new line
+type Row<'a> = (&'a str, char, &'a str);
+
+pub fn diff(args: ArgMatches) {
+    let config_paths: Vec<&str> = args.values_of("config").unwrap_or_default().collect();
+    let (old_path, new_path) = match config_paths.as_slice() {
+        [old_path, new_path] => (*old_path, *new_path),
+        _ => {
+            eprintln!(
+                "Error: `starship diff` compares exactly two configurations, given with --config"
+            );
+            process::exit(1);
+        }
+    };
+
+    let dir = args
+        .value_of("path")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::current_dir().expect("Unable to identify current directory"));
+    let side_by_side = args.is_present("side_by_side");
+
+    println!("--- {}\n+++ {}", old_path, new_path);
+    for (name, properties) in SCENARIOS {
+        let old_prompt = render(&args, &dir, old_path, properties);
+        let new_prompt = render(&args, &dir, new_path, properties);
+        let lines = diff_lines(&old_prompt, &new_prompt);
+
+        println!("@@ {} @@", name);
+        if side_by_side {
+            print!("{}", side_by_side_diff(&lines));
+        } else {
+            print!("{}", unified_diff(&lines));
+        }
+    }
+}
+
+/// Render the prompt with the configuration at `config_path`, keeping its escape
+/// sequences so that style changes show up in the diff
+fn render(
+    args: &ArgMatches,
+    dir: &PathBuf,
+    config_path: &str,
+    properties: &[(&str, &str)],
+) -> String {
+    let config = match StarshipConfig::from_path(config_path) {
+        Some(config) => config,
+        None => {
+            eprintln!("Error: Unable to read the configuration at {}", config_path);
+            process::exit(1);
+        }
+    };
+
+    let mut context = Context::new_with_config(args.clone(), dir, config);
+    context.properties.insert("escape", "raw".to_string());
+    for (property, value) in properties {
+        context.properties.insert(property, (*value).to_string());
+    }
+
+    print::get_prompt(&context)
+}
+
+/// Diff the lines of two prompts, using their longest common subsequence
+fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old: Vec<&str> = old.split('\n').collect();
+    let new: Vec<&str> = new.split('\n').collect();
+
+    // common[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
+fn unified_diff(lines: &[Line]) -> String {
+    lines
+        .iter()
+        .map(|line| match line {
+            Line::Same(text) => format!(" {}\n", text),
+            Line::Removed(text) => format!("-{}\n", text),
+            Line::Added(text) => format!("+{}\n", text),
+        })
+        .collect()
+}
+
+/// Show the old and new prompts in two columns, pairing up the lines replacing each other
+fn side_by_side_diff(lines: &[Line]) -> String {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut removed: Vec<&str> = Vec::new();
+    let mut added: Vec<&str> = Vec::new();
+
+    for line in lines {
+        match line {
+            Line::Same(text) => {
+                flush_change(&mut rows, &mut removed, &mut added);
+                rows.push((text, ' ', text));
+            }
+            Line::Removed(text) => removed.push(text),
+            Line::Added(text) => added.push(text),
+        }
+    }
+    flush_change(&mut rows, &mut removed, &mut added);
+
+    let width = rows
+        .iter()
+        .map(|(old, _, _)| visible_width(old))
+        .max()
+        .unwrap_or_default();
+    rows.iter()
+        .map(|(old, marker, new)| {
+            let padding = " ".repeat(width - visible_width(old));
+            format!("{}{} {} {}\n", old, padding, marker, new)
+        })
+        .collect()
+}
+
+/// Pair up the removed and added lines of a change into rows
+fn flush_change<'a>(rows: &mut Vec<Row<'a>>, removed: &mut Vec<&'a str>, added: &mut Vec<&'a str>) {
+    for k in 0..removed.len().max(added.len()) {
+        let (old, new) = (removed.get(k).copied(), added.get(k).copied());
+        let marker = match (old, new) {
+            (Some(_), Some(_)) => '|',
+            (Some(_), None) => '<',
+            _ => '>',
+        };
+        rows.push((old.unwrap_or_default(), marker, new.unwrap_or_default()));
+    }
+    removed.clear();
+    added.clear();
+}
+
+/// The width of `text` in a terminal, without the style escape sequences (`ESC [ ... m`)
+fn visible_width(text: &str) -> usize {
+    let mut visible = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            visible.push(c);
+        }
+    }
+    visible.width()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_prompt_lines() {
+        assert_eq!(
+            diff_lines("~/starship on master\n❯ ", "~/starship\nrust\n❯ "),
+            vec![
+                Line::Removed("~/starship on master"),
+                Line::Added("~/starship"),
+                Line::Added("rust"),
+                Line::Same("❯ "),
+            ]
+        );
+        assert_eq!(diff_lines("❯ ", "❯ "), vec![Line::Same("❯ ")]);
+    }
+
+    #[test]
+    fn width_without_escape_sequences() {
+        assert_eq!(visible_width("\u{1b}[1;32m❯\u{1b}[0m on"), 4);
+    }
+
+    #[test]
+    fn unified_and_side_by_side_diffs() {
+        let lines = diff_lines("a\nbb\n>", "a\nc\nd\n>");
+        assert_eq!(unified_diff(&lines), " a\n-bb\n+c\n+d\n >\n");
+        assert_eq!(
+            side_by_side_diff(&lines),
+            "a    a\nbb | c\n   > d\n>    >\n"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 pub mod model;
 mod parser;
 pub mod string_formatter;
+mod version;
 
-pub use model::{StyleVariableHolder, VariableHolder};
+pub use model::{GroupDecoration, StyleVariableHolder, VariableHolder};
 pub use string_formatter::StringFormatter;
+pub use version::VersionFormatter;
@@ -17,12 +17,44 @@ pub struct TextGroup<'a> {
     pub style: Vec<StyleElement<'a>>,
 }
 
+/// Where the value of a variable is placed when padded to a fixed width
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Pads or truncates the value of a variable to `width` columns
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Alignment {
+    pub align: Align,
+    pub width: usize,
+}
+
+/// Texts rendered around and between the variables of a group that isn't empty
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupDecoration<'a> {
+    pub prefix: Cow<'a, str>,
+    pub separator: Cow<'a, str>,
+    pub suffix: Cow<'a, str>,
+}
+
+#[derive(Clone)]
+pub struct Group<'a> {
+    pub name: Cow<'a, str>,
+    pub members: Vec<Cow<'a, str>>,
+    pub decoration: GroupDecoration<'a>,
+}
+
 #[derive(Clone)]
 pub enum FormatElement<'a> {
     Text(Cow<'a, str>),
     Variable(Cow<'a, str>),
+    AlignedVariable(Cow<'a, str>, Alignment),
     TextGroup(TextGroup<'a>),
     Conditional(Vec<FormatElement<'a>>),
+    Group(Group<'a>),
 }
 
 #[derive(Clone)]
@@ -34,13 +66,14 @@ pub enum StyleElement<'a> {
 impl<'a> VariableHolder<Cow<'a, str>> for FormatElement<'a> {
     fn get_variables(&self) -> BTreeSet<Cow<'a, str>> {
         match self {
-            FormatElement::Variable(var) => {
+            FormatElement::Variable(var) | FormatElement::AlignedVariable(var, _) => {
                 let mut variables = BTreeSet::new();
                 variables.insert(var.clone());
                 variables
             }
             FormatElement::TextGroup(textgroup) => textgroup.format.get_variables(),
             FormatElement::Conditional(format) => format.get_variables(),
+            FormatElement::Group(group) => group.members.iter().cloned().collect(),
             _ => Default::default(),
         }
     }
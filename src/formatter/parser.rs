@@ -9,11 +9,12 @@ struct IdentParser;
 fn _parse_value(value: Pair<Rule>) -> FormatElement {
     match value.as_rule() {
         Rule::text => FormatElement::Text(_parse_text(value).into()),
-        Rule::variable => FormatElement::Variable(_parse_variable(value).into()),
+        Rule::variable => _parse_format_variable(value),
         Rule::textgroup => FormatElement::TextGroup(_parse_textgroup(value)),
         Rule::conditional => {
             FormatElement::Conditional(_parse_format(value.into_inner().next().unwrap()))
         }
+        Rule::group => FormatElement::Group(_parse_group(value)),
         _ => unreachable!(),
     }
 }
@@ -29,10 +30,53 @@ fn _parse_textgroup(textgroup: Pair<Rule>) -> TextGroup {
     }
 }
 
+fn _parse_group(group: Pair<Rule>) -> Group {
+    let mut inner_rules = group.into_inner();
+    let name = inner_rules.next().unwrap().as_str();
+
+    Group {
+        name: name.into(),
+        members: inner_rules.map(|member| member.as_str().into()).collect(),
+        decoration: GroupDecoration::default(),
+    }
+}
+
 fn _parse_variable(variable: Pair<Rule>) -> &str {
     variable.into_inner().next().unwrap().as_str()
 }
 
+fn _parse_format_variable(variable: Pair<Rule>) -> FormatElement {
+    let mut inner_rules = variable.into_inner();
+    let name = inner_rules.next().unwrap().as_str();
+
+    match inner_rules.next() {
+        Some(alignment) => FormatElement::AlignedVariable(name.into(), _parse_alignment(alignment)),
+        None => FormatElement::Variable(name.into()),
+    }
+}
+
+fn _parse_alignment(alignment: Pair<Rule>) -> Alignment {
+    let mut align = Align::Left;
+    let mut width = 0;
+
+    for pair in alignment.into_inner() {
+        match pair.as_rule() {
+            Rule::alignment_direction => {
+                align = match pair.as_str() {
+                    "^" => Align::Center,
+                    ">" => Align::Right,
+                    _ => Align::Left,
+                }
+            }
+            // Widths too large to be parsed can't be displayed anyway
+            Rule::alignment_width => width = pair.as_str().parse().unwrap_or(usize::MAX),
+            _ => unreachable!(),
+        }
+    }
+
+    Alignment { align, width }
+}
+
 fn _parse_text(text: Pair<Rule>) -> String {
     text.into_inner()
         .map(|pair| pair.as_str().chars())
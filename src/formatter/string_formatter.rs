@@ -8,28 +8,33 @@ use std::fmt;
 use std::iter::FromIterator;
 
 use crate::config::parse_style_string;
+use crate::context::Context;
 use crate::segment::Segment;
+use crate::utils;
 
 use super::model::*;
 use super::parser::{parse, Rule};
 
+/// The value of a variable. Texts of the format strings and meta-variables live for `'a`,
+/// and can be borrowed by the segments, while the values of plain variables usually only
+/// live for `'v`, and are copied into the segments.
 #[derive(Clone)]
-enum VariableValue<'a> {
-    Plain(Cow<'a, str>),
-    Styled(Vec<Segment>),
+enum VariableValue<'a, 'v> {
+    Plain(Cow<'v, str>),
+    Styled(Vec<Segment<'a>>),
     Meta(Vec<FormatElement<'a>>),
 }
 
-impl<'a> Default for VariableValue<'a> {
+impl<'a, 'v> Default for VariableValue<'a, 'v> {
     fn default() -> Self {
         VariableValue::Plain(Cow::Borrowed(""))
     }
 }
 
-type VariableMapType<'a> =
-    BTreeMap<String, Option<Result<VariableValue<'a>, StringFormatterError>>>;
-type StyleVariableMapType<'a> =
-    BTreeMap<String, Option<Result<Cow<'a, str>, StringFormatterError>>>;
+type VariableMapType<'a, 'v> =
+    BTreeMap<String, Option<Result<VariableValue<'a, 'v>, StringFormatterError>>>;
+type StyleVariableMapType<'v> =
+    BTreeMap<String, Option<Result<Cow<'v, str>, StringFormatterError>>>;
 
 #[derive(Debug, Clone)]
 pub enum StringFormatterError {
@@ -54,44 +59,117 @@ impl From<String> for StringFormatterError {
     }
 }
 
-pub struct StringFormatter<'a> {
+pub struct StringFormatter<'a, 'v> {
     format: Vec<FormatElement<'a>>,
-    variables: VariableMapType<'a>,
-    style_variables: StyleVariableMapType<'a>,
+    variables: VariableMapType<'a, 'v>,
+    style_variables: StyleVariableMapType<'v>,
 }
 
-impl<'a> StringFormatter<'a> {
+impl<'a, 'v> StringFormatter<'a, 'v> {
     /// Creates an instance of StringFormatter from a format string
     ///
     /// This method will throw an Error when the given format string fails to parse.
     pub fn new(format: &'a str) -> Result<Self, StringFormatterError> {
         parse(format)
-            .map(|format| {
-                // Cache all variables
-                let variables = VariableMapType::from_iter(
-                    format
-                        .get_variables()
-                        .into_iter()
-                        .map(|key| (key.to_string(), None))
-                        .collect::<Vec<(String, Option<_>)>>(),
-                );
-                let style_variables = StyleVariableMapType::from_iter(
-                    format
-                        .get_style_variables()
-                        .into_iter()
-                        .map(|key| (key.to_string(), None))
-                        .collect::<Vec<(String, Option<_>)>>(),
-                );
-                (format, variables, style_variables)
-            })
-            .map(|(format, variables, style_variables)| Self {
-                format,
-                variables,
-                style_variables,
-            })
+            .map(Self::from_format)
             .map_err(StringFormatterError::Parse)
     }
 
+    fn from_format(format: Vec<FormatElement<'a>>) -> Self {
+        // Cache all variables
+        let variables = VariableMapType::from_iter(
+            format
+                .get_variables()
+                .into_iter()
+                .map(|key| (key.to_string(), None))
+                .collect::<Vec<(String, Option<_>)>>(),
+        );
+        let style_variables = StyleVariableMapType::from_iter(
+            format
+                .get_style_variables()
+                .into_iter()
+                .map(|key| (key.to_string(), None))
+                .collect::<Vec<(String, Option<_>)>>(),
+        );
+        Self {
+            format,
+            variables,
+            style_variables,
+        }
+    }
+
+    /// Expands the variables which are aliases of other format strings
+    ///
+    /// The mapper returns the format string of a variable name that is an alias. Aliases
+    /// are expanded before anything else is mapped, and can themselves contain aliases.
+    /// This method will throw an Error when an alias fails to parse, refers to itself, or is
+    /// used where only a single variable fits: in a fixed width variable or in a group.
+    pub fn map_aliases<M>(self, mapper: M) -> Result<Self, StringFormatterError>
+    where
+        M: Fn(&str) -> Option<&'a str>,
+    {
+        fn _expand_aliases<'a, M>(
+            format: Vec<FormatElement<'a>>,
+            mapper: &M,
+            expanding: &mut Vec<String>,
+        ) -> Result<Vec<FormatElement<'a>>, StringFormatterError>
+        where
+            M: Fn(&str) -> Option<&'a str>,
+        {
+            let mut expanded = Vec::with_capacity(format.len());
+            for el in format {
+                match el {
+                    FormatElement::Variable(name) => match mapper(&name) {
+                        Some(alias) => {
+                            if expanding.iter().any(|alias_name| *alias_name == name) {
+                                return Err(StringFormatterError::Custom(format!(
+                                    "Alias `{}` refers to itself: {} -> {}",
+                                    name,
+                                    expanding.join(" -> "),
+                                    name
+                                )));
+                            }
+                            let alias_format = parse(alias).map_err(StringFormatterError::Parse)?;
+                            expanding.push(name.to_string());
+                            expanded.extend(_expand_aliases(alias_format, mapper, expanding)?);
+                            expanding.pop();
+                        }
+                        None => expanded.push(FormatElement::Variable(name)),
+                    },
+                    FormatElement::TextGroup(mut textgroup) => {
+                        textgroup.format = _expand_aliases(textgroup.format, mapper, expanding)?;
+                        expanded.push(FormatElement::TextGroup(textgroup));
+                    }
+                    FormatElement::Conditional(format) => expanded.push(
+                        FormatElement::Conditional(_expand_aliases(format, mapper, expanding)?),
+                    ),
+                    FormatElement::AlignedVariable(name, _) if mapper(&name).is_some() => {
+                        return Err(StringFormatterError::Custom(format!(
+                            "Alias `{}` can't be given a width, only variables can",
+                            name
+                        )));
+                    }
+                    FormatElement::Group(group) => {
+                        if let Some(alias) =
+                            group.members.iter().find(|name| mapper(name).is_some())
+                        {
+                            return Err(StringFormatterError::Custom(format!(
+                                "Alias `{}` can't be a member of group `{}`, only variables can",
+                                alias, group.name
+                            )));
+                        }
+                        expanded.push(FormatElement::Group(group));
+                    }
+                    other => expanded.push(other),
+                }
+            }
+            Ok(expanded)
+        }
+
+        let format = _expand_aliases(self.format, &mapper, &mut Vec::new())?;
+        Ok(Self::from_format(format))
+    }
+
     /// Maps variable name to its value
     ///
     /// You should provide a function or closure that accepts the variable name `name: &str` as a
@@ -108,7 +186,7 @@ impl<'a> StringFormatter<'a> {
     ///
     pub fn map<T, M>(mut self, mapper: M) -> Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Cow<'v, str>>,
         M: Fn(&str) -> Option<Result<T, StringFormatterError>> + Sync,
     {
         self.variables
@@ -169,7 +247,7 @@ impl<'a> StringFormatter<'a> {
     /// See `StringFormatter::map` for description on the parameters.
     pub fn map_variables_to_segments<M>(mut self, mapper: M) -> Self
     where
-        M: Fn(&str) -> Option<Result<Vec<Segment>, StringFormatterError>> + Sync,
+        M: Fn(&str) -> Option<Result<Vec<Segment<'a>>, StringFormatterError>> + Sync,
     {
         self.variables
             .par_iter_mut()
@@ -180,12 +258,43 @@ impl<'a> StringFormatter<'a> {
         self
     }
 
+    /// Maps the name of a group to the texts decorating it
+    ///
+    /// A group that isn't mapped only renders the variables inside it that aren't empty.
+    pub fn map_groups<M>(mut self, mapper: M) -> Self
+    where
+        M: Fn(&str) -> Option<GroupDecoration<'a>>,
+    {
+        fn _map_groups<'a, M>(format: &mut [FormatElement<'a>], mapper: &M)
+        where
+            M: Fn(&str) -> Option<GroupDecoration<'a>>,
+        {
+            for el in format.iter_mut() {
+                match el {
+                    FormatElement::Group(group) => {
+                        if let Some(decoration) = mapper(&group.name) {
+                            group.decoration = decoration;
+                        }
+                    }
+                    FormatElement::TextGroup(textgroup) => {
+                        _map_groups(&mut textgroup.format, mapper)
+                    }
+                    FormatElement::Conditional(format) => _map_groups(format, mapper),
+                    _ => (),
+                }
+            }
+        }
+
+        _map_groups(&mut self.format, &mapper);
+        self
+    }
+
     /// Maps variable name in a style string to its value
     ///
     /// See `StringFormatter::map` for description on the parameters.
     pub fn map_style<T, M>(mut self, mapper: M) -> Self
     where
-        T: Into<Cow<'a, str>>,
+        T: Into<Cow<'v, str>>,
         M: Fn(&str) -> Option<Result<T, StringFormatterError>> + Sync,
     {
         self.style_variables
@@ -203,24 +312,33 @@ impl<'a> StringFormatter<'a> {
     ///
     /// - Format string in meta variables fails to parse
     /// - Variable mapper returns an error.
-    pub fn parse(self, default_style: Option<Style>) -> Result<Vec<Segment>, StringFormatterError> {
+    ///
+    /// When a `context` is given, colors in style strings are resolved against its palette.
+    pub fn parse(
+        self,
+        default_style: Option<Style>,
+        context: Option<&Context>,
+    ) -> Result<Vec<Segment<'a>>, StringFormatterError> {
         fn _parse_textgroup<'a>(
             textgroup: TextGroup<'a>,
-            variables: &'a VariableMapType<'a>,
-            style_variables: &'a StyleVariableMapType<'a>,
-        ) -> Result<Vec<Segment>, StringFormatterError> {
-            let style = _parse_style(textgroup.style, style_variables);
+            variables: &VariableMapType<'a, '_>,
+            style_variables: &StyleVariableMapType,
+            context: Option<&Context>,
+        ) -> Result<Vec<Segment<'a>>, StringFormatterError> {
+            let style = _parse_style(textgroup.style, style_variables, context);
             _parse_format(
                 textgroup.format,
                 style.transpose()?,
                 &variables,
                 &style_variables,
+                context,
             )
         }
 
-        fn _parse_style<'a>(
+        fn _parse_style(
             style: Vec<StyleElement>,
-            variables: &'a StyleVariableMapType<'a>,
+            variables: &StyleVariableMapType,
+            context: Option<&Context>,
         ) -> Option<Result<Style, StringFormatterError>> {
             let style_strings = style
                 .into_iter()
@@ -239,17 +357,104 @@ impl<'a> StringFormatter<'a> {
                 .map(|style_strings| {
                     let style_string: String =
                         style_strings.iter().flat_map(|s| s.chars()).collect();
-                    parse_style_string(&style_string)
+                    parse_style_string(&style_string, context)
                 })
                 .transpose()
         }
 
+        fn _parse_variable<'a>(
+            name: Cow<'a, str>,
+            style: Option<Style>,
+            variables: &VariableMapType<'a, '_>,
+            style_variables: &StyleVariableMapType,
+            context: Option<&Context>,
+        ) -> Result<Vec<Segment<'a>>, StringFormatterError> {
+            variables
+                .get(name.as_ref())
+                .expect("Uncached variable found")
+                .as_ref()
+                .map(|segments| match segments.clone()? {
+                    VariableValue::Styled(segments) => Ok(segments
+                        .into_iter()
+                        .map(|mut segment| {
+                            // Derive upper style if the style of segments are none.
+                            if !segment.has_style() {
+                                if let Some(style) = style {
+                                    segment.set_style(style);
+                                }
+                            }
+                            segment
+                        })
+                        .collect()),
+                    VariableValue::Plain(text) => {
+                        Ok(vec![_new_segment(name, text.into_owned(), style)])
+                    }
+                    VariableValue::Meta(format) => {
+                        let formatter = StringFormatter {
+                            format,
+                            variables: _clone_without_meta(variables),
+                            style_variables: style_variables.clone(),
+                        };
+                        formatter.parse(style, context)
+                    }
+                })
+                .unwrap_or_else(|| Ok(Vec::new()))
+        }
+
+        fn _parse_group<'a>(
+            group: Group<'a>,
+            style: Option<Style>,
+            variables: &VariableMapType<'a, '_>,
+            style_variables: &StyleVariableMapType,
+            context: Option<&Context>,
+        ) -> Result<Vec<Segment<'a>>, StringFormatterError> {
+            let members = group
+                .members
+                .into_iter()
+                .map(|name| _parse_variable(name, style, variables, style_variables, context))
+                .collect::<Result<Vec<_>, StringFormatterError>>()?;
+            let (shown, hidden): (Vec<_>, Vec<_>) = members
+                .into_iter()
+                .partition(|segments| !segments.iter().all(Segment::is_empty));
+
+            if shown.is_empty() {
+                // Keep the segments without any value, which may stand for empty modules
+                return Ok(hidden
+                    .into_iter()
+                    .flatten()
+                    .filter(|segment| segment.value.is_empty())
+                    .collect());
+            }
+
+            let GroupDecoration {
+                prefix,
+                separator,
+                suffix,
+            } = group.decoration;
+            let mut segments = Vec::new();
+            if !prefix.is_empty() {
+                segments.push(_new_segment("_text", prefix, style));
+            }
+            for (i, member) in shown.into_iter().enumerate() {
+                if i > 0 && !separator.is_empty() {
+                    segments.push(_new_segment("_text", separator.clone(), style));
+                }
+                segments.extend(member);
+            }
+            if !suffix.is_empty() {
+                segments.push(_new_segment("_text", suffix, style));
+            }
+
+            Ok(segments)
+        }
+
         fn _parse_format<'a>(
             format: Vec<FormatElement<'a>>,
             style: Option<Style>,
-            variables: &'a VariableMapType<'a>,
-            style_variables: &'a StyleVariableMapType<'a>,
-        ) -> Result<Vec<Segment>, StringFormatterError> {
+            variables: &VariableMapType<'a, '_>,
+            style_variables: &StyleVariableMapType,
+            context: Option<&Context>,
+        ) -> Result<Vec<Segment<'a>>, StringFormatterError> {
             let results: Result<Vec<Vec<Segment>>, StringFormatterError> = format
                 .into_iter()
                 .map(|el| {
@@ -260,44 +465,26 @@ impl<'a> StringFormatter<'a> {
                                 format: textgroup.format,
                                 style: textgroup.style,
                             };
-                            _parse_textgroup(textgroup, &variables, &style_variables)
+                            _parse_textgroup(textgroup, &variables, &style_variables, context)
+                        }
+                        FormatElement::Variable(name) => {
+                            _parse_variable(name, style, variables, style_variables, context)
+                        }
+                        FormatElement::AlignedVariable(name, alignment) => {
+                            _parse_variable(name, style, variables, style_variables, context).map(
+                                |segments| {
+                                    let ambiguous_width =
+                                        context.map_or(1, Context::ambiguous_width);
+                                    _align_segments(segments, alignment, style, ambiguous_width)
+                                },
+                            )
                         }
-                        FormatElement::Variable(name) => variables
-                            .get(name.as_ref())
-                            .expect("Uncached variable found")
-                            .as_ref()
-                            .map(|segments| match segments.clone()? {
-                                VariableValue::Styled(segments) => Ok(segments
-                                    .into_iter()
-                                    .map(|mut segment| {
-                                        // Derive upper style if the style of segments are none.
-                                        if !segment.has_style() {
-                                            if let Some(style) = style {
-                                                segment.set_style(style);
-                                            }
-                                        }
-                                        segment
-                                    })
-                                    .collect()),
-                                VariableValue::Plain(text) => {
-                                    Ok(vec![_new_segment(name, text, style)])
-                                }
-                                VariableValue::Meta(format) => {
-                                    let formatter = StringFormatter {
-                                        format,
-                                        variables: _clone_without_meta(variables),
-                                        style_variables: style_variables.clone(),
-                                    };
-                                    formatter.parse(style)
-                                }
-                            })
-                            .unwrap_or_else(|| Ok(Vec::new())),
                         FormatElement::Conditional(format) => {
                             // Show the conditional format string if all the variables inside are not
                             // none.
-                            fn _should_show_elements<'a>(
+                            fn _should_show_elements(
                                 format_elements: &[FormatElement],
-                                variables: &'a VariableMapType<'a>,
+                                variables: &VariableMapType,
                             ) -> bool {
                                 format_elements.get_variables().iter().any(|var| {
                                     variables
@@ -331,11 +518,14 @@ impl<'a> StringFormatter<'a> {
                             let should_show: bool = _should_show_elements(&format, variables);
 
                             if should_show {
-                                _parse_format(format, style, variables, style_variables)
+                                _parse_format(format, style, variables, style_variables, context)
                             } else {
                                 Ok(Vec::new())
                             }
                         }
+                        FormatElement::Group(group) => {
+                            _parse_group(group, style, variables, style_variables, context)
+                        }
                     }
                 })
                 .collect();
@@ -347,36 +537,79 @@ impl<'a> StringFormatter<'a> {
             default_style,
             &self.variables,
             &self.style_variables,
+            context,
         )
     }
 }
 
-impl<'a> VariableHolder<String> for StringFormatter<'a> {
+impl<'a, 'v> VariableHolder<String> for StringFormatter<'a, 'v> {
     fn get_variables(&self) -> BTreeSet<String> {
         BTreeSet::from_iter(self.variables.keys().cloned())
     }
 }
 
-impl<'a> StyleVariableHolder<String> for StringFormatter<'a> {
+impl<'a, 'v> StyleVariableHolder<String> for StringFormatter<'a, 'v> {
     fn get_style_variables(&self) -> BTreeSet<String> {
         BTreeSet::from_iter(self.style_variables.keys().cloned())
     }
 }
 
 /// Helper function to create a new segment
-fn _new_segment(
-    name: impl Into<String>,
-    value: impl Into<String>,
+fn _new_segment<'a>(
+    name: impl Into<Cow<'a, str>>,
+    value: impl Into<Cow<'a, str>>,
     style: Option<Style>,
-) -> Segment {
+) -> Segment<'a> {
     Segment {
         _name: name.into(),
         value: value.into(),
         style,
+        image: None,
     }
 }
 
-fn _clone_without_meta<'a>(variables: &VariableMapType<'a>) -> VariableMapType<'a> {
+/// Pads the segments of a variable with spaces, or truncates them from the right,
+/// so that they take exactly `alignment.width` columns
+fn _align_segments(
+    segments: Vec<Segment>,
+    alignment: Alignment,
+    style: Option<Style>,
+    ambiguous_width: usize,
+) -> Vec<Segment> {
+    let mut remaining = alignment.width;
+    let mut aligned = Vec::new();
+
+    'segments: for mut segment in segments {
+        let mut value = String::new();
+        for c in segment.value.chars() {
+            let width = utils::char_width(c, ambiguous_width);
+            if width > remaining {
+                segment.value = value.into();
+                aligned.push(segment);
+                break 'segments;
+            }
+            remaining -= width;
+            value.push(c);
+        }
+        aligned.push(segment);
+    }
+
+    let (left, right) = match alignment.align {
+        Align::Left => (0, remaining),
+        Align::Center => (remaining / 2, remaining - remaining / 2),
+        Align::Right => (remaining, 0),
+    };
+    if left > 0 {
+        aligned.insert(0, _new_segment("_text", " ".repeat(left), style));
+    }
+    if right > 0 {
+        aligned.push(_new_segment("_text", " ".repeat(right), style));
+    }
+
+    aligned
+}
+
+fn _clone_without_meta<'a, 'v>(variables: &VariableMapType<'a, 'v>) -> VariableMapType<'a, 'v> {
     VariableMapType::from_iter(variables.iter().map(|(key, value)| {
         let value = match value {
             Some(Ok(value)) => match value {
@@ -414,7 +647,7 @@ mod tests {
         let style = Some(Color::Red.bold());
 
         let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
-        let result = formatter.parse(style).unwrap();
+        let result = formatter.parse(style, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "text", style);
     }
@@ -423,7 +656,7 @@ mod tests {
     fn test_textgroup_text_only() {
         const FORMAT_STR: &str = "[text](red bold)";
         let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "text", Some(Color::Red.bold()));
     }
@@ -438,7 +671,7 @@ mod tests {
                 "var1" => Some(Ok("text1".to_owned())),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "text1", None);
     }
@@ -454,7 +687,7 @@ mod tests {
                 "style" => Some(Ok("red bold".to_owned())),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "root", root_style);
     }
@@ -466,17 +699,177 @@ mod tests {
         let formatter = StringFormatter::new(FORMAT_STR)
             .unwrap()
             .map(|variable| Some(Ok(format!("${{{}}}", variable))));
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "${env:PWD}", None);
     }
 
+    #[test]
+    fn test_aligned_variable() {
+        const FORMAT_STR: &str = "${left:<6}|${center:^6}|${right:>6}|${default:6}";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|_| Some(Ok("ab")));
+        let result = formatter.parse(None, None).unwrap();
+        let value: String = result.iter().map(Segment::get_value).collect();
+        assert_eq!(value, "ab    |  ab  |    ab|ab    ");
+    }
+
+    #[test]
+    fn test_aligned_variable_truncated() {
+        const FORMAT_STR: &str = "[${var:^4}](red)";
+        let var_style = Some(Color::Red.normal());
+        let styled_style = Some(Color::Green.normal());
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map_variables_to_segments(|variable| match variable {
+                "var" => Some(Ok(vec![
+                    _new_segment("_1".to_owned(), "ab".to_owned(), None),
+                    _new_segment("_2".to_owned(), "cdef".to_owned(), styled_style),
+                    _new_segment("_3".to_owned(), "gh".to_owned(), None),
+                ])),
+                _ => None,
+            });
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "ab", var_style);
+        match_next!(result_iter, "cd", styled_style);
+        assert!(result_iter.next().is_none());
+    }
+
+    #[test]
+    fn test_aligned_variable_wide_chars() {
+        const FORMAT_STR: &str = "${var:>5}";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|_| Some(Ok("分支名")));
+        let result = formatter.parse(None, None).unwrap();
+        let value: String = result.iter().map(Segment::get_value).collect();
+        assert_eq!(value, " 分支");
+    }
+
+    #[test]
+    fn test_aligned_ambiguous_width_chars() {
+        let align = |ambiguous_width| {
+            let segments = vec![_new_segment("var", "─§", None)];
+            let alignment = Alignment {
+                align: Align::Left,
+                width: 3,
+            };
+            _align_segments(segments, alignment, None, ambiguous_width)
+                .iter()
+                .map(Segment::get_value)
+                .collect::<String>()
+        };
+        assert_eq!(align(1), "─§ ");
+        assert_eq!(align(2), "─ ");
+    }
+
+    #[test]
+    fn test_aligned_empty_variable() {
+        const FORMAT_STR: &str = "${var:3}|";
+
+        let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
+        let result = formatter.parse(None, None).unwrap();
+        let value: String = result.iter().map(Segment::get_value).collect();
+        assert_eq!(value, "   |");
+    }
+
+    #[test]
+    fn test_aliases() {
+        const FORMAT_STR: &str = "$lang [$vcs](red)";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map_aliases(|alias| match alias {
+                "lang" => Some("$rust($golang)"),
+                "vcs" => Some("$branch$lang"),
+                _ => None,
+            })
+            .unwrap();
+        let variables = formatter.get_variables();
+        assert_eq!(
+            variables.iter().map(String::as_str).collect::<Vec<_>>(),
+            vec!["branch", "golang", "rust"]
+        );
+
+        let result = formatter
+            .map(|var| Some(Ok(var.to_string())))
+            .parse(None, None)
+            .unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "rust", None);
+        match_next!(result_iter, "golang", None);
+        match_next!(result_iter, " ", None);
+        match_next!(result_iter, "branch", Some(Color::Red.normal()));
+        match_next!(result_iter, "rust", Some(Color::Red.normal()));
+        match_next!(result_iter, "golang", Some(Color::Red.normal()));
+        assert!(result_iter.next().is_none());
+    }
+
+    #[test]
+    fn test_alias_cycle() {
+        let result = StringFormatter::new("$a")
+            .unwrap()
+            .map_aliases(|alias| match alias {
+                "a" => Some("$b"),
+                "b" => Some("($a)"),
+                _ => None,
+            });
+        match result {
+            Err(StringFormatterError::Custom(error)) => {
+                assert_eq!(error, "Alias `a` refers to itself: a -> b -> a")
+            }
+            _ => panic!("expected the cycle to be an error"),
+        }
+    }
+
+    #[test]
+    fn test_alias_in_aligned_variable() {
+        let result =
+            StringFormatter::new("${lang:^12}")
+                .unwrap()
+                .map_aliases(|alias| match alias {
+                    "lang" => Some("$rust$golang"),
+                    _ => None,
+                });
+        match result {
+            Err(StringFormatterError::Custom(error)) => {
+                assert_eq!(
+                    error,
+                    "Alias `lang` can't be given a width, only variables can"
+                )
+            }
+            _ => panic!("expected the aligned alias to be an error"),
+        }
+    }
+
+    #[test]
+    fn test_alias_in_group() {
+        let result = StringFormatter::new("$[name](lang vcs)")
+            .unwrap()
+            .map_aliases(|alias| match alias {
+                "vcs" => Some("$git_branch$hg_branch"),
+                _ => None,
+            });
+        match result {
+            Err(StringFormatterError::Custom(error)) => assert_eq!(
+                error,
+                "Alias `vcs` can't be a member of group `name`, only variables can"
+            ),
+            _ => panic!("expected the alias in a group to be an error"),
+        }
+    }
+
     #[test]
     fn test_escaped_chars() {
         const FORMAT_STR: &str = r#"\\\[\$text\]\(red bold\)"#;
 
         let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, r#"\[$text](red bold)"#, None);
     }
@@ -489,7 +882,7 @@ mod tests {
         let inner_style = Some(Color::Blue.normal());
 
         let formatter = StringFormatter::new(FORMAT_STR).unwrap().map(empty_mapper);
-        let result = formatter.parse(outer_style).unwrap();
+        let result = formatter.parse(outer_style, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "outer ", outer_style);
         match_next!(result_iter, "middle ", middle_style);
@@ -507,7 +900,7 @@ mod tests {
                 "var" => Some(Ok("text".to_owned())),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "text", var_style);
     }
@@ -533,7 +926,7 @@ mod tests {
                 ])),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "styless", var_style);
         match_next!(result_iter, "styled", styled_style);
@@ -556,7 +949,7 @@ mod tests {
                 "b" => Some(Ok("$b")),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "$a", None);
         match_next!(result_iter, "$b", None);
@@ -578,7 +971,7 @@ mod tests {
                 "c" => Some(Ok("$c")),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "$a", None);
         match_next!(result_iter, "$b", None);
@@ -595,7 +988,7 @@ mod tests {
                 "some" => Some(Ok("$some")),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "$some", None);
         match_next!(result_iter, " should render but ", None);
@@ -612,7 +1005,7 @@ mod tests {
                 "some" => Some(Ok("$some")),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, "$some", None);
         match_next!(result_iter, " ", None);
@@ -631,7 +1024,7 @@ mod tests {
                 "all" => Some("$some"),
                 _ => None,
             });
-        let result = formatter.parse(None).unwrap();
+        let result = formatter.parse(None, None).unwrap();
         let mut result_iter = result.iter();
         match_next!(result_iter, " ", None);
     }
@@ -683,8 +1076,60 @@ mod tests {
                     "never" => Some(Err(never_error.clone())),
                     _ => None,
                 })
-                .parse(None)
+                .parse(None, None)
         });
         assert!(segments.is_err());
     }
+
+    #[test]
+    fn test_group() {
+        const FORMAT_STR: &str = "$[git](a none b) $[empty](none)$c";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map_groups(|name| match name {
+                "git" | "empty" => Some(GroupDecoration {
+                    prefix: "[".into(),
+                    separator: "|".into(),
+                    suffix: "]".into(),
+                }),
+                _ => None,
+            })
+            .map(|var| match var {
+                "a" => Some(Ok("a")),
+                "b" => Some(Ok("b")),
+                "c" => Some(Ok("c")),
+                "none" => Some(Ok("")),
+                _ => None,
+            });
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "[", None);
+        match_next!(result_iter, "a", None);
+        match_next!(result_iter, "|", None);
+        match_next!(result_iter, "b", None);
+        match_next!(result_iter, "]", None);
+        match_next!(result_iter, " ", None);
+        match_next!(result_iter, "", None);
+        match_next!(result_iter, "c", None);
+        assert!(result_iter.next().is_none());
+    }
+
+    #[test]
+    fn test_group_without_decoration() {
+        const FORMAT_STR: &str = "[$[unknown]( a  custom.b )](red)";
+
+        let formatter = StringFormatter::new(FORMAT_STR)
+            .unwrap()
+            .map(|var| match var {
+                "a" => Some(Ok("a")),
+                "custom.b" => Some(Ok("b")),
+                _ => None,
+            });
+        let result = formatter.parse(None, None).unwrap();
+        let mut result_iter = result.iter();
+        match_next!(result_iter, "a", Some(Color::Red.normal()));
+        match_next!(result_iter, "b", Some(Color::Red.normal()));
+        assert!(result_iter.next().is_none());
+    }
 }
@@ -0,0 +1,131 @@
+use super::string_formatter::StringFormatterError;
+use super::StringFormatter;
+
+/// Formats a version string according to a `version_format` of a module.
+///
+/// The format string can contain the following variables:
+///
+/// - `raw`: The version as reported by the tool, without a leading `v`
+/// - `major`, `minor`, `patch`: The numeric components at the start of the version,
+///   e.g. `1`, `45` and `0` for `1.45.0-nightly`
+///
+/// Components missing from the version are empty, so they can be wrapped in a
+/// conditional, e.g. `v${major}(.${minor})`.
+pub struct VersionFormatter<'a> {
+    formatter: StringFormatter<'a, 'static>,
+}
+
+impl<'a> VersionFormatter<'a> {
+    /// Creates an instance of VersionFormatter from a format string
+    ///
+    /// This method will throw an Error when the given format string fails to parse.
+    pub fn new(format: &'a str) -> Result<Self, StringFormatterError> {
+        let formatter = StringFormatter::new(format)?;
+
+        Ok(Self { formatter })
+    }
+
+    /// Formats a version string with the format of this formatter
+    pub fn format_version(self, version: &str) -> Result<String, StringFormatterError> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let components = parse_components(version);
+
+        self.formatter
+            .map(|variable| match variable {
+                "raw" => Some(Ok(version.to_string())),
+                "major" => components.first().map(|major| Ok(major.to_string())),
+                "minor" => components.get(1).map(|minor| Ok(minor.to_string())),
+                "patch" => components.get(2).map(|patch| Ok(patch.to_string())),
+                _ => None,
+            })
+            .parse(None, None)
+            .map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment.value.as_ref())
+                    .collect()
+            })
+    }
+
+    /// Formats the version of a module, logging errors in its `version_format`
+    ///
+    /// Falls back to the raw version prefixed with `v` if the format is invalid.
+    pub fn format_module_version(module_name: &str, version: &str, version_format: &str) -> String {
+        match VersionFormatter::new(version_format).and_then(|f| f.format_version(version)) {
+            Ok(formatted) => formatted,
+            Err(error) => {
+                log::warn!("Error in `{}.version_format`:\n{}", module_name, error);
+                let version = version.trim();
+                format!("v{}", version.strip_prefix('v').unwrap_or(version))
+            }
+        }
+    }
+}
+
+/// Splits the leading numeric components off a version, e.g. `["3", "8", "0"]` for `3.8.0rc1`
+fn parse_components(version: &str) -> Vec<&str> {
+    let mut components = Vec::new();
+    for part in version.split('.').take(3) {
+        let digits = part
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(part.len());
+        if digits == 0 {
+            break;
+        }
+        components.push(&part[..digits]);
+        if digits < part.len() {
+            break;
+        }
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VERSION: &str = "3.2.1-nightly";
+
+    #[test]
+    fn test_parse_components() {
+        assert_eq!(parse_components("1.45.0"), vec!["1", "45", "0"]);
+        assert_eq!(parse_components("3.8.0rc1"), vec!["3", "8", "0"]);
+        assert_eq!(parse_components("2.1-beta.3"), vec!["2", "1"]);
+        assert_eq!(parse_components("1.2.3.4"), vec!["1", "2", "3"]);
+        assert_eq!(parse_components("22"), vec!["22"]);
+        assert_eq!(parse_components("nightly"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_format_raw_version() {
+        let formatter = VersionFormatter::new("v${raw}").unwrap();
+        assert_eq!(formatter.format_version(VERSION).unwrap(), "v3.2.1-nightly");
+    }
+
+    #[test]
+    fn test_format_version_components() {
+        let formatter = VersionFormatter::new("${major}/${minor}/${patch}").unwrap();
+        assert_eq!(formatter.format_version(VERSION).unwrap(), "3/2/1");
+    }
+
+    #[test]
+    fn test_format_version_with_leading_v() {
+        let formatter = VersionFormatter::new("v${major}.${minor}").unwrap();
+        assert_eq!(formatter.format_version("v12.0.0").unwrap(), "v12.0");
+    }
+
+    #[test]
+    fn test_format_version_missing_components() {
+        let formatter = VersionFormatter::new("v${major}(.${minor})(.${patch})").unwrap();
+        assert_eq!(formatter.format_version("22").unwrap(), "v22");
+    }
+
+    #[test]
+    fn test_format_module_version_with_invalid_format() {
+        assert_eq!(
+            VersionFormatter::format_module_version("rust", VERSION, "v${raw"),
+            "v3.2.1-nightly"
+        );
+    }
+}
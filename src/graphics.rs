@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::context::Context;
+use crate::segment::Segment;
+
+/// The number of cells an image takes, the same as a wide character like an emoji
+const IMAGE_WIDTH: usize = 2;
+
+/// Kitty limits the size of each chunk of image data
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// The protocols terminals use to draw inline images
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    ITerm2,
+}
+
+impl GraphicsProtocol {
+    /// Get the protocol selected with the root `graphics_protocol` option.
+    ///
+    /// `auto` detects terminals known to support a protocol. Anything else,
+    /// including the default `none`, disables images.
+    pub fn from_config(setting: &str) -> Option<Self> {
+        match setting {
+            "kitty" => Some(GraphicsProtocol::Kitty),
+            "iterm2" => Some(GraphicsProtocol::ITerm2),
+            "auto" => Self::detect(),
+            _ => None,
+        }
+    }
+
+    fn detect() -> Option<Self> {
+        let term = env::var("TERM").unwrap_or_default();
+        let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+        let lc_terminal = env::var("LC_TERMINAL").unwrap_or_default();
+
+        if term == "xterm-kitty" || env::var("KITTY_WINDOW_ID").is_ok() {
+            Some(GraphicsProtocol::Kitty)
+        } else if term_program == "iTerm.app"
+            || term_program == "WezTerm"
+            || lc_terminal == "iTerm2"
+        {
+            Some(GraphicsProtocol::ITerm2)
+        } else {
+            None
+        }
+    }
+
+    /// The escape sequence drawing a PNG image at the cursor, without moving it
+    pub fn image_sequence(self, png: &[u8]) -> String {
+        let data = base64::encode(png);
+
+        match self {
+            GraphicsProtocol::Kitty => {
+                let chunks = data
+                    .as_bytes()
+                    .chunks(KITTY_CHUNK_SIZE)
+                    .map(|chunk| std::str::from_utf8(chunk).unwrap())
+                    .collect::<Vec<&str>>();
+                chunks
+                    .iter()
+                    .enumerate()
+                    .map(|(i, chunk)| {
+                        let more = (i + 1 < chunks.len()) as u8;
+                        if i == 0 {
+                            format!(
+                                "\u{1b}_Ga=T,f=100,c={},r=1,C=1,q=2,m={};{}\u{1b}\\",
+                                IMAGE_WIDTH, more, chunk
+                            )
+                        } else {
+                            format!("\u{1b}_Gm={};{}\u{1b}\\", more, chunk)
+                        }
+                    })
+                    .collect()
+            }
+            GraphicsProtocol::ITerm2 => format!(
+                "\u{1b}7\u{1b}]1337;File=inline=1;width={};height=1;preserveAspectRatio=1:{}\u{7}\u{1b}8",
+                IMAGE_WIDTH, data
+            ),
+        }
+    }
+}
+
+/// Replace the symbols listed in the root `symbol_images` table with their image.
+///
+/// A segment is replaced if its text, without surrounding whitespace, is one of the
+/// symbols. Its text becomes the spaces the image is drawn over, so that the shell
+/// still knows how wide the prompt is. Symbols are left as-is if the terminal doesn't
+/// support any protocol, or if the image can't be read.
+pub fn apply_symbol_images<'a>(segments: Vec<Segment<'a>>, context: &Context) -> Vec<Segment<'a>> {
+    let config = context.config.get_root_config();
+    if config.symbol_images.is_empty() || context.is_screen_reader() {
+        return segments;
+    }
+    let protocol = match GraphicsProtocol::from_config(config.graphics_protocol) {
+        Some(protocol) => protocol,
+        None => return segments,
+    };
+
+    let mut images: HashMap<&str, Option<String>> = HashMap::new();
+    segments
+        .into_iter()
+        .map(|segment| {
+            let symbol = segment.value.trim();
+            let path = match config.symbol_images.get(symbol) {
+                Some(path) if !symbol.is_empty() => *path,
+                _ => return segment,
+            };
+            let image = images
+                .entry(path)
+                .or_insert_with(|| read_image(path).map(|png| protocol.image_sequence(&png)));
+
+            match image {
+                Some(image) => {
+                    let trailing = segment.value.len() - segment.value.trim_end().len();
+                    Segment {
+                        value: " ".repeat(IMAGE_WIDTH + trailing).into(),
+                        image: Some(image.clone()),
+                        ..segment
+                    }
+                }
+                None => segment,
+            }
+        })
+        .collect()
+}
+
+fn read_image(path: &str) -> Option<Vec<u8>> {
+    let path = match path.strip_prefix("~/") {
+        Some(relative) => dirs_next::home_dir()?.join(relative),
+        None => PathBuf::from(path),
+    };
+
+    fs::read(&path)
+        .map_err(|error| log::warn!("Unable to read image {}: {}", path.display(), error))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_from_config() {
+        assert_eq!(
+            GraphicsProtocol::from_config("kitty"),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            GraphicsProtocol::from_config("iterm2"),
+            Some(GraphicsProtocol::ITerm2)
+        );
+        assert_eq!(GraphicsProtocol::from_config("none"), None);
+    }
+
+    #[test]
+    fn iterm2_image_sequence() {
+        assert_eq!(
+            GraphicsProtocol::ITerm2.image_sequence(b"png"),
+            "\u{1b}7\u{1b}]1337;File=inline=1;width=2;height=1;preserveAspectRatio=1:cG5n\u{7}\u{1b}8"
+        );
+    }
+
+    #[test]
+    fn kitty_image_sequence() {
+        assert_eq!(
+            GraphicsProtocol::Kitty.image_sequence(b"png"),
+            "\u{1b}_Ga=T,f=100,c=2,r=1,C=1,q=2,m=0;cG5n\u{1b}\\"
+        );
+
+        // Large images are sent in several chunks
+        let sequence = GraphicsProtocol::Kitty.image_sequence(&[0; 4000]);
+        assert!(sequence.starts_with("\u{1b}_Ga=T,f=100,c=2,r=1,C=1,q=2,m=1;"));
+        assert!(sequence.contains("\u{1b}\\\u{1b}_Gm=0;"));
+    }
+}
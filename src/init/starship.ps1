@@ -3,6 +3,10 @@
 # Starship assumes UTF-8
 [Console]::OutputEncoding = [System.Text.Encoding]::UTF8
 function global:prompt {
+    # Save the status of the last command before anything else overwrites it.
+    # `$?` is false when a cmdlet fails, which doesn't change `$LASTEXITCODE`.
+    $origDollarQuestion = $global:?
+    $origLastExitCode = $global:LASTEXITCODE
     $out = $null
     # @ makes sure the result is an array even if single or no values are returned
     $jobs = @(Get-Job | Where-Object { $_.State -eq 'Running' }).Count
@@ -13,14 +17,17 @@ function global:prompt {
     if ($lastCmd = Get-History -Count 1) {
         $duration = [math]::Round(($lastCmd.EndExecutionTime - $lastCmd.StartExecutionTime).TotalMilliseconds)
         # & ensures the path is interpreted as something to execute
-        $out = @(&::STARSHIP:: prompt "--path=$current_directory" --status=$lastexitcode --jobs=$jobs --cmd-duration=$duration)
+        $out = @(&::STARSHIP:: prompt "--path=$current_directory" --status=$origLastExitCode --cmd-success=$origDollarQuestion --jobs=$jobs --cmd-duration=$duration)
     } else {
-        $out = @(&::STARSHIP:: prompt "--path=$current_directory" --status=$lastexitcode --jobs=$jobs)
+        $out = @(&::STARSHIP:: prompt "--path=$current_directory" --status=$origLastExitCode --cmd-success=$origDollarQuestion --jobs=$jobs)
     }
 
     # Convert stdout (array of lines) to expected return type string
     # `n is an escaped newline
     $out -join "`n"
+
+    # Restore the exit code of the last native command, which was overwritten by starship
+    $global:LASTEXITCODE = $origLastExitCode
 }
 
 $ENV:STARSHIP_SHELL = "powershell"
@@ -6,8 +6,12 @@ pub mod config;
 pub mod configs;
 pub mod context;
 pub mod formatter;
+pub mod graphics;
+pub mod metrics;
 pub mod module;
 pub mod modules;
+pub mod panic_hook;
 pub mod print;
 pub mod segment;
+mod tool_versions;
 mod utils;
@@ -11,12 +11,17 @@ mod config;
 mod configs;
 mod configure;
 mod context;
+mod diff;
 mod formatter;
+mod graphics;
 mod init;
+mod metrics;
 mod module;
 mod modules;
+mod panic_hook;
 mod print;
 mod segment;
+mod tool_versions;
 mod utils;
 
 use crate::module::ALL_MODULES;
@@ -32,6 +37,12 @@ fn main() {
         .help("The status code of the previously run command")
         .takes_value(true);
 
+    let cmd_success_arg = Arg::with_name("cmd_success")
+        .long("cmd-success")
+        .value_name("CMD_SUCCESS")
+        .help("Whether the previously run command succeeded, as reported by PowerShell's `$?`")
+        .takes_value(true);
+
     let path_arg = Arg::with_name("path")
         .short("p")
         .long("path")
@@ -68,6 +79,19 @@ fn main() {
         .help("The number of currently running jobs")
         .takes_value(true);
 
+    let metrics_file_arg = Arg::with_name("metrics_file")
+        .long("metrics-file")
+        .value_name("PATH")
+        .help("Append module timings and cache counters to the given file")
+        .takes_value(true);
+
+    let escape_arg = Arg::with_name("escape")
+        .long("escape")
+        .value_name("ESCAPE")
+        .help("How the prompt is encoded: wrapped for the shell (ps1), without wrapping (raw) or as plain text (none)")
+        .possible_values(&["ps1", "raw", "none"])
+        .takes_value(true);
+
     let init_scripts_arg = Arg::with_name("print_full_init")
         .long("print-full-init")
         .help("Print the main initialization script (as opposed to the init stub)");
@@ -91,10 +115,13 @@ fn main() {
                 SubCommand::with_name("prompt")
                     .about("Prints the full starship prompt")
                     .arg(&status_code_arg)
+                    .arg(&cmd_success_arg)
                     .arg(&path_arg)
                     .arg(&cmd_duration_arg)
                     .arg(&keymap_arg)
-                    .arg(&jobs_arg),
+                    .arg(&jobs_arg)
+                    .arg(&metrics_file_arg)
+                    .arg(&escape_arg),
             )
             .subcommand(
                 SubCommand::with_name("module")
@@ -112,6 +139,7 @@ fn main() {
                             .help("List out all supported modules"),
                     )
                     .arg(&status_code_arg)
+                    .arg(&cmd_success_arg)
                     .arg(&path_arg)
                     .arg(&cmd_duration_arg)
                     .arg(&keymap_arg)
@@ -140,6 +168,26 @@ fn main() {
             .subcommand(
                 SubCommand::with_name("explain").about("Explains the currently showing modules"),
             )
+            .subcommand(
+                SubCommand::with_name("diff")
+                    .about("Shows how the prompt changes between two configuration files")
+                    .arg(
+                        Arg::with_name("config")
+                            .long("config")
+                            .value_name("FILE")
+                            .help("The configuration files to compare, given twice: old, then new")
+                            .takes_value(true)
+                            .multiple(true)
+                            .number_of_values(1)
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::with_name("side_by_side")
+                            .long("side-by-side")
+                            .help("Show the prompts in two columns instead of a unified diff"),
+                    )
+                    .arg(&path_arg),
+            )
             .subcommand(
                 SubCommand::with_name("completions")
                     .about("Generate starship shell completions for your shell to stdout")
@@ -198,6 +246,7 @@ fn main() {
             }
         }
         ("explain", Some(sub_m)) => print::explain(sub_m.clone()),
+        ("diff", Some(sub_m)) => diff::diff(sub_m.clone()),
         ("completions", Some(sub_m)) => {
             let shell: Shell = sub_m
                 .value_of("shell")
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as FmtWrite;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Counters collected while rendering a prompt, written out with `--metrics-file`.
+///
+/// Every line follows the Prometheus text format (`name{label="value"} value timestamp`),
+/// so that an appended file can be graphed as-is.
+#[derive(Default)]
+pub struct Metrics {
+    module_durations: Mutex<Vec<(String, Duration)>>,
+    caches: Mutex<BTreeMap<&'static str, CacheCounter>>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct CacheCounter {
+    hits: usize,
+    misses: usize,
+}
+
+impl Metrics {
+    /// Record the time spent rendering a module
+    pub fn record_module(&self, module: &str, duration: Duration) {
+        self.module_durations
+            .lock()
+            .unwrap()
+            .push((module.to_string(), duration));
+    }
+
+    /// Record a lookup in one of the caches held by the context
+    pub fn record_cache(&self, cache: &'static str, hit: bool) {
+        let mut caches = self.caches.lock().unwrap();
+        let counter = caches.entry(cache).or_default();
+        if hit {
+            counter.hits += 1;
+        } else {
+            counter.misses += 1;
+        }
+    }
+
+    /// Render the collected metrics, one sample per line
+    pub fn render(&self, prompt_duration: Duration, timestamp_ms: u128) -> String {
+        let mut output = String::new();
+
+        let mut durations = self.module_durations.lock().unwrap().clone();
+        durations.sort_by(|a, b| a.0.cmp(&b.0));
+        for (module, duration) in durations {
+            writeln!(
+                output,
+                "starship_module_duration_seconds{{module=\"{}\"}} {:.6} {}",
+                escape_label(&module),
+                duration.as_secs_f64(),
+                timestamp_ms
+            )
+            .unwrap();
+        }
+
+        for (cache, counter) in self.caches.lock().unwrap().iter() {
+            writeln!(
+                output,
+                "starship_cache_hits_total{{cache=\"{}\"}} {} {}",
+                cache, counter.hits, timestamp_ms
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "starship_cache_misses_total{{cache=\"{}\"}} {} {}",
+                cache, counter.misses, timestamp_ms
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            output,
+            "starship_prompt_duration_seconds {:.6} {}",
+            prompt_duration.as_secs_f64(),
+            timestamp_ms
+        )
+        .unwrap();
+
+        output
+    }
+
+    /// Append the collected metrics to `path`, creating the file if needed
+    pub fn append_to(&self, path: &Path, prompt_duration: Duration) -> io::Result<()> {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_millis())
+            .unwrap_or_default();
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(self.render(prompt_duration, timestamp_ms).as_bytes())
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_metrics() {
+        let metrics = Metrics::default();
+        metrics.record_module("rust", Duration::from_micros(1500));
+        metrics.record_module("custom.\"quoted\"", Duration::from_millis(2));
+        metrics.record_cache("programs", false);
+        metrics.record_cache("programs", true);
+        metrics.record_cache("programs", true);
+        metrics.record_cache("repo", false);
+
+        let expected = "\
+starship_module_duration_seconds{module=\"custom.\\\"quoted\\\"\"} 0.002000 42
+starship_module_duration_seconds{module=\"rust\"} 0.001500 42
+starship_cache_hits_total{cache=\"programs\"} 2 42
+starship_cache_misses_total{cache=\"programs\"} 1 42
+starship_cache_hits_total{cache=\"repo\"} 0 42
+starship_cache_misses_total{cache=\"repo\"} 1 42
+starship_prompt_duration_seconds 0.010000 42
+";
+        assert_eq!(metrics.render(Duration::from_millis(10), 42), expected);
+    }
+}
@@ -1,32 +1,51 @@
 use crate::context::Shell;
 use crate::segment::Segment;
-use crate::utils::wrap_colorseq_for_shell;
+use crate::utils::{wrap_colorseq_for_shell, wrap_unprintable_for_shell};
 use ansi_term::{ANSIString, ANSIStrings};
+use std::borrow::Cow;
 use std::fmt;
 
 // List of all modules
 // Keep these ordered alphabetically.
 // Default ordering is handled in configs/mod.rs
 pub const ALL_MODULES: &[&str] = &[
+    "android",
     "aws",
+    "azure",
     #[cfg(feature = "battery")]
     "battery",
+    "bazel",
+    "buf",
     "character",
     "cmake",
     "cmd_duration",
+    "cobol",
     "conda",
+    "container",
+    "daml",
+    "dart",
     "directory",
+    "direnv",
     "docker_context",
     "dotnet",
     "elixir",
     "elm",
     "erlang",
     "env_var",
+    "fennel",
+    "flutter",
+    "fortran",
+    "gleam",
+    "gcloud",
     "git_branch",
     "git_commit",
     "git_state",
     "git_status",
     "golang",
+    "gradle",
+    "guix_shell",
+    "haskell",
+    "haxe",
     "hg_branch",
     "hostname",
     "java",
@@ -35,21 +54,33 @@ pub const ALL_MODULES: &[&str] = &[
     "kubernetes",
     "line_break",
     "memory_usage",
+    "meson",
+    "mojo",
     "nim",
     "nix_shell",
     "nodejs",
     "ocaml",
+    "openstack",
     "package",
+    "project",
     "purescript",
     "python",
+    "raku",
+    "red",
+    "rlang",
     "ruby",
     "crystal",
     "rust",
     "php",
+    "solidity",
+    "spack",
     "terraform",
     "singularity",
     "time",
+    "typst",
     "username",
+    "vagrant",
+    "vlang",
     "zig",
 ];
 
@@ -63,25 +94,25 @@ pub struct Module<'a> {
     _name: String,
 
     /// The module's description
-    description: String,
+    description: Cow<'a, str>,
 
     /// The collection of segments that compose this module.
-    pub segments: Vec<Segment>,
+    pub segments: Vec<Segment<'a>>,
 }
 
 impl<'a> Module<'a> {
     /// Creates a module with no segments.
-    pub fn new(name: &str, desc: &str, config: Option<&'a toml::Value>) -> Module<'a> {
+    pub fn new(name: &str, desc: &'a str, config: Option<&'a toml::Value>) -> Module<'a> {
         Module {
             config,
             _name: name.to_string(),
-            description: desc.to_string(),
+            description: Cow::Borrowed(desc),
             segments: Vec::new(),
         }
     }
 
     /// Set segments in module
-    pub fn set_segments(&mut self, segments: Vec<Segment>) {
+    pub fn set_segments(&mut self, segments: Vec<Segment<'a>>) {
         self.segments = segments;
     }
 
@@ -91,7 +122,7 @@ impl<'a> Module<'a> {
     }
 
     /// Get module's description
-    pub fn get_description(&self) -> &String {
+    pub fn get_description(&self) -> &str {
         &self.description
     }
 
@@ -118,11 +149,25 @@ impl<'a> Module<'a> {
             .map(Segment::ansi_string)
             .collect::<Vec<ANSIString>>();
 
-        match shell {
+        let ansi_strings = match shell {
             Shell::Bash => ansi_strings_modified(ansi_strings, shell),
             Shell::Zsh => ansi_strings_modified(ansi_strings, shell),
             _ => ansi_strings,
-        }
+        };
+
+        // Images are drawn before the spaces of their segment, and are invisible to the shell
+        self.segments
+            .iter()
+            .zip(ansi_strings)
+            .map(|(segment, ansi)| match &segment.image {
+                Some(image) => ANSIString::from(format!(
+                    "{}{}",
+                    wrap_unprintable_for_shell(image, shell),
+                    ansi
+                )),
+                None => ansi,
+            })
+            .collect()
     }
 }
 
@@ -154,7 +199,7 @@ mod tests {
         let module = Module {
             config: None,
             _name: name.to_string(),
-            description: desc.to_string(),
+            description: Cow::Borrowed(desc),
             segments: Vec::new(),
         };
 
@@ -168,7 +213,7 @@ mod tests {
         let module = Module {
             config: None,
             _name: name.to_string(),
-            description: desc.to_string(),
+            description: Cow::Borrowed(desc),
             segments: vec![Segment::new("test_segment")],
         };
 
@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::android::AndroidConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// The gradle build files read for the Android configuration, relative to the current
+/// directory. The `app` module holds it in projects created by Android Studio.
+const GRADLE_FILES: &[&str] = &[
+    "build.gradle",
+    "build.gradle.kts",
+    "app/build.gradle",
+    "app/build.gradle.kts",
+];
+
+/// Creates a module with the Android SDK and NDK versions targeted by the current project
+///
+/// Will display the Android versions if any of the following criteria are met:
+///     - Current directory contains an `AndroidManifest.xml` file
+///     - Current directory contains a `build.gradle` or `build.gradle.kts` file
+///       using an Android gradle plugin (`com.android.*`)
+///
+/// The versions are read from the gradle files and `local.properties`, without running gradle.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("android");
+    let config: AndroidConfig = AndroidConfig::try_load(module.config);
+
+    let has_android_files = context
+        .try_begin_module_scan("android")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+    let has_gradle_files = context
+        .try_begin_scan()?
+        .set_files(&["build.gradle", "build.gradle.kts"])
+        .is_match();
+
+    if !has_android_files && !has_gradle_files {
+        return None;
+    }
+
+    let gradle_files: Vec<String> = GRADLE_FILES
+        .iter()
+        .filter_map(|file| utils::read_file(context.current_dir.join(file)).ok())
+        .collect();
+    let uses_android_plugin = gradle_files
+        .iter()
+        .any(|contents| contents.contains("com.android."));
+
+    if !has_android_files && !uses_android_plugin {
+        return None;
+    }
+
+    let compile_sdk = gradle_files.iter().find_map(|contents| {
+        find_gradle_value(contents, "compileSdkVersion")
+            .or_else(|| find_gradle_value(contents, "compileSdk"))
+    });
+    let ndk = gradle_files
+        .iter()
+        .find_map(|contents| find_gradle_value(contents, "ndkVersion"))
+        .or_else(|| ndk_from_local_properties(&context.current_dir));
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "compile_sdk" => compile_sdk.as_deref().map(Ok),
+                "ndk" => ndk.as_deref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `android`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Find the value of a setting in a Groovy or Kotlin gradle file, which can be
+/// written as `key 30`, `key = 30`, `key(30)` or `key "30"`
+fn find_gradle_value(contents: &str, key: &str) -> Option<String> {
+    contents.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix(key)?;
+        if !rest.starts_with(&[' ', '\t', '=', '('][..]) {
+            return None;
+        }
+
+        let value: String = rest
+            .trim_start_matches(&[' ', '\t', '=', '(', '"', '\''][..])
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || ['.', '-', '_'].contains(c))
+            .collect();
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    })
+}
+
+/// The NDK version from the `ndk.dir` set in `local.properties`, whose last component is
+/// the version for the NDKs installed side by side by the SDK manager
+fn ndk_from_local_properties(dir: &Path) -> Option<String> {
+    let local_properties = utils::read_file(dir.join("local.properties")).ok()?;
+    let ndk_dir = local_properties
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("ndk.dir="))?;
+
+    let version = ndk_dir
+        .trim()
+        .trim_end_matches(&['/', '\\'][..])
+        .rsplit(&['/', '\\'][..])
+        .next()?;
+    if version.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_gradle_value;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    }
+
+    #[test]
+    fn test_find_gradle_value() {
+        let groovy = "android {\n    compileSdkVersion 30\n    ndkVersion \"21.3.6528147\"\n}\n";
+        assert_eq!(
+            find_gradle_value(groovy, "compileSdkVersion"),
+            Some("30".to_string())
+        );
+        assert_eq!(
+            find_gradle_value(groovy, "ndkVersion"),
+            Some("21.3.6528147".to_string())
+        );
+        assert_eq!(find_gradle_value(groovy, "compileSdk"), None);
+
+        let kotlin = "android {\n    compileSdk = 34\n    compileSdkVersion(\"android-S\")\n}\n";
+        assert_eq!(
+            find_gradle_value(kotlin, "compileSdk"),
+            Some("34".to_string())
+        );
+        assert_eq!(
+            find_gradle_value(kotlin, "compileSdkVersion"),
+            Some("android-S".to_string())
+        );
+    }
+
+    #[test]
+    fn folder_without_android_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_file(
+            &dir.path().join("build.gradle"),
+            "plugins {\n    id 'java'\n}\n",
+        )?;
+        let actual = render_module("android", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_android_manifest() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("AndroidManifest.xml"))?.sync_all()?;
+        let actual = render_module("android", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("🤖 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_android_app_module() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir(dir.path().join("app"))?;
+        write_file(
+            &dir.path().join("build.gradle"),
+            "buildscript {\n    dependencies {\n        classpath 'com.android.tools.build:gradle:4.0.1'\n    }\n}\n",
+        )?;
+        write_file(
+            &dir.path().join("app/build.gradle.kts"),
+            "plugins {\n    id(\"com.android.application\")\n}\nandroid {\n    compileSdk = 34\n}\n",
+        )?;
+        write_file(
+            &dir.path().join("local.properties"),
+            "sdk.dir=/opt/android\nndk.dir=/opt/android/ndk/21.3.6528147\n",
+        )?;
+        let actual = render_module("android", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Green.bold().paint("🤖 API 34 NDK 21.3.6528147")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,85 @@
+use serde_json as json;
+use std::env;
+use std::path::PathBuf;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::azure::AzureConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+type SubscriptionName = String;
+type UserName = String;
+
+/// The directory of the Azure CLI configuration, `$AZURE_CONFIG_DIR` or `~/.azure`
+fn get_azure_config_dir() -> Option<PathBuf> {
+    env::var("AZURE_CONFIG_DIR")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs_next::home_dir()?.join(".azure")))
+}
+
+/// Find the selected subscription in the contents of `azureProfile.json`
+fn parse_azure_profile(contents: &str) -> Option<(SubscriptionName, Option<UserName>)> {
+    // The Azure CLI writes the file with a byte order mark
+    let profile: json::Value = json::from_str(contents.trim_start_matches('\u{feff}')).ok()?;
+    let subscription = profile
+        .get("subscriptions")?
+        .as_array()?
+        .iter()
+        .find(|subscription| subscription.get("isDefault") == Some(&json::Value::Bool(true)))?;
+
+    let name = subscription.get("name")?.as_str()?.to_string();
+    let user = subscription
+        .get("user")
+        .and_then(|user| user.get("name"))
+        .and_then(json::Value::as_str)
+        .map(str::to_string);
+
+    Some((name, user))
+}
+
+/// Creates a module with the current Azure subscription
+///
+/// Will display the subscription selected with `az account set`, and the user signed in
+/// to it, read from `azureProfile.json` rather than by running the `az` command.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("azure");
+    let config: AzureConfig = AzureConfig::try_load(module.config);
+
+    let azure_profile = utils::read_file(get_azure_config_dir()?.join("azureProfile.json")).ok()?;
+    let (subscription, username) = parse_azure_profile(&azure_profile)?;
+
+    let subscription = match config.subscription_aliases.get(&subscription) {
+        Some(alias) => (*alias).to_string(),
+        None => subscription,
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "subscription" => Some(Ok(&subscription)),
+                "username" => username.as_ref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `azure`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
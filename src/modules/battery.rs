@@ -32,15 +32,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             let formatter = formatter
                 .map_meta(|variable, _| match variable {
                     "symbol" => match state {
-                        battery::State::Full => Some(config.full_symbol),
-                        battery::State::Charging => Some(config.charging_symbol),
-                        battery::State::Discharging => Some(config.discharging_symbol),
-                        battery::State::Unknown => config.unknown_symbol,
-                        battery::State::Empty => config.empty_symbol,
-                        _ => {
-                            log::debug!("Unhandled battery state `{}`", state);
-                            None
-                        }
+                        State::Full => Some(config.full_symbol),
+                        State::Charging => Some(
+                            display_style
+                                .charging_symbol
+                                .unwrap_or(config.charging_symbol),
+                        ),
+                        State::Discharging => Some(
+                            display_style
+                                .discharging_symbol
+                                .unwrap_or(config.discharging_symbol),
+                        ),
+                        State::Unknown => config.unknown_symbol,
+                        State::Empty => config.empty_symbol,
                     },
                     _ => None,
                 })
@@ -50,10 +54,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 })
                 .map(|variable| match variable {
                     "percentage" => Some(Ok(format!("{}{}", percentage.round(), percentage_char))),
+                    "state" => Some(Ok(state.name().to_string())),
                     _ => None,
                 });
 
-            match formatter.parse(None) {
+            match formatter.parse(None, Some(context)) {
                 Ok(format_string) => {
                     module.set_segments(format_string);
                     Some(module)
@@ -71,6 +76,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 }
 
+#[cfg(not(target_os = "android"))]
 fn get_battery_status() -> Option<BatteryStatus> {
     let battery_manager = battery::Manager::new().ok()?;
     let batteries = battery_manager.batteries().ok()?;
@@ -81,7 +87,7 @@ fn get_battery_status() -> Option<BatteryStatus> {
                 Some(BatteryInfo {
                     energy: battery.energy().value,
                     energy_full: battery.energy_full().value,
-                    state: battery.state(),
+                    state: battery.state().into(),
                 })
             }
             Err(e) => {
@@ -93,7 +99,7 @@ fn get_battery_status() -> Option<BatteryStatus> {
             BatteryInfo {
                 energy: 0.0,
                 energy_full: 0.0,
-                state: battery::State::Unknown,
+                state: State::Unknown,
             },
             |mut acc, x| {
                 acc.energy += x.energy;
@@ -114,13 +120,56 @@ fn get_battery_status() -> Option<BatteryStatus> {
     }
 }
 
+/// The `battery` crate doesn't build on Android, so the status is read from sysfs
+/// instead, falling back to `termux-battery-status` from Termux:API when sysfs is
+/// not readable.
+#[cfg(target_os = "android")]
+fn get_battery_status() -> Option<BatteryStatus> {
+    const SYSFS_BATTERY: &str = "/sys/class/power_supply/battery";
+
+    let from_sysfs = || -> Option<BatteryStatus> {
+        let capacity = crate::utils::read_file(format!("{}/capacity", SYSFS_BATTERY)).ok()?;
+        let status = crate::utils::read_file(format!("{}/status", SYSFS_BATTERY)).ok()?;
+        Some(BatteryStatus {
+            percentage: capacity.trim().parse().ok()?,
+            state: parse_android_state(status.trim()),
+        })
+    };
+
+    let from_termux_api = || -> Option<BatteryStatus> {
+        let output = crate::utils::exec_cmd("termux-battery-status", &[])?;
+        let status: serde_json::Value = serde_json::from_str(&output.stdout).ok()?;
+        Some(BatteryStatus {
+            percentage: status.get("percentage")?.as_f64()? as f32,
+            state: parse_android_state(status.get("status")?.as_str()?),
+        })
+    };
+
+    let battery = from_sysfs().or_else(from_termux_api)?;
+    log::debug!("Battery status: {:?}", battery);
+    Some(battery)
+}
+
+/// Parse the charging state reported by Android, either from sysfs ("Not charging")
+/// or from Termux:API ("NOT_CHARGING")
+#[cfg(target_os = "android")]
+fn parse_android_state(status: &str) -> State {
+    match status.to_lowercase().replace('_', " ").as_str() {
+        "charging" => State::Charging,
+        "discharging" | "not charging" => State::Discharging,
+        "full" => State::Full,
+        _ => State::Unknown,
+    }
+}
+
 /// the merge returns Charging if at least one is charging
 ///                   Discharging if at least one is Discharging
 ///                   Full if both are Full or one is Full and the other Unknow
 ///                   Empty if both are Empty or one is Empty and the other Unknow
 ///                   Unknown otherwise
-fn merge_battery_states(state1: battery::State, state2: battery::State) -> battery::State {
-    use battery::State::{Charging, Discharging, Unknown};
+#[cfg(not(target_os = "android"))]
+fn merge_battery_states(state1: State, state2: State) -> State {
+    use State::{Charging, Discharging, Unknown};
     if state1 == Charging || state2 == Charging {
         Charging
     } else if state1 == Discharging || state2 == Discharging {
@@ -136,14 +185,55 @@ fn merge_battery_states(state1: battery::State, state2: battery::State) -> batte
     }
 }
 
+/// The charging state of the battery, independent of the platform backend
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Unknown,
+    Charging,
+    Discharging,
+    Empty,
+    Full,
+}
+
+impl State {
+    /// The name of the state, as shown by `$state`
+    fn name(self) -> &'static str {
+        match self {
+            State::Unknown => "unknown",
+            State::Charging => "charging",
+            State::Discharging => "discharging",
+            State::Empty => "empty",
+            State::Full => "full",
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
+impl From<battery::State> for State {
+    fn from(state: battery::State) -> Self {
+        match state {
+            battery::State::Charging => State::Charging,
+            battery::State::Discharging => State::Discharging,
+            battery::State::Empty => State::Empty,
+            battery::State::Full => State::Full,
+            battery::State::Unknown => State::Unknown,
+            _ => {
+                log::debug!("Unhandled battery state `{}`", state);
+                State::Unknown
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "android"))]
 struct BatteryInfo {
     energy: f32,
     energy_full: f32,
-    state: battery::State,
+    state: State,
 }
 
 #[derive(Debug)]
 struct BatteryStatus {
     percentage: f32,
-    state: battery::State,
+    state: State,
 }
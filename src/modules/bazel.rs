@@ -0,0 +1,165 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::bazel::BazelConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+/// Creates a module with the Bazel version pinned by the current workspace
+///
+/// Will display the Bazel version if any of the following criteria are met:
+///     - Current directory contains a `WORKSPACE` or `WORKSPACE.bazel` file
+///     - Current directory contains a `MODULE.bazel` file
+///     - Current directory contains a `.bazelversion` file
+///
+/// The version is read from `.bazelversion`, falling back to `USE_BAZEL_VERSION` in
+/// `.bazeliskrc`, so Bazel itself is never started.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("bazel");
+    let config: BazelConfig = BazelConfig::try_load(module.config);
+
+    let is_bazel_project = context
+        .try_begin_module_scan("bazel")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_bazel_project {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => get_pinned_version(context).map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    ))
+                }),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `bazel`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_pinned_version(context: &Context) -> Option<String> {
+    utils::read_file(context.current_dir.join(".bazelversion"))
+        .ok()
+        .and_then(|bazelversion| parse_bazelversion(&bazelversion))
+        .or_else(|| {
+            utils::read_file(context.current_dir.join(".bazeliskrc"))
+                .ok()
+                .and_then(|bazeliskrc| parse_bazeliskrc(&bazeliskrc))
+        })
+}
+
+fn parse_bazelversion(bazelversion: &str) -> Option<String> {
+    let version = bazelversion.lines().next()?.trim();
+    format_bazel_version(version)
+}
+
+fn parse_bazeliskrc(bazeliskrc: &str) -> Option<String> {
+    // USE_BAZEL_VERSION=3.7.0
+    let version = bazeliskrc
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("USE_BAZEL_VERSION"))?
+        .trim_start()
+        .strip_prefix('=')?
+        .trim();
+    format_bazel_version(version)
+}
+
+fn format_bazel_version(version: &str) -> Option<String> {
+    // Bazelisk also accepts labels such as `latest`, which are not versions
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_bazeliskrc, parse_bazelversion};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_parse_bazelversion() {
+        assert_eq!(parse_bazelversion("3.7.0\n"), Some("3.7.0".to_string()));
+        assert_eq!(parse_bazelversion("latest\n"), None);
+        assert_eq!(parse_bazelversion(""), None);
+    }
+
+    #[test]
+    fn test_parse_bazeliskrc() {
+        let bazeliskrc = "BAZELISK_BASE_URL=https://example.com\nUSE_BAZEL_VERSION = 3.4.1\n";
+        assert_eq!(parse_bazeliskrc(bazeliskrc), Some("3.4.1".to_string()));
+        assert_eq!(parse_bazeliskrc("BAZELISK_HOME=/tmp"), None);
+    }
+
+    #[test]
+    fn folder_without_bazel_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("bazel", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_unpinned_workspace() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("WORKSPACE"))?.sync_all()?;
+        let actual = render_module("bazel", dir.path(), None);
+        let expected = Some(format!("via {}", Color::Green.bold().paint("🍃 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_bazelversion() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join(".bazelversion"))?;
+        file.write_all(b"3.7.0\n")?;
+        file.sync_all()?;
+        let actual = render_module("bazel", dir.path(), None);
+        let expected = Some(format!("via {}", Color::Green.bold().paint("🍃 v3.7.0 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_bazeliskrc() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("MODULE.bazel"))?.sync_all()?;
+        let mut file = File::create(dir.path().join(".bazeliskrc"))?;
+        file.write_all(b"USE_BAZEL_VERSION=3.4.1\n")?;
+        file.sync_all()?;
+        let actual = render_module("bazel", dir.path(), None);
+        let expected = Some(format!("via {}", Color::Green.bold().paint("🍃 v3.4.1 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,111 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::buf::BufConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current buf CLI version
+///
+/// Will display the buf version if any of the following criteria are met:
+///     - Current directory contains a `buf.yaml` file
+///     - Current directory contains a `buf.gen.yaml` file
+///     - Current directory contains a `buf.work.yaml` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("buf");
+    let config: BufConfig = BufConfig::try_load(module.config);
+
+    let is_buf_project = context
+        .try_begin_module_scan("buf")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_buf_project {
+        return None;
+    }
+
+    let buf_version = context.exec_cmd("buf", &["--version"])?;
+    // Older releases of buf print the version to stderr instead of stdout
+    let module_version = parse_buf_version(&buf_version.stdout)
+        .or_else(|| parse_buf_version(&buf_version.stderr))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `buf`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_buf_version(buf_version: &str) -> Option<String> {
+    let version = buf_version.split_whitespace().next()?;
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_buf_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_buf_version() {
+        assert_eq!(parse_buf_version("0.24.0\n"), Some("0.24.0".to_string()));
+        assert_eq!(parse_buf_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_buf_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("buf", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_buf_yaml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("buf.yaml"))?.sync_all()?;
+        let actual = render_module("buf", dir.path(), None);
+        let expected = Some(format!("with {} ", Color::Blue.bold().paint("🦬 v0.24.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_buf_work_yaml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("buf.work.yaml"))?.sync_all()?;
+        let actual = render_module("buf", dir.path(), None);
+        let expected = Some(format!("with {} ", Color::Blue.bold().paint("🦬 v0.24.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
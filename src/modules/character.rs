@@ -1,5 +1,6 @@
 use super::{Context, Module, RootModuleConfig, Shell};
 use crate::configs::character::CharacterConfig;
+use crate::context::CommandStatus;
 use crate::formatter::StringFormatter;
 
 /// Creates a module for the prompt character
@@ -10,6 +11,11 @@ use crate::formatter::StringFormatter;
 ///   (green arrow by default)
 /// - If the exit-code was anything else, it will be formatted with
 ///   `error_symbol` (red arrow by default)
+/// - In PowerShell, if a cmdlet failed without changing the exit-code, it will be
+///   formatted with `cmdlet_error_symbol` (yellow arrow by default)
+///
+/// When set, `root_symbol`, `ssh_symbol` and `ssh_root_symbol` take the place of the
+/// status symbols while running as root, in an SSH session, or both.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     enum ShellEditMode {
         Normal,
@@ -22,11 +28,9 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let config: CharacterConfig = CharacterConfig::try_load(module.config);
 
     let props = &context.properties;
-    let exit_code_default = String::from("0");
-    let exit_code = props.get("status_code").unwrap_or(&exit_code_default);
     let keymap_default = String::from("viins");
     let keymap = props.get("keymap").unwrap_or(&keymap_default);
-    let exit_success = exit_code == "0";
+    let status = context.command_status();
 
     // Match shell "keymap" names to normalized vi modes
     // NOTE: in vi mode, fish reports normal mode as "default".
@@ -40,13 +44,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let symbol = match mode {
         ShellEditMode::Normal => config.vicmd_symbol,
-        ShellEditMode::Insert => {
-            if exit_success {
-                config.success_symbol
-            } else {
-                config.error_symbol
-            }
-        }
+        ShellEditMode::Insert => match (privilege_symbol(context, &config), status) {
+            (Some(symbol), _) => symbol,
+            (None, CommandStatus::Success) => config.success_symbol,
+            (None, CommandStatus::Error) => config.error_symbol,
+            (None, CommandStatus::CmdletError) => config.cmdlet_error_symbol,
+        },
     };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
@@ -55,7 +58,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "symbol" => Some(symbol),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -68,3 +71,28 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     Some(module)
 }
+
+/// Pick the symbol warning about a root shell or a remote session, if one is configured.
+/// A remote root shell uses `ssh_root_symbol`, falling back to `root_symbol`.
+fn privilege_symbol<'a>(context: &Context, config: &CharacterConfig<'a>) -> Option<&'a str> {
+    // Avoid looking up the user id when none of the symbols are used
+    if config.root_symbol.is_empty()
+        && config.ssh_symbol.is_empty()
+        && config.ssh_root_symbol.is_empty()
+    {
+        return None;
+    }
+
+    let symbol = match (context.is_root(), context.is_ssh_session()) {
+        (true, true) if !config.ssh_root_symbol.is_empty() => config.ssh_root_symbol,
+        (true, _) => config.root_symbol,
+        (false, true) => config.ssh_symbol,
+        (false, false) => "",
+    };
+
+    if symbol.is_empty() {
+        None
+    } else {
+        Some(symbol)
+    }
+}
@@ -1,25 +1,28 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::cmake::CMakeConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current CMake version
 ///
 /// Will display the CMake version if any of the following criteria are met:
 ///     - The current directory contains a `CMakeLists.txt` file
+///     - The current directory contains a `CMakeCache.txt` file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cmake");
+    let config = CMakeConfig::try_load(module.config);
+
     let is_cmake_project = context
-        .try_begin_scan()?
-        .set_files(&["CMakeLists.txt"])
+        .try_begin_module_scan("cmake")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_cmake_project {
         return None;
     }
 
-    let mut module = context.new_module("cmake");
-    let config = CMakeConfig::try_load(module.config);
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -31,13 +34,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => utils::exec_cmd("cmake", &["--version"])
+                "version" => context
+                    .exec_cmd("cmake", &["--version"])
                     .map(|output| format_cmake_version(&output.stdout))
                     .flatten()
-                    .map(Ok),
+                    .map(|version| {
+                        Ok(VersionFormatter::format_module_version(
+                            module.get_name(),
+                            &version,
+                            config.version_format,
+                        ))
+                    }),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -53,7 +63,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
 fn format_cmake_version(cmake_version: &str) -> Option<String> {
     let version = cmake_version.split_whitespace().nth(2)?;
-    Some(format!("v{}", version))
+    Some(version.to_string())
 }
 
 #[cfg(test)]
@@ -81,4 +91,14 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn buildfolder_with_cmake_cache() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("CMakeCache.txt"))?.sync_all()?;
+        let actual = render_module("cmake", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🛆 v3.17.3")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
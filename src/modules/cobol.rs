@@ -0,0 +1,130 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::cobol::CobolConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current COBOL version
+///
+/// Will display the COBOL version if any of the following criteria are met:
+///     - Current directory contains a file with `.cob`/`.COB` extension
+///     - Current directory contains a file with `.cbl`/`.CBL` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("cobol");
+    let config: CobolConfig = CobolConfig::try_load(module.config);
+
+    let is_cobol_project = context
+        .try_begin_module_scan("cobol")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_cobol_project {
+        return None;
+    }
+
+    let cobol_version = context.exec_cmd("cobc", &["-version"])?.stdout;
+    let module_version = parse_cobol_version(&cobol_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `cobol`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_cobol_version(cobol_version: &str) -> Option<String> {
+    let version = cobol_version
+        // take the first line, "cobc (GnuCOBOL) 3.1-rc1.0"
+        .lines()
+        .next()?
+        // split into ["cobc", "(GnuCOBOL)", "3.1-rc1.0"]
+        .split_whitespace()
+        // return "3.1-rc1.0"
+        .nth(2)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_cobol_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_cobol_version() {
+        let cobol_version = "\
+cobc (GnuCOBOL) 3.1-rc1.0
+Built     Aug 02 2020 20:04:12
+Packaged  Jul 01 2020 00:39:43 UTC
+C version \"10.1.0\"";
+        assert_eq!(
+            parse_cobol_version(cobol_version),
+            Some("3.1-rc1.0".to_string())
+        );
+        assert_eq!(parse_cobol_version("cobc"), None);
+    }
+
+    #[test]
+    fn folder_without_cobol_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("cobol", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_cob_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.cob"))?.sync_all()?;
+        let actual = render_module("cobol", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("⚙️ v3.1-rc1.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_uppercase_cbl_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("MAIN.CBL"))?.sync_all()?;
+        let actual = render_module("cobol", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("⚙️ v3.1-rc1.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
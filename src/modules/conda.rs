@@ -8,7 +8,8 @@ use crate::formatter::StringFormatter;
 
 /// Creates a module with the current Conda environment
 ///
-/// Will display the Conda environment iff `$CONDA_DEFAULT_ENV` is set.
+/// Will display the Conda environment iff `$CONDA_DEFAULT_ENV` is set, unless it is
+/// the `base` environment and `ignore_base` is enabled.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     // Reference implementation: https://github.com/denysdovhan/spaceship-prompt/blob/master/sections/conda.zsh
     let conda_env = env::var("CONDA_DEFAULT_ENV").unwrap_or_else(|_| "".into());
@@ -19,7 +20,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("conda");
     let config: CondaConfig = CondaConfig::try_load(module.config);
 
-    let conda_env = truncate(conda_env, config.truncation_length);
+    if config.ignore_base && conda_env == "base" {
+        return None;
+    }
+
+    // Environments created with `conda create -p` are named after their path, which
+    // uses backslashes on Windows
+    let conda_env = match config.truncation_length {
+        0 => conda_env,
+        length => truncate(conda_env.replace('\\', "/"), length),
+    };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -35,7 +45,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "environment" => Some(Ok(conda_env.as_str())),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
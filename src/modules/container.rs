@@ -0,0 +1,160 @@
+use std::env;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::container::ContainerConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// Creates a module with the name of the container the shell is running in
+///
+/// Will display the container if any of the following criteria are met:
+///     - `/run/.containerenv` exists (podman, toolbox and distrobox)
+///     - `/.dockerenv` exists (docker)
+///     - `$container` is set (systemd-nspawn, LXC and most OCI runtimes)
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let container_name = get_container_name(Path::new("/"), env::var("container").ok())?;
+
+    let mut module = context.new_module("container");
+    let config: ContainerConfig = ContainerConfig::try_load(module.config);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "name" => Some(Ok(&container_name)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `container`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Find the image of the container mounted at `root`, falling back to the name of the
+/// container engine when the image can't be told
+fn get_container_name(root: &Path, container_env: Option<String>) -> Option<String> {
+    let containerenv = root.join("run/.containerenv");
+    if containerenv.exists() {
+        let image = utils::read_file(&containerenv)
+            .ok()
+            .and_then(|contents| parse_containerenv_image(&contents));
+        return Some(image.unwrap_or_else(|| "podman".to_string()));
+    }
+
+    if root.join(".dockerenv").exists() {
+        return Some("Docker".to_string());
+    }
+
+    container_env.filter(|container| !container.is_empty())
+}
+
+/// Find the image name in the contents of `/run/.containerenv`, without its registry
+/// or tag
+fn parse_containerenv_image(containerenv: &str) -> Option<String> {
+    // image="registry.fedoraproject.org/fedora-toolbox:33"
+    let image = containerenv
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("image="))?
+        .trim_matches('"');
+    let name = image.rsplit('/').next()?;
+    let name = name.split(':').next()?;
+    if name.is_empty() {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_container_name, parse_containerenv_image};
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_parse_containerenv_image() {
+        let containerenv = "\
+engine=\"podman-2.1.1\"
+name=\"fedora-toolbox-33\"
+id=\"4d37b1b4d8e2\"
+image=\"registry.fedoraproject.org/fedora-toolbox:33\"
+rootless=1
+";
+        assert_eq!(
+            parse_containerenv_image(containerenv),
+            Some("fedora-toolbox".to_string())
+        );
+        assert_eq!(parse_containerenv_image(""), None);
+    }
+
+    #[test]
+    fn not_in_container() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        assert_eq!(get_container_name(root.path(), None), None);
+        assert_eq!(get_container_name(root.path(), Some(String::new())), None);
+        root.close()
+    }
+
+    #[test]
+    fn in_podman_container() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        fs::create_dir(root.path().join("run"))?;
+        let mut file = File::create(root.path().join("run/.containerenv"))?;
+        file.write_all(b"image=\"docker.io/library/ubuntu:20.04\"\n")?;
+        file.sync_all()?;
+        assert_eq!(
+            get_container_name(root.path(), Some("oci".to_string())),
+            Some("ubuntu".to_string())
+        );
+        root.close()
+    }
+
+    #[test]
+    fn in_podman_container_without_image() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        fs::create_dir(root.path().join("run"))?;
+        File::create(root.path().join("run/.containerenv"))?.sync_all()?;
+        assert_eq!(
+            get_container_name(root.path(), None),
+            Some("podman".to_string())
+        );
+        root.close()
+    }
+
+    #[test]
+    fn in_docker_container() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        File::create(root.path().join(".dockerenv"))?.sync_all()?;
+        assert_eq!(
+            get_container_name(root.path(), None),
+            Some("Docker".to_string())
+        );
+        root.close()
+    }
+
+    #[test]
+    fn container_env_set() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        assert_eq!(
+            get_container_name(root.path(), Some("systemd-nspawn".to_string())),
+            Some("systemd-nspawn".to_string())
+        );
+        root.close()
+    }
+}
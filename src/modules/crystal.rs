@@ -1,8 +1,7 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::crystal::CrystalConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current Crystal version
 ///
@@ -10,20 +9,21 @@ use crate::utils;
 ///     - Current directory contains a `.cr` file
 ///     - Current directory contains a `shard.yml` file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("crystal");
+    let config: CrystalConfig = CrystalConfig::try_load(module.config);
+
     let is_crystal_project = context
-        .try_begin_scan()?
-        .set_files(&["shard.yml"])
-        .set_extensions(&["cr"])
+        .try_begin_module_scan("crystal")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_crystal_project {
         return None;
     }
 
-    let crystal_version = utils::exec_cmd("crystal", &["--version"])?.stdout;
-
-    let mut module = context.new_module("crystal");
-    let config: CrystalConfig = CrystalConfig::try_load(module.config);
+    let crystal_version = context.exec_cmd("crystal", &["--version"])?.stdout;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -36,10 +36,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => format_crystal_version(&crystal_version).map(Ok),
+                "version" => format_crystal_version(&crystal_version).map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    ))
+                }),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -60,10 +66,7 @@ fn format_crystal_version(crystal_version: &str) -> Option<String> {
         // return "0.32.1"
         .nth(1)?;
 
-    let mut formatted_version = String::with_capacity(version.len() + 1);
-    formatted_version.push('v');
-    formatted_version.push_str(version);
-    Some(formatted_version)
+    Some(version.to_string())
 }
 
 #[cfg(test)]
@@ -1,15 +1,50 @@
-use std::io::Write;
-use std::process::{Command, Output, Stdio};
+use std::borrow::Cow;
+use std::io::{Read, Write};
+#[cfg(not(windows))]
+use std::path::PathBuf;
+use std::process::{Child, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::OnceCell;
+
+use super::{Context, Module, RootModuleConfig, Shell};
+
+use crate::configs::custom::{CustomConfig, Multiline};
+use crate::formatter::StringFormatter;
+use crate::segment::Segment;
+use crate::utils;
+use ansi_term::Style;
 
-use super::{Context, Module, RootModuleConfig};
+/// Environment variable passing the output of `when` to `command`
+const WHEN_OUTPUT_VAR: &str = "STARSHIP_WHEN_OUTPUT";
+
+/// Environment variables passed to the commands spawned by a custom module
+type CommandEnv = Vec<(&'static str, String)>;
+
+/// The environment variables kept for the commands of a sandboxed module
+const SANDBOX_ENV_VARS: &[&str] = &["PATH", "HOME", "LANG", "SYSTEMROOT"];
+
+/// Restrictions on the commands of a custom module with `sandbox = true`, for
+/// configurations shared by a team: a stripped environment, no network access
+/// where `unshare` can provide it, and a hard timeout
+#[derive(Clone, Copy)]
+struct Sandbox {
+    timeout: Duration,
+}
 
-use crate::{configs::custom::CustomConfig, formatter::StringFormatter};
+/// The result of a `when` command which exited with one of the accepted codes
+struct WhenOutput {
+    status: i64,
+    stdout: String,
+}
 
 /// Creates a custom module with some configuration
 ///
 /// The relevant TOML config will set the files, extensions, and directories needed
 /// for the module to be displayed. If none of them match, and optional "when"
-/// command can be run -- if its result is 0, the module will be shown.
+/// command can be run -- if its result is in `when_exit_codes` (0 by default),
+/// the module will be shown.
 ///
 /// Finally, the content of the module itself is also set by a command.
 pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
@@ -30,21 +65,53 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
         scan_dir = scan_dir.set_folders(&config.directories.0);
     }
 
-    let mut is_match = scan_dir.is_match();
+    let is_match = scan_dir.is_match();
+    let env = command_env(context);
+    let sandbox = if config.sandbox {
+        Some(Sandbox {
+            timeout: Duration::from_millis(config.sandbox_timeout),
+        })
+    } else {
+        None
+    };
 
-    if !is_match {
-        if let Some(when) = config.when {
-            is_match = exec_when(when, &config.shell.0);
-        }
+    // `when` is run at most once, whether it decides if the module is shown, or its
+    // output or status are used after a file, extension or directory already matched
+    let when_result: OnceCell<Option<WhenOutput>> = OnceCell::new();
+    let run_when = || {
+        when_result
+            .get_or_init(|| {
+                config.when.and_then(|when| {
+                    exec_when(
+                        when,
+                        &config.shell.0,
+                        &env,
+                        &config.when_exit_codes,
+                        sandbox,
+                    )
+                })
+            })
+            .as_ref()
+    };
 
-        if !is_match {
-            return None;
-        }
+    if !is_match && run_when().is_none() {
+        return None;
     }
 
     let mut module = Module::new(name, config.description, Some(toml_config));
 
-    let output = exec_command(config.command, &config.shell.0)?;
+    let output = if config.use_when_output {
+        run_when()?.stdout.clone()
+    } else if config.command.contains(WHEN_OUTPUT_VAR) {
+        // The output is passed through the environment, not pasted into the command,
+        // so that whatever `when` prints is never run as shell code
+        let when_output = run_when().map(|when| when.stdout.trim_end().to_string());
+        let mut env = env.clone();
+        env.push((WHEN_OUTPUT_VAR, when_output.unwrap_or_default()));
+        exec_command(config.command, &config.shell.0, &env, sandbox)?
+    } else {
+        exec_command(config.command, &config.shell.0, &env, sandbox)?
+    };
 
     let trimmed = output.trim();
     if trimmed.is_empty() {
@@ -62,12 +129,18 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                // This may result in multiple calls to `get_module_version` when a user have
-                // multiple `$version` variables defined in `format`.
-                "output" => Some(Ok(trimmed)),
+                "when_status" => run_when().map(|when| Ok(when.status.to_string())),
                 _ => None,
             })
-            .parse(None)
+            .map_variables_to_segments(|variable| match variable {
+                "output" => Some(Ok(output_segments(
+                    trimmed,
+                    config.multiline,
+                    config.multiline_separator,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -81,6 +154,80 @@ pub fn module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Split the output of a command into segments according to the `multiline` option
+///
+/// When line breaks are kept, each line becomes its own segment and the breaks are
+/// emitted as unstyled segments, so that no style escape sequence spans a line break.
+fn output_segments(output: &str, multiline: Multiline, separator: &str) -> Vec<Segment<'static>> {
+    let lines = output.lines().map(str::trim_end);
+
+    let output_segment = |value: String| Segment {
+        _name: Cow::Borrowed("output"),
+        style: None,
+        value: Cow::Owned(value),
+        image: None,
+    };
+
+    match multiline {
+        Multiline::Join => vec![output_segment(
+            lines
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .collect::<Vec<_>>()
+                .join(separator),
+        )],
+        Multiline::FirstLine => vec![output_segment(lines.take(1).collect::<String>())],
+        Multiline::Keep => lines
+            .enumerate()
+            .flat_map(|(i, line)| {
+                let line_break = Segment {
+                    _name: Cow::Borrowed("line_break"),
+                    style: Some(Style::new()),
+                    value: Cow::Borrowed("\n"),
+                    image: None,
+                };
+                let line = output_segment(line.to_string());
+
+                if i == 0 {
+                    vec![line]
+                } else {
+                    vec![line_break, line]
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Collect the prompt context exposed to custom commands as `STARSHIP_*` environment variables
+fn command_env(context: &Context) -> CommandEnv {
+    let mut env = CommandEnv::new();
+
+    if let Some((width, _)) = term_size::dimensions() {
+        env.push(("STARSHIP_COLUMNS", width.to_string()));
+    }
+
+    let shell = match context.shell {
+        Shell::Bash => Some("bash"),
+        Shell::Fish => Some("fish"),
+        Shell::Ion => Some("ion"),
+        Shell::PowerShell => Some("powershell"),
+        Shell::Zsh => Some("zsh"),
+        Shell::Unknown => None,
+    };
+    if let Some(shell) = shell {
+        env.push(("STARSHIP_SHELL", shell.to_string()));
+    }
+
+    if let Some(status_code) = context.properties.get("status_code") {
+        env.push(("STARSHIP_STATUS", status_code.clone()));
+    }
+    if let Some(cmd_duration) = context.properties.get("cmd_duration") {
+        env.push(("STARSHIP_DURATION", cmd_duration.clone()));
+    }
+
+    env
+}
+
 /// Return the invoking shell, using `shell` and fallbacking in order to STARSHIP_SHELL and "sh"
 #[cfg(not(windows))]
 fn get_shell<'a, 'b>(shell_args: &'b [&'a str]) -> (std::borrow::Cow<'a, str>, &'b [&'a str]) {
@@ -95,15 +242,51 @@ fn get_shell<'a, 'b>(shell_args: &'b [&'a str]) -> (std::borrow::Cow<'a, str>, &
 
 /// Attempt to run the given command in a shell by passing it as `stdin` to `get_shell()`
 #[cfg(not(windows))]
-fn shell_command(cmd: &str, shell_args: &[&str]) -> Option<Output> {
+fn shell_command(
+    cmd: &str,
+    shell_args: &[&str],
+    env: &[(&str, String)],
+    sandbox: Option<Sandbox>,
+) -> Option<Output> {
+    let isolate_network =
+        sandbox.is_some() && cfg!(target_os = "linux") && utils::is_program_available("unshare");
+    let output = run_shell(cmd, shell_args, env, sandbox, isolate_network)?;
+
+    // `unshare` fails when unprivileged user namespaces are disabled
+    if isolate_network && !output.status.success() && output.stderr.starts_with(b"unshare:") {
+        log::debug!("Unable to run the command without network access, running it anyway");
+        return run_shell(cmd, shell_args, env, sandbox, false);
+    }
+    Some(output)
+}
+
+#[cfg(not(windows))]
+fn run_shell(
+    cmd: &str,
+    shell_args: &[&str],
+    env: &[(&str, String)],
+    sandbox: Option<Sandbox>,
+    isolate_network: bool,
+) -> Option<Output> {
     let (shell, shell_args) = get_shell(shell_args);
-    let mut command = Command::new(shell.as_ref());
+    let mut command = if isolate_network {
+        let mut command = Command::new("unshare");
+        command
+            .arg("--net")
+            .arg("--map-root-user")
+            .arg("--")
+            .arg(shell.as_ref());
+        command
+    } else {
+        Command::new(shell.as_ref())
+    };
 
     command
         .args(shell_args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
+    set_env(&mut command, env, sandbox);
 
     handle_powershell(&mut command, &shell, shell_args);
 
@@ -112,27 +295,52 @@ fn shell_command(cmd: &str, shell_args: &[&str]) -> Option<Output> {
         Err(err) => {
             log::trace!("Error executing command: {:?}", err);
             log::debug!(
-                "Could not launch command with given shell or STARSHIP_SHELL env variable, retrying with /usr/bin/env sh"
+                "Could not launch command with given shell or STARSHIP_SHELL env variable, retrying with a fallback sh"
             );
 
-            Command::new("/usr/bin/env")
-                .arg("sh")
-                .stdin(Stdio::piped())
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .ok()?
+            spawn_fallback_shell(env, sandbox)?
         }
     };
 
     child.stdin.as_mut()?.write_all(cmd.as_bytes()).ok()?;
-    child.wait_with_output().ok()
+    wait_for_output(child, sandbox)
+}
+
+/// Spawn `sh` reading from `stdin`, trying `/usr/bin/env sh` first.
+///
+/// Termux on Android has no `/usr/bin/env`, so `sh` is then looked up under `$PREFIX/bin`.
+#[cfg(not(windows))]
+fn spawn_fallback_shell(env: &[(&str, String)], sandbox: Option<Sandbox>) -> Option<Child> {
+    let mut candidates = vec![(PathBuf::from("/usr/bin/env"), vec!["sh"])];
+    if let Ok(prefix) = std::env::var("PREFIX") {
+        candidates.push((PathBuf::from(prefix).join("bin/sh"), vec![]));
+    }
+
+    candidates.into_iter().find_map(|(shell, args)| {
+        let mut command = Command::new(&shell);
+        command
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        set_env(&mut command, env, sandbox);
+
+        command
+            .spawn()
+            .map_err(|err| log::trace!("Error launching {:?}: {:?}", shell, err))
+            .ok()
+    })
 }
 
 /// Attempt to run the given command in a shell by passing it as `stdin` to `get_shell()`,
 /// or by invoking cmd.exe /C.
 #[cfg(windows)]
-fn shell_command(cmd: &str, shell_args: &[&str]) -> Option<Output> {
+fn shell_command(
+    cmd: &str,
+    shell_args: &[&str],
+    env: &[(&str, String)],
+    sandbox: Option<Sandbox>,
+) -> Option<Output> {
     let (shell, shell_args) = if !shell_args.is_empty() {
         (
             Some(std::borrow::Cow::Borrowed(shell_args[0])),
@@ -152,13 +360,14 @@ fn shell_command(cmd: &str, shell_args: &[&str]) -> Option<Output> {
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
+        set_env(&mut command, env, sandbox);
 
         handle_powershell(&mut command, &forced_shell, shell_args);
 
         if let Ok(mut child) = command.spawn() {
             child.stdin.as_mut()?.write_all(cmd.as_bytes()).ok()?;
 
-            return child.wait_with_output().ok();
+            return wait_for_output(child, sandbox);
         }
 
         log::debug!(
@@ -166,47 +375,130 @@ fn shell_command(cmd: &str, shell_args: &[&str]) -> Option<Output> {
         );
     }
 
-    let command = Command::new("cmd.exe")
+    let mut command = Command::new("cmd.exe");
+    command
         .arg("/C")
         .arg(cmd)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn();
+        .stderr(Stdio::piped());
+    set_env(&mut command, env, sandbox);
 
-    command.ok()?.wait_with_output().ok()
+    wait_for_output(command.spawn().ok()?, sandbox)
 }
 
-/// Execute the given command capturing all output, and return whether it return 0
-fn exec_when(cmd: &str, shell_args: &[&str]) -> bool {
-    log::trace!("Running '{}'", cmd);
+/// Pass `env` to the command, on top of the environment of starship, or only on top
+/// of `SANDBOX_ENV_VARS` when sandboxed
+fn set_env(command: &mut Command, env: &[(&str, String)], sandbox: Option<Sandbox>) {
+    if sandbox.is_some() {
+        command.env_clear().envs(
+            SANDBOX_ENV_VARS
+                .iter()
+                .filter_map(|key| Some((*key, std::env::var_os(key)?))),
+        );
+    }
+    command.envs(env.iter().cloned());
+}
 
-    if let Some(output) = shell_command(cmd, shell_args) {
-        if !output.status.success() {
-            log::trace!("non-zero exit code '{:?}'", output.status.code());
-            log::trace!(
-                "stdout: {}",
-                std::str::from_utf8(&output.stdout).unwrap_or("<invalid utf8>")
-            );
-            log::trace!(
-                "stderr: {}",
-                std::str::from_utf8(&output.stderr).unwrap_or("<invalid utf8>")
-            );
+/// Wait for the command to exit and collect its output. A sandboxed command is
+/// killed when it runs for longer than the timeout of the sandbox.
+fn wait_for_output(mut child: Child, sandbox: Option<Sandbox>) -> Option<Output> {
+    let timeout = match sandbox {
+        Some(sandbox) => sandbox.timeout,
+        None => return child.wait_with_output().ok(),
+    };
+
+    // Read the output while waiting, so that a command filling the pipes isn't blocked
+    fn read_all<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                pipe.read_to_end(&mut buf).ok();
+            }
+            buf
+        })
+    }
+    drop(child.stdin.take());
+    let stdout = read_all(child.stdout.take());
+    let stderr = read_all(child.stderr.take());
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if start.elapsed() < timeout => thread::sleep(Duration::from_millis(5)),
+            Ok(None) => {
+                log::warn!(
+                    "Killing a sandboxed command which ran for longer than {}ms",
+                    timeout.as_millis()
+                );
+                child.kill().ok();
+                child.wait().ok();
+                return None;
+            }
+            Err(error) => {
+                log::trace!("Error waiting for command: {:?}", error);
+                return None;
+            }
         }
+    };
+
+    Some(Output {
+        status,
+        stdout: stdout.join().ok()?,
+        stderr: stderr.join().ok()?,
+    })
+}
+
+/// Execute the given command capturing all output, and return its status and output
+/// if it exits with one of `exit_codes`
+fn exec_when(
+    cmd: &str,
+    shell_args: &[&str],
+    env: &[(&str, String)],
+    exit_codes: &[i64],
+    sandbox: Option<Sandbox>,
+) -> Option<WhenOutput> {
+    log::trace!("Running '{}'", cmd);
 
-        output.status.success()
+    if let Some(output) = shell_command(cmd, shell_args, env, sandbox) {
+        let status = match output.status.code().map(i64::from) {
+            Some(status) if exit_codes.contains(&status) => status,
+            _ => {
+                log::trace!("unexpected exit code '{:?}'", output.status.code());
+                log::trace!(
+                    "stdout: {}",
+                    std::str::from_utf8(&output.stdout).unwrap_or("<invalid utf8>")
+                );
+                log::trace!(
+                    "stderr: {}",
+                    std::str::from_utf8(&output.stderr).unwrap_or("<invalid utf8>")
+                );
+                return None;
+            }
+        };
+
+        Some(WhenOutput {
+            status,
+            stdout: String::from_utf8_lossy(&output.stdout).into(),
+        })
     } else {
         log::debug!("Cannot start command");
 
-        false
+        None
     }
 }
 
 /// Execute the given command, returning its output on success
-fn exec_command(cmd: &str, shell_args: &[&str]) -> Option<String> {
+fn exec_command(
+    cmd: &str,
+    shell_args: &[&str],
+    env: &[(&str, String)],
+    sandbox: Option<Sandbox>,
+) -> Option<String> {
     log::trace!("Running '{}'", cmd);
 
-    if let Some(output) = shell_command(cmd, shell_args) {
+    if let Some(output) = shell_command(cmd, shell_args, env, sandbox) {
         if !output.status.success() {
             log::trace!("Non-zero exit code '{:?}'", output.status.code());
             log::trace!(
@@ -257,21 +549,34 @@ mod tests {
 
     #[test]
     fn when_returns_right_value() {
-        assert!(exec_when("echo hello", SHELL));
-        assert!(!exec_when(FAILING_COMMAND, SHELL));
+        assert!(exec_when("echo hello", SHELL, &[], &[0], None).is_some());
+        assert!(exec_when(FAILING_COMMAND, SHELL, &[], &[0], None).is_none());
     }
 
     #[test]
     fn when_returns_false_if_invalid_command() {
-        assert!(!exec_when(UNKNOWN_COMMAND, SHELL));
+        assert!(exec_when(UNKNOWN_COMMAND, SHELL, &[], &[0], None).is_none());
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn when_accepts_several_exit_codes() {
+        let when = exec_when("echo degraded; exit 2", SHELL, &[], &[0, 2], None).unwrap();
+        assert_eq!(when.status, 2);
+        assert_eq!(when.stdout, "degraded\n");
+        assert!(exec_when("exit 0", SHELL, &[], &[2], None).is_none());
+        assert!(exec_when("exit 1", SHELL, &[], &[0, 2], None).is_none());
     }
 
     #[test]
     #[cfg(not(windows))]
     fn command_returns_right_string() {
-        assert_eq!(exec_command("echo hello", SHELL), Some("hello\n".into()));
         assert_eq!(
-            exec_command("echo 강남스타일", SHELL),
+            exec_command("echo hello", SHELL, &[], None),
+            Some("hello\n".into())
+        );
+        assert_eq!(
+            exec_command("echo 강남스타일", SHELL, &[], None),
             Some("강남스타일\n".into())
         );
     }
@@ -279,9 +584,12 @@ mod tests {
     #[test]
     #[cfg(windows)]
     fn command_returns_right_string() {
-        assert_eq!(exec_command("echo hello", SHELL), Some("hello\r\n".into()));
         assert_eq!(
-            exec_command("echo 강남스타일", SHELL),
+            exec_command("echo hello", SHELL, &[], None),
+            Some("hello\r\n".into())
+        );
+        assert_eq!(
+            exec_command("echo 강남스타일", SHELL, &[], None),
             Some("강남스타일\r\n".into())
         );
     }
@@ -290,11 +598,11 @@ mod tests {
     #[cfg(not(windows))]
     fn command_ignores_stderr() {
         assert_eq!(
-            exec_command("echo foo 1>&2; echo bar", SHELL),
+            exec_command("echo foo 1>&2; echo bar", SHELL, &[], None),
             Some("bar\n".into())
         );
         assert_eq!(
-            exec_command("echo foo; echo bar 1>&2", SHELL),
+            exec_command("echo foo; echo bar 1>&2", SHELL, &[], None),
             Some("foo\n".into())
         );
     }
@@ -303,18 +611,121 @@ mod tests {
     #[cfg(windows)]
     fn command_ignores_stderr() {
         assert_eq!(
-            exec_command("echo foo 1>&2 & echo bar", SHELL),
+            exec_command("echo foo 1>&2 & echo bar", SHELL, &[], None),
             Some("bar\r\n".into())
         );
         assert_eq!(
-            exec_command("echo foo& echo bar 1>&2", SHELL),
+            exec_command("echo foo& echo bar 1>&2", SHELL, &[], None),
             Some("foo\r\n".into())
         );
     }
 
     #[test]
     fn command_can_fail() {
-        assert_eq!(exec_command(FAILING_COMMAND, SHELL), None);
-        assert_eq!(exec_command(UNKNOWN_COMMAND, SHELL), None);
+        assert_eq!(exec_command(FAILING_COMMAND, SHELL, &[], None), None);
+        assert_eq!(exec_command(UNKNOWN_COMMAND, SHELL, &[], None), None);
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn command_receives_prompt_context() {
+        let mut context = Context::new_with_dir(clap::ArgMatches::default(), ".");
+        context.shell = Shell::Zsh;
+        context.properties.insert("status_code", "1".to_string());
+        context
+            .properties
+            .insert("cmd_duration", "2500".to_string());
+        let env = command_env(&context);
+
+        assert_eq!(
+            exec_command(
+                "echo $STARSHIP_SHELL $STARSHIP_STATUS $STARSHIP_DURATION",
+                SHELL,
+                &env,
+                None
+            ),
+            Some("zsh 1 2500\n".into())
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn sandboxed_command_gets_stripped_environment() {
+        std::env::set_var("STARSHIP_SANDBOX_TEST_SECRET", "hunter2");
+        let sandbox = Some(Sandbox {
+            timeout: Duration::from_secs(5),
+        });
+        let env = vec![("STARSHIP_SHELL", "zsh".to_string())];
+
+        assert_eq!(
+            exec_command(
+                "echo [$STARSHIP_SANDBOX_TEST_SECRET] $STARSHIP_SHELL",
+                SHELL,
+                &env,
+                sandbox
+            ),
+            Some("[] zsh\n".into())
+        );
+        assert_eq!(
+            exec_command("echo $STARSHIP_SANDBOX_TEST_SECRET", SHELL, &[], None),
+            Some("hunter2\n".into())
+        );
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn sandboxed_command_times_out() {
+        let sandbox = Some(Sandbox {
+            timeout: Duration::from_millis(50),
+        });
+        let start = Instant::now();
+
+        assert_eq!(exec_command("sleep 5", SHELL, &[], sandbox), None);
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            exec_command("echo fast", SHELL, &[], sandbox),
+            Some("fast\n".into())
+        );
+    }
+
+    #[test]
+    fn command_env_skips_unknown_values() {
+        let mut context = Context::new_with_dir(clap::ArgMatches::default(), ".");
+        context.shell = Shell::Unknown;
+        let env = command_env(&context);
+
+        assert!(env.iter().all(|(key, _)| ![
+            "STARSHIP_SHELL",
+            "STARSHIP_STATUS",
+            "STARSHIP_DURATION"
+        ]
+        .contains(key)));
+    }
+
+    fn render(segments: Vec<Segment>) -> String {
+        segments.iter().map(Segment::get_value).collect()
+    }
+
+    #[test]
+    fn multiline_output_is_joined() {
+        let segments = output_segments("foo\n  bar\r\n\nbaz", Multiline::Join, " | ");
+        assert_eq!(segments.len(), 1);
+        assert_eq!(render(segments), "foo | bar | baz");
+    }
+
+    #[test]
+    fn multiline_output_keeps_first_line() {
+        let segments = output_segments("foo  \r\nbar", Multiline::FirstLine, " ");
+        assert_eq!(render(segments), "foo");
+    }
+
+    #[test]
+    fn multiline_output_is_kept() {
+        let segments = output_segments("foo\r\nbar", Multiline::Keep, " ");
+        assert_eq!(render(segments.clone()), "foo\nbar");
+        assert!(segments
+            .iter()
+            .filter(|segment| segment.value == "\n")
+            .all(Segment::has_style));
     }
 }
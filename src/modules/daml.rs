@@ -0,0 +1,124 @@
+use yaml_rust::YamlLoader;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::daml::DamlConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+const DAML_YAML: &str = "daml.yaml";
+
+/// Creates a module with the Daml SDK version of the current project
+///
+/// Will display the Daml SDK version if the current directory contains a `daml.yaml`
+/// file. The version is read from its `sdk-version` field, without running `daml`.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("daml");
+    let config: DamlConfig = DamlConfig::try_load(module.config);
+
+    let is_daml_project = context
+        .try_begin_module_scan("daml")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_daml_project {
+        return None;
+    }
+
+    let daml_yaml = utils::read_file(context.current_dir.join(DAML_YAML)).ok()?;
+    let sdk_version = parse_sdk_version(&daml_yaml)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &sdk_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `daml`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_sdk_version(daml_yaml: &str) -> Option<String> {
+    let yaml_docs = YamlLoader::load_from_str(daml_yaml).ok()?;
+    let sdk_version = yaml_docs.first()?["sdk-version"].as_str()?.trim();
+
+    if sdk_version.is_empty() {
+        return None;
+    }
+
+    Some(sdk_version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_sdk_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_parse_sdk_version() {
+        let daml_yaml = "sdk-version: 1.6.0\nname: quickstart\nversion: 0.0.1\n";
+        assert_eq!(parse_sdk_version(daml_yaml), Some("1.6.0".to_string()));
+        assert_eq!(parse_sdk_version("name: quickstart\n"), None);
+        assert_eq!(parse_sdk_version("sdk-version: \"\"\n"), None);
+    }
+
+    #[test]
+    fn folder_without_daml_yaml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("daml", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_daml_yaml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("daml.yaml"))?;
+        file.write_all(b"sdk-version: 1.6.0\nname: quickstart\n")?;
+        file.sync_all()?;
+        let actual = render_module("daml", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Cyan.bold().paint("Λ v1.6.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_daml_yaml_without_sdk_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("daml.yaml"))?;
+        file.write_all(b"name: quickstart\n")?;
+        file.sync_all()?;
+        let actual = render_module("daml", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
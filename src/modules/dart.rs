@@ -0,0 +1,149 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::dart::DartConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Dart version
+///
+/// Will display the Dart version if any of the following criteria are met:
+///     - Current directory contains a file with `.dart` extension
+///     - Current directory contains a `.dart_tool` directory
+///     - Current directory contains a `pubspec.yaml`, `pubspec.yml` or `pubspec.lock` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("dart");
+    let config: DartConfig = DartConfig::try_load(module.config);
+
+    let is_dart_project = context
+        .try_begin_module_scan("dart")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_dart_project {
+        return None;
+    }
+
+    let dart_version = context.exec_cmd("dart", &["--version"])?;
+    // Older Dart SDKs print the version to stderr instead of stdout
+    let module_version = parse_dart_version(&dart_version.stdout)
+        .or_else(|| parse_dart_version(&dart_version.stderr))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `dart`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_dart_version(dart_version: &str) -> Option<String> {
+    let version = dart_version
+        // split into ["Dart", "SDK", "version:", "2.8.4", "(stable)", ...]
+        .split_whitespace()
+        // return "2.8.4"
+        .skip_while(|word| *word != "version:")
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dart_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
+
+    #[test]
+    fn test_parse_dart_version() {
+        let input =
+            "Dart SDK version: 2.12.0 (stable) (Thu Feb 25 19:50:53 2021 +0100) on \"linux_x64\"";
+        assert_eq!(parse_dart_version(input), Some("2.12.0".to_string()));
+        assert_eq!(parse_dart_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_dart_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_dart_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("any.dart"))?.sync_all()?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🎯 v2.8.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_dart_tool_directory() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir_all(dir.path().join(".dart_tool"))?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🎯 v2.8.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_pubspec_yaml_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("pubspec.yaml"))?.sync_all()?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🎯 v2.8.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_pubspec_yml_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("pubspec.yml"))?.sync_all()?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🎯 v2.8.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_pubspec_lock_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("pubspec.lock"))?.sync_all()?;
+        let actual = render_module("dart", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("🎯 v2.8.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
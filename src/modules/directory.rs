@@ -58,10 +58,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let home_dir = dirs_next::home_dir().unwrap();
     log::debug!("Current directory: {:?}", current_dir);
 
-    let repo = &context.get_repo().ok()?;
+    // The repository is only looked up when the path is contracted to its root
+    let repo_root = if config.truncate_to_repo {
+        context.get_repo().ok().and_then(|repo| repo.root.as_ref())
+    } else {
+        None
+    };
 
-    let dir_string = match &repo.root {
-        Some(repo_root) if config.truncate_to_repo && (repo_root != &home_dir) => {
+    let dir_string = match repo_root {
+        Some(repo_root) if repo_root != &home_dir => {
             log::debug!("Repo root: {:?}", repo_root);
             // Contract the path to the git repo root
             contract_repo_path(current_dir, repo_root)
@@ -99,10 +104,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "path" => Some(Ok(&final_dir_string)),
+                "path" => Some(Ok(final_dir_string.clone())),
+                "repo_name" => context.get_repo().ok()?.name().map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -0,0 +1,156 @@
+use serde_json as json;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::direnv::DirenvConfig;
+use crate::formatter::StringFormatter;
+
+/// Whether direnv may load an `.envrc`, as reported by `direnv status --json`
+#[derive(Debug, PartialEq)]
+enum AllowStatus {
+    Allowed,
+    NotAllowed,
+    Denied,
+}
+
+#[derive(Debug, PartialEq)]
+struct DirenvState {
+    rc_path: String,
+    allowed: AllowStatus,
+    loaded: bool,
+}
+
+/// Creates a module with the state of the `.envrc` of the current directory tree
+///
+/// Will display the state of the `.envrc` found by direnv in the current directory,
+/// or one of its parents: whether it is allowed, denied or not allowed yet, and
+/// whether it is loaded.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("direnv");
+    let config: DirenvConfig = DirenvConfig::try_load(module.config);
+
+    if config.disabled {
+        return None;
+    }
+
+    let direnv_status = context.exec_cmd("direnv", &["status", "--json"])?;
+    let state = parse_direnv_status(&direnv_status.stdout)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                "allowed" => Some(match state.allowed {
+                    AllowStatus::Allowed => config.allowed_symbol,
+                    AllowStatus::NotAllowed => config.not_allowed_symbol,
+                    AllowStatus::Denied => config.denied_symbol,
+                }),
+                "loaded" if state.loaded => Some(config.loaded_symbol),
+                "loaded" => Some(config.unloaded_symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "rc_path" => Some(Ok(&state.rc_path)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `direnv`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_direnv_status(direnv_status: &str) -> Option<DirenvState> {
+    let status: json::Value = json::from_str(direnv_status).ok()?;
+    let state = status.get("state")?;
+
+    // `foundRC` is null when there is no `.envrc` in the current directory tree
+    let found_rc = state.get("foundRC")?;
+    let rc_path = found_rc.get("path")?.as_str()?;
+    let allowed = match found_rc.get("allowed")?.as_u64()? {
+        0 => AllowStatus::Allowed,
+        1 => AllowStatus::NotAllowed,
+        2 => AllowStatus::Denied,
+        _ => return None,
+    };
+    let loaded = state
+        .get("loadedRC")
+        .and_then(|loaded_rc| loaded_rc.get("path"))
+        .and_then(json::Value::as_str)
+        == Some(rc_path);
+
+    Some(DirenvState {
+        rc_path: rc_path.to_string(),
+        allowed,
+        loaded,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_direnv_status, AllowStatus, DirenvState};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::io;
+
+    #[test]
+    fn test_parse_direnv_status() {
+        let status = r#"{
+            "config": {"ConfigDir": "/home/user/.config/direnv"},
+            "state": {
+                "foundRC": {"allowed": 2, "path": "/home/user/project/.envrc"},
+                "loadedRC": null
+            }
+        }"#;
+        assert_eq!(
+            parse_direnv_status(status),
+            Some(DirenvState {
+                rc_path: "/home/user/project/.envrc".to_string(),
+                allowed: AllowStatus::Denied,
+                loaded: false,
+            })
+        );
+
+        let status = r#"{"state": {"foundRC": null, "loadedRC": null}}"#;
+        assert_eq!(parse_direnv_status(status), None);
+    }
+
+    #[test]
+    fn module_disabled_by_default() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("direnv", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn loaded_and_allowed() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module(
+            "direnv",
+            dir.path(),
+            Some(toml::toml! {
+                [direnv]
+                disabled = false
+            }),
+        );
+        let expected = Some(format!(
+            "{} ",
+            Color::Fixed(11).bold().paint("direnv loaded/allowed")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
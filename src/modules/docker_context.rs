@@ -14,6 +14,8 @@ use crate::utils;
 ///     - Or a file named `$DOCKER_CONFIG/config.json`
 ///     - The file is JSON and contains a field named `currentContext`
 ///     - The value of `currentContext` is not `default`
+///     - If `only_with_files` is enabled, the current directory contains a `Dockerfile`
+///       or a Compose file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("docker_context");
     let config: DockerContextConfig = DockerContextConfig::try_load(module.config);
@@ -21,7 +23,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     if config.only_with_files
         && !context
             .try_begin_scan()?
-            .set_files(&["docker-compose.yml", "Dockerfile"])
+            .set_files(&[
+                "Dockerfile",
+                "docker-compose.yml",
+                "docker-compose.yaml",
+                "compose.yml",
+                "compose.yaml",
+            ])
             .is_match()
     {
         return None;
@@ -43,7 +51,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         serde_json::Value::Object(root) => {
             let current_context = root.get("currentContext")?;
             match current_context {
-                serde_json::Value::String(ctx) => {
+                serde_json::Value::String(ctx) if ctx != "default" => {
                     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
                         formatter
                             .map_meta(|variable, _| match variable {
@@ -58,7 +66,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                                 "context" => Some(Ok(ctx)),
                                 _ => None,
                             })
-                            .parse(None)
+                            .parse(None, Some(context))
                     });
 
                     module.set_segments(match parsed {
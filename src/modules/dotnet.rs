@@ -1,23 +1,22 @@
+use once_cell::sync::OnceCell;
 use quick_xml::events::Event;
 use quick_xml::Reader;
+use std::cmp::Ordering;
 use std::ffi::OsStr;
 use std::iter::Iterator;
 use std::ops::Deref;
-use std::path::Path;
-use std::str;
+use std::path::{Path, PathBuf};
+use std::{env, fs, str};
 
 use super::{Context, Module, RootModuleConfig};
 use crate::configs::dotnet::DotnetConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils;
 
 type JValue = serde_json::Value;
 
 const GLOBAL_JSON_FILE: &str = "global.json";
 const PROJECT_JSON_FILE: &str = "project.json";
-const DIRECTORY_BUILD_PROPS_FILE: &str = "Directory.Build.props";
-const DIRECTORY_BUILD_TARGETS_FILE: &str = "Directory.Build.targets";
-const PACKAGES_PROPS_FILE: &str = "Packages.props";
 
 /// A module which shows the latest (or pinned) version of the dotnet SDK
 ///
@@ -25,18 +24,16 @@ const PACKAGES_PROPS_FILE: &str = "Packages.props";
 /// the current directory:
 /// global.json, project.json, *.sln, *.csproj, *.fsproj, *.xproj
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("dotnet");
+    let config = DotnetConfig::try_load(module.config);
+
     // First check if this is a DotNet Project before doing the O(n)
     // check for the version using the JSON files
     let is_dotnet_project = context
-        .try_begin_scan()?
-        .set_files(&[
-            GLOBAL_JSON_FILE,
-            PROJECT_JSON_FILE,
-            DIRECTORY_BUILD_PROPS_FILE,
-            DIRECTORY_BUILD_TARGETS_FILE,
-            PACKAGES_PROPS_FILE,
-        ])
-        .set_extensions(&["sln", "csproj", "fsproj", "xproj"])
+        .try_begin_module_scan("dotnet")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_dotnet_project {
@@ -45,13 +42,29 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let dotnet_files = get_local_dotnet_files(context).ok()?;
 
-    let mut module = context.new_module("dotnet");
-    let config = DotnetConfig::try_load(module.config);
-
     // Internally, this module uses its own mechanism for version detection.
     // Typically it is twice as fast as running `dotnet --version`.
     let enable_heuristic = config.heuristic;
 
+    // The version is only looked up if it is needed, by `$version` or `$pinned`
+    let sdk_version = OnceCell::new();
+    let get_sdk_version = || {
+        sdk_version
+            .get_or_init(|| {
+                let repo_root = context.get_repo().ok().and_then(|r| r.root.as_deref());
+                if enable_heuristic {
+                    estimate_dotnet_version(context, &dotnet_files, repo_root)
+                } else {
+                    let pinned = find_pinned_sdk_version(context, &dotnet_files, repo_root);
+                    get_version_from_cli(context).map(|version| SdkVersion {
+                        version,
+                        pinned: pinned.is_some(),
+                    })
+                }
+            })
+            .as_ref()
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_style(|variable| match variable {
@@ -60,22 +73,23 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "symbol" => Some(Ok(config.symbol)),
+                "pinned" => get_sdk_version()
+                    .filter(|sdk| sdk.pinned)
+                    .map(|_| Ok(config.pinned_symbol)),
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    let version = if enable_heuristic {
-                        let repo_root = context.get_repo().ok().and_then(|r| r.root.as_deref());
-                        estimate_dotnet_version(&dotnet_files, &context.current_dir, repo_root)
-                    } else {
-                        get_version_from_cli()
-                    };
-                    version.map(|v| Ok(v.0))
-                }
+                "version" => get_sdk_version().map(|sdk| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &sdk.version,
+                        config.version_format,
+                    ))
+                }),
                 "tfm" => find_current_tfm(&dotnet_files).map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -136,10 +150,10 @@ fn get_tfm_from_project_file(path: &Path) -> Option<String> {
 }
 
 fn estimate_dotnet_version<'a>(
+    context: &Context,
     files: &[DotNetFile<'a>],
-    current_dir: &Path,
     repo_root: Option<&Path>,
-) -> Option<Version> {
+) -> Option<SdkVersion> {
     let get_file_of_type = |t: FileType| files.iter().find(|f| f.file_type == t);
 
     // It's important to check for a global.json or a solution file first,
@@ -148,24 +162,43 @@ fn estimate_dotnet_version<'a>(
         .or_else(|| get_file_of_type(FileType::SolutionFile))
         .or_else(|| files.iter().next())?;
 
+    let pinned = |version| SdkVersion {
+        version,
+        pinned: true,
+    };
     match relevant_file.file_type {
-        FileType::GlobalJson => {
-            get_pinned_sdk_version_from_file(relevant_file.path).or_else(get_latest_sdk_from_cli)
-        }
+        FileType::GlobalJson => get_pinned_sdk_version_from_file(relevant_file.path)
+            .map(pinned)
+            .or_else(|| get_latest_sdk(context)),
         FileType::SolutionFile => {
             // With this heuristic, we'll assume that a "global.json" won't
             // be found in any directory above the solution file.
-            get_latest_sdk_from_cli()
+            get_latest_sdk(context)
         }
         _ => {
             // If we see a dotnet project, we'll check a small number of neighboring
             // directories to see if we can find a global.json. Otherwise, assume the
             // latest SDK is in use.
-            try_find_nearby_global_json(current_dir, repo_root).or_else(get_latest_sdk_from_cli)
+            try_find_nearby_global_json(&context.current_dir, repo_root)
+                .map(pinned)
+                .or_else(|| get_latest_sdk(context))
         }
     }
 }
 
+/// Finds the SDK version pinned by a `global.json` in the current directory or nearby
+fn find_pinned_sdk_version(
+    context: &Context,
+    files: &[DotNetFile],
+    repo_root: Option<&Path>,
+) -> Option<Version> {
+    files
+        .iter()
+        .find(|f| f.file_type == FileType::GlobalJson)
+        .and_then(|global_json| get_pinned_sdk_version_from_file(global_json.path))
+        .or_else(|| try_find_nearby_global_json(&context.current_dir, repo_root))
+}
+
 /// Looks for a `global.json` which may exist in one of the parent directories of the current path.
 /// If there is one present, and it contains valid version pinning information, then return that version.
 ///
@@ -235,12 +268,7 @@ fn get_pinned_sdk_version(json: &str) -> Option<Version> {
                 JValue::Object(sdk) => {
                     let version = sdk.get("version")?;
                     match version {
-                        JValue::String(version_string) => {
-                            let mut buffer = String::with_capacity(version_string.len() + 1);
-                            buffer.push('v');
-                            buffer.push_str(version_string);
-                            Some(Version(buffer))
-                        }
+                        JValue::String(version_string) => Some(Version(version_string.clone())),
                         _ => None,
                     }
                 }
@@ -289,13 +317,70 @@ fn map_str_to_lower(value: Option<&OsStr>) -> Option<String> {
     Some(value?.to_str()?.to_ascii_lowercase())
 }
 
-fn get_version_from_cli() -> Option<Version> {
-    let version_output = utils::exec_cmd("dotnet", &["--version"])?;
-    Some(Version(format!("v{}", version_output.stdout.trim())))
+/// The latest installed SDK, from the `sdk` directory of the dotnet installation,
+/// falling back to the dotnet CLI
+fn get_latest_sdk(context: &Context) -> Option<SdkVersion> {
+    get_latest_sdk_from_install_dir()
+        .or_else(|| get_latest_sdk_from_cli(context))
+        .map(|version| SdkVersion {
+            version,
+            pinned: false,
+        })
+}
+
+fn get_latest_sdk_from_install_dir() -> Option<Version> {
+    let sdk_dir = find_dotnet_root()?.join("sdk");
+    log::debug!("Listing the .NET SDKs in: {}", sdk_dir.display());
+    fs::read_dir(sdk_dir)
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(|c: char| c.is_ascii_digit()))
+        .max_by(|a, b| compare_sdk_versions(a, b))
+        .map(Version)
+}
+
+/// The directory of the dotnet installation, `$DOTNET_ROOT` or the directory of the
+/// `dotnet` executable in `$PATH`
+fn find_dotnet_root() -> Option<PathBuf> {
+    if let Some(dotnet_root) = env::var_os("DOTNET_ROOT") {
+        return Some(PathBuf::from(dotnet_root));
+    }
+    // `dotnet` is often a symlink into the installation, e.g. `/usr/bin/dotnet`
+    let dotnet = fs::canonicalize(utils::find_program("dotnet")?).ok()?;
+    dotnet.parent().map(Path::to_path_buf)
+}
+
+/// Orders SDK versions like `3.1.402` and `5.0.100-rc.2.20479.15` by their numbers,
+/// with prereleases before the release of the same numbers
+fn compare_sdk_versions(a: &str, b: &str) -> Ordering {
+    fn parse(version: &str) -> (Vec<u64>, bool, &str) {
+        let mut parts = version.splitn(2, '-');
+        let numbers = parts
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|number| number.parse().unwrap_or(0))
+            .collect();
+        let prerelease = parts.next();
+        (
+            numbers,
+            prerelease.is_none(),
+            prerelease.unwrap_or_default(),
+        )
+    }
+
+    parse(a).cmp(&parse(b))
+}
+
+fn get_version_from_cli(context: &Context) -> Option<Version> {
+    let version_output = context.exec_cmd("dotnet", &["--version"])?;
+    Some(Version(version_output.stdout.trim().to_string()))
 }
 
-fn get_latest_sdk_from_cli() -> Option<Version> {
-    match utils::exec_cmd("dotnet", &["--list-sdks"]) {
+fn get_latest_sdk_from_cli(context: &Context) -> Option<Version> {
+    match context.exec_cmd("dotnet", &["--list-sdks"]) {
         Some(sdks_output) => {
             fn parse_failed<T>() -> Option<T> {
                 log::warn!("Unable to parse the output from `dotnet --list-sdks`.");
@@ -311,10 +396,7 @@ fn get_latest_sdk_from_cli() -> Option<Version> {
             let take_until = latest_sdk.find('[').or_else(parse_failed)? - 1;
             if take_until > 1 {
                 let version = &latest_sdk[..take_until];
-                let mut buffer = String::with_capacity(version.len() + 1);
-                buffer.push('v');
-                buffer.push_str(version);
-                Some(Version(buffer))
+                Some(Version(version.to_string()))
             } else {
                 parse_failed()
             }
@@ -326,7 +408,7 @@ fn get_latest_sdk_from_cli() -> Option<Version> {
                 "Received a non-success exit code from `dotnet --list-sdks`. \
                  Falling back to `dotnet --version`.",
             );
-            get_version_from_cli()
+            get_version_from_cli(context)
         }
     }
 }
@@ -347,6 +429,12 @@ enum FileType {
 
 struct Version(String);
 
+/// A version of the .NET SDK, and whether it is pinned by a `global.json`
+struct SdkVersion {
+    version: Version,
+    pinned: bool,
+}
+
 impl Deref for Version {
     type Target = String;
     fn deref(&self) -> &Self::Target {
@@ -365,7 +453,29 @@ fn should_parse_version_from_global_json() {
     "#;
 
     let version = get_pinned_sdk_version(json_text).unwrap();
-    assert_eq!("v1.2.3", version.0);
+    assert_eq!("1.2.3", version.0);
+}
+
+#[test]
+fn should_order_sdk_versions() {
+    let mut versions = vec![
+        "5.0.100",
+        "3.1.402",
+        "5.0.100-rc.2.20479.15",
+        "3.1.1000",
+        "5.0.100-preview.8.20417.9",
+    ];
+    versions.sort_by(|a, b| compare_sdk_versions(a, b));
+    assert_eq!(
+        versions,
+        vec![
+            "3.1.402",
+            "3.1.1000",
+            "5.0.100-preview.8.20417.9",
+            "5.0.100-rc.2.20479.15",
+            "5.0.100",
+        ]
+    );
 }
 
 #[test]
@@ -1,8 +1,7 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::elixir::ElixirConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 use regex::Regex;
 const ELIXIR_VERSION_PATTERN: &str = "\
@@ -15,16 +14,22 @@ Elixir (?P<elixir>\\d[.\\d]+).*";
 /// Will display the Elixir version if any of the following criteria are met:
 ///     - Current directory contains a `mix.exs` file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
-    let is_elixir_project = context.try_begin_scan()?.set_files(&["mix.exs"]).is_match();
+    let mut module = context.new_module("elixir");
+    let config = ElixirConfig::try_load(module.config);
+
+    let is_elixir_project = context
+        .try_begin_module_scan("elixir")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
 
     if !is_elixir_project {
         return None;
     }
 
-    let (otp_version, elixir_version) = get_elixir_version()?;
+    let (otp_version, elixir_version) = get_elixir_version(context)?;
 
-    let mut module = context.new_module("elixir");
-    let config = ElixirConfig::try_load(module.config);
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -36,11 +41,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(&elixir_version)),
-                "otp_version" => Some(Ok(&otp_version)),
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &elixir_version,
+                    config.version_format,
+                ))),
+                "otp_version" => Some(Ok(otp_version.clone())),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -54,8 +63,8 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_elixir_version() -> Option<(String, String)> {
-    let output = utils::exec_cmd("elixir", &["--version"])?.stdout;
+fn get_elixir_version(context: &Context) -> Option<(String, String)> {
+    let output = context.exec_cmd("elixir", &["--version"])?.stdout;
 
     parse_elixir_version(&output)
 }
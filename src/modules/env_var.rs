@@ -11,12 +11,42 @@ use crate::formatter::StringFormatter;
 /// Will display the environment variable's value if all of the following criteria are met:
 ///     - env_var.disabled is absent or false
 ///     - env_var.variable is defined
-///     - a variable named as the value of env_var.variable is defined
+///     - a variable named as the value of env_var.variable is defined, or env_var.default is
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("env_var");
     let config: EnvVarConfig = EnvVarConfig::try_load(module.config);
 
-    let env_value = get_env_value(config.variable?, config.default)?;
+    let variable = config.variable?;
+    render_env_var(&mut module, &config, variable, context)?;
+    Some(module)
+}
+
+/// Creates a module for an instance of the env_var module, configured in an
+/// `[env_var.<name>]` table, which displays the variable `name` unless `variable` is set
+pub fn instance_module<'a>(name: &str, context: &'a Context) -> Option<Module<'a>> {
+    let toml_config = context.config.get_env_var_module_config(name)?;
+    let config = EnvVarConfig::load(toml_config);
+    if config.disabled {
+        return None;
+    }
+
+    let mut module = Module::new(
+        &format!("env_var.{}", name),
+        super::description("env_var"),
+        Some(toml_config),
+    );
+    let variable = config.variable.unwrap_or(name);
+    render_env_var(&mut module, &config, variable, context)?;
+    Some(module)
+}
+
+fn render_env_var<'a>(
+    module: &mut Module<'a>,
+    config: &EnvVarConfig<'a>,
+    variable: &str,
+    context: &'a Context,
+) -> Option<()> {
+    let env_value = get_env_value(variable, config.default)?;
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -31,18 +61,18 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "env_value" => Some(Ok(&env_value)),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
         Ok(segments) => segments,
         Err(error) => {
-            log::warn!("Error in module `env_var`:\n{}", error);
+            log::warn!("Error in module `{}`:\n{}", module.get_name(), error);
             return None;
         }
     });
 
-    Some(module)
+    Some(())
 }
 
 fn get_env_value(name: &str, default: Option<&str>) -> Option<String> {
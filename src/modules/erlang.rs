@@ -1,7 +1,11 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::erlang::ErlangConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Create a module with the current Erlang version
 ///
@@ -9,18 +13,20 @@ use crate::formatter::StringFormatter;
 ///     - Current directory contains a rebar.config file
 ///     - Current directory contains a erlang.mk file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("erlang");
+    let config = ErlangConfig::try_load(module.config);
+
     let is_erlang_project = context
-        .try_begin_scan()?
-        .set_files(&["rebar.config", "erlang.mk"])
+        .try_begin_module_scan("erlang")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_erlang_project {
         return None;
     }
 
-    let mut module = context.new_module("erlang");
-    let config = ErlangConfig::try_load(module.config);
-
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -32,10 +38,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => get_erlang_version().map(Ok),
+                "version" => get_erlang_version(context).map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    ))
+                }),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -49,10 +61,36 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_erlang_version() -> Option<String> {
-    use crate::utils;
+fn get_erlang_version(context: &Context) -> Option<String> {
+    context
+        .get_pinned_version("erlang", &["erlang"])
+        .map(str::to_string)
+        .or_else(get_erlang_version_from_root)
+        .or_else(|| get_erlang_version_from_erl(context))
+}
+
+/// Reads the OTP version from the installation in `$ERLANG_ROOT`, which is much
+/// faster than booting an Erlang VM to ask for it
+fn get_erlang_version_from_root() -> Option<String> {
+    let erlang_root = env::var_os("ERLANG_ROOT")?;
+    read_otp_version(Path::new(&erlang_root))
+}
 
-    Some(utils::exec_cmd(
+/// Reads `releases/<release>/OTP_VERSION` in an Erlang installation, picking the
+/// most recent release if there are several of them
+fn read_otp_version(erlang_root: &Path) -> Option<String> {
+    let release = fs::read_dir(erlang_root.join("releases"))
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join("OTP_VERSION").is_file())
+        .max_by_key(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())?;
+
+    let otp_version = fs::read_to_string(release.path().join("OTP_VERSION")).ok()?;
+    Some(otp_version.trim().to_string())
+}
+
+fn get_erlang_version_from_erl(context: &Context) -> Option<String> {
+    Some(context.exec_cmd(
         "erl",
         &[
             "-noshell",
@@ -67,9 +105,10 @@ fn get_erlang_version() -> Option<String> {
 
 #[cfg(test)]
 mod tests {
+    use super::read_otp_version;
     use crate::modules::utils::test::render_module;
     use ansi_term::Color;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io;
 
     #[test]
@@ -96,4 +135,27 @@ mod tests {
 
         dir.close()
     }
+
+    #[test]
+    fn test_read_otp_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let releases = dir.path().join("releases");
+        fs::create_dir_all(releases.join("9"))?;
+        fs::create_dir_all(releases.join("22"))?;
+        fs::write(releases.join("9").join("OTP_VERSION"), "9.3.3\n")?;
+        fs::write(releases.join("22").join("OTP_VERSION"), "22.1.3\n")?;
+
+        assert_eq!(read_otp_version(dir.path()), Some("22.1.3".to_string()));
+
+        dir.close()
+    }
+
+    #[test]
+    fn test_read_otp_version_without_releases() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+
+        assert_eq!(read_otp_version(dir.path()), None);
+
+        dir.close()
+    }
 }
@@ -0,0 +1,105 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::fennel::FennelConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Fennel version
+///
+/// Will display the Fennel version if any of the following criteria are met:
+///     - Current directory contains a file with `.fnl` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("fennel");
+    let config: FennelConfig = FennelConfig::try_load(module.config);
+
+    let is_fennel_project = context
+        .try_begin_module_scan("fennel")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_fennel_project {
+        return None;
+    }
+
+    let fennel_version = context.exec_cmd("fennel", &["--version"])?.stdout;
+    let module_version = parse_fennel_version(&fennel_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `fennel`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_fennel_version(fennel_version: &str) -> Option<String> {
+    let version = fennel_version
+        // split into ["Fennel", "1.2.1", "on", "PUC", "Lua", "5.4"]
+        .split_whitespace()
+        // return "1.2.1"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_fennel_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_fennel_version() {
+        assert_eq!(
+            parse_fennel_version("Fennel 1.2.1 on PUC Lua 5.4\n"),
+            Some("1.2.1".to_string())
+        );
+        assert_eq!(parse_fennel_version("Fennel"), None);
+    }
+
+    #[test]
+    fn folder_without_fennel_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("fennel", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_fennel_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("man.fnl"))?.sync_all()?;
+        let actual = render_module("fennel", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("🧅 v1.2.1")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
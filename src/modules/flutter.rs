@@ -0,0 +1,194 @@
+use yaml_rust::YamlLoader;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::flutter::FlutterConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+/// Creates a module with the current Flutter SDK version and channel
+///
+/// Will display the Flutter version if any of the following criteria are met:
+///     - Current directory contains a `.metadata` file, written by `flutter create`
+///     - Current directory contains a `pubspec.yaml` file depending on `flutter`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("flutter");
+    let config: FlutterConfig = FlutterConfig::try_load(module.config);
+
+    let is_flutter_project = context
+        .try_begin_module_scan("flutter")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match()
+        || depends_on_flutter(context)?;
+
+    if !is_flutter_project {
+        return None;
+    }
+
+    let flutter_version = context.exec_cmd("flutter", &["--version"])?;
+    let (version, channel) = parse_flutter_version(&flutter_version.stdout)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &version,
+                    config.version_format,
+                ))),
+                "channel" => channel.clone().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `flutter`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Whether the `pubspec.yaml` of the current directory lists `flutter` as a dependency,
+/// which tells a Flutter app apart from a plain Dart package
+fn depends_on_flutter(context: &Context) -> Option<bool> {
+    let has_pubspec = context
+        .try_begin_scan()?
+        .set_files(&["pubspec.yaml"])
+        .is_match();
+    if !has_pubspec {
+        return Some(false);
+    }
+
+    let pubspec = utils::read_file(context.current_dir.join("pubspec.yaml")).ok()?;
+    Some(pubspec_depends_on_flutter(&pubspec))
+}
+
+fn pubspec_depends_on_flutter(pubspec: &str) -> bool {
+    match YamlLoader::load_from_str(pubspec) {
+        Ok(yaml_docs) => yaml_docs
+            .first()
+            .filter(|pubspec| !pubspec["dependencies"]["flutter"].is_badvalue())
+            .is_some(),
+        Err(_) => false,
+    }
+}
+
+fn parse_flutter_version(flutter_version: &str) -> Option<(String, Option<String>)> {
+    // "Flutter 3.13.9 • channel stable • https://github.com/flutter/flutter.git"
+    let mut words = flutter_version.lines().next()?.split_whitespace();
+    if words.next()? != "Flutter" {
+        return None;
+    }
+    let version = words.next()?.to_string();
+    let channel = words
+        .skip_while(|word| *word != "channel")
+        .nth(1)
+        .map(str::to_string);
+
+    Some((version, channel))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_flutter_version, pubspec_depends_on_flutter};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    const FLUTTER_PUBSPEC: &[u8] = b"\
+name: app
+dependencies:
+  flutter:
+    sdk: flutter
+";
+
+    #[test]
+    fn test_parse_flutter_version() {
+        assert_eq!(
+            parse_flutter_version(
+                "Flutter 3.13.9 • channel stable • https://github.com/flutter/flutter.git\n"
+            ),
+            Some(("3.13.9".to_string(), Some("stable".to_string())))
+        );
+        assert_eq!(
+            parse_flutter_version("Flutter 1.22.0-12.1.pre\n"),
+            Some(("1.22.0-12.1.pre".to_string(), None))
+        );
+        assert_eq!(parse_flutter_version("Dart SDK version: 2.8.4\n"), None);
+    }
+
+    #[test]
+    fn test_pubspec_depends_on_flutter() {
+        let dart_pubspec = "name: cli\ndependencies:\n  args: ^1.6.0\n";
+        assert!(pubspec_depends_on_flutter(
+            std::str::from_utf8(FLUTTER_PUBSPEC).unwrap()
+        ));
+        assert!(!pubspec_depends_on_flutter(dart_pubspec));
+        assert!(!pubspec_depends_on_flutter("name: cli\n"));
+    }
+
+    #[test]
+    fn folder_without_flutter_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("flutter", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_dart_pubspec() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("pubspec.yaml"))?;
+        file.write_all(b"name: cli\ndependencies:\n  args: ^1.6.0\n")?;
+        file.sync_all()?;
+        let actual = render_module("flutter", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_flutter_pubspec() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("pubspec.yaml"))?;
+        file.write_all(FLUTTER_PUBSPEC)?;
+        file.sync_all()?;
+        let actual = render_module("flutter", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🐦 v3.13.9 (stable)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_metadata() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(".metadata"))?.sync_all()?;
+        let actual = render_module("flutter", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Blue.bold().paint("🐦 v3.13.9 (stable)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,146 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::fortran::FortranConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Fortran compiler version
+///
+/// Will display the Fortran compiler version if any of the following criteria are met:
+///     - Current directory contains a `fpm.toml` file
+///     - Current directory contains a file with `.f90`, `.f95`, `.f03` or `.f08` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("fortran");
+    let config: FortranConfig = FortranConfig::try_load(module.config);
+
+    let is_fortran_project = context
+        .try_begin_module_scan("fortran")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_fortran_project {
+        return None;
+    }
+
+    let compiler_version = context
+        .exec_cmd(config.compiler_binary, &["--version"])?
+        .stdout;
+    let module_version = parse_fortran_version(&compiler_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `fortran`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_fortran_version(compiler_version: &str) -> Option<String> {
+    let first_line = compiler_version.lines().next()?;
+    let version = first_line
+        // "GNU Fortran (GCC) 10.2.0" or "ifort (IFORT) 19.1.2.254 20200623"
+        [first_line.find(')')? + 1..]
+        .split_whitespace()
+        // return "10.2.0" or "19.1.2.254"
+        .next()?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_fortran_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_fortran_version() {
+        let gfortran = "\
+GNU Fortran (Ubuntu 9.3.0-17ubuntu1~20.04) 9.3.0
+Copyright (C) 2019 Free Software Foundation, Inc.";
+        assert_eq!(parse_fortran_version(gfortran), Some("9.3.0".to_string()));
+
+        let ifort = "\
+ifort (IFORT) 19.1.2.254 20200623
+Copyright (C) 1985-2020 Intel Corporation.  All rights reserved.";
+        assert_eq!(parse_fortran_version(ifort), Some("19.1.2.254".to_string()));
+
+        assert_eq!(parse_fortran_version("gfortran"), None);
+    }
+
+    #[test]
+    fn folder_without_fortran_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("fortran", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_f90_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.f90"))?.sync_all()?;
+        let actual = render_module("fortran", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Purple.bold().paint("🅵 v10.2.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_fpm_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("fpm.toml"))?.sync_all()?;
+        let actual = render_module("fortran", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Purple.bold().paint("🅵 v10.2.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn compiler_binary_option() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.f08"))?.sync_all()?;
+        let actual = render_module(
+            "fortran",
+            dir.path(),
+            Some(toml::toml! {
+                [fortran]
+                compiler_binary = "ifort"
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Purple.bold().paint("🅵 v19.1.2.254")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
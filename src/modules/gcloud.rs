@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::env;
+use std::path::{Path, PathBuf};
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::gcloud::GcloudConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+type Account = String;
+type Project = String;
+type Region = String;
+
+/// The directory holding the gcloud configurations, `$CLOUDSDK_CONFIG` or `~/.config/gcloud`
+fn get_gcloud_config_dir() -> Option<PathBuf> {
+    env::var("CLOUDSDK_CONFIG")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs_next::home_dir()?.join(".config/gcloud")))
+}
+
+/// The name of the active configuration, `$CLOUDSDK_ACTIVE_CONFIG_NAME` or the one
+/// selected with `gcloud config configurations activate`
+fn get_active_config(config_dir: &Path) -> Option<String> {
+    let name = env::var("CLOUDSDK_ACTIVE_CONFIG_NAME")
+        .ok()
+        .or_else(|| utils::read_file(config_dir.join("active_config")).ok())?;
+    let name = name.trim();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Read the account, project and region from the properties of a gcloud configuration,
+/// an INI file with the `[core]` and `[compute]` sections
+fn parse_gcloud_config(contents: &str) -> (Option<Account>, Option<Project>, Option<Region>) {
+    let mut section = "";
+    let (mut account, mut project, mut region) = (None, None, None);
+
+    for line in contents.lines().map(str::trim) {
+        if let Some(name) = line
+            .strip_prefix('[')
+            .and_then(|line| line.strip_suffix(']'))
+        {
+            section = name;
+            continue;
+        }
+
+        let mut parts = line.splitn(2, '=');
+        let (key, value) = match (parts.next(), parts.next()) {
+            (Some(key), Some(value)) => (key.trim(), value.trim().to_string()),
+            _ => continue,
+        };
+        match (section, key) {
+            ("core", "account") => account = Some(value),
+            ("core", "project") => project = Some(value),
+            ("compute", "region") => region = Some(value),
+            _ => {}
+        }
+    }
+
+    (account, project, region)
+}
+
+fn alias_name(name: String, aliases: &HashMap<String, &str>) -> String {
+    match aliases.get(&name) {
+        None => name,
+        Some(alias) => (*alias).to_string(),
+    }
+}
+
+/// Creates a module with the active Google Cloud configuration
+///
+/// Will display the account, project and region of the active gcloud configuration,
+/// read from its file rather than by running the slow `gcloud` command.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("gcloud");
+    let config: GcloudConfig = GcloudConfig::try_load(module.config);
+
+    let config_dir = get_gcloud_config_dir()?;
+    let active_config = get_active_config(&config_dir)?;
+    let gcloud_config = utils::read_file(
+        config_dir
+            .join("configurations")
+            .join(format!("config_{}", active_config)),
+    )
+    .ok()?;
+
+    let (account, project, region) = parse_gcloud_config(&gcloud_config);
+    if account.is_none() && project.is_none() {
+        return None;
+    }
+
+    let project = project.map(|project| alias_name(project, &config.project_aliases));
+    let region = region.map(|region| alias_name(region, &config.region_aliases));
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "account" => account.as_ref().map(Ok),
+                "project" => project.as_ref().map(Ok),
+                "region" => region.as_ref().map(Ok),
+                "active" => Some(Ok(&active_config)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `gcloud`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
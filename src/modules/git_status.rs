@@ -6,9 +6,11 @@ use crate::configs::git_status::GitStatusConfig;
 use crate::context::Repo;
 use crate::formatter::StringFormatter;
 use crate::segment::Segment;
+use once_cell::sync::OnceCell;
 use std::sync::{Arc, RwLock};
 
-const ALL_STATUS_FORMAT: &str = "$conflicted$stashed$deleted$renamed$modified$staged$untracked";
+const ALL_STATUS_FORMAT: &str =
+    "$conflicted$stashed$deleted$renamed$modified$staged$untracked$summary";
 
 /// Creates a module with the Git branch in the current directory
 ///
@@ -24,12 +26,24 @@ const ALL_STATUS_FORMAT: &str = "$conflicted$stashed$deleted$renamed$modified$st
 ///   - `+` — A new file has been added to the staging area
 ///   - `»` — A renamed file has been added to the staging area
 ///   - `✘` — A file's deletion has been added to the staging area
+///
+/// When more files than `summarize_above` have changed, the file counters are
+/// replaced with a single summary, e.g. `~42 files`
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let repo = context.get_repo().ok()?;
     let info = Arc::new(GitStatusInfo::load(repo));
 
     let mut module = context.new_module("git_status");
     let config: GitStatusConfig = GitStatusConfig::try_load(module.config);
+    // The number of changed files, if there are too many to show every counter
+    let summarized: OnceCell<Option<usize>> = OnceCell::new();
+    let summarized = || {
+        *summarized.get_or_init(|| {
+            let threshold = info.get_summarize_above(config.summarize_above);
+            info.get_changed()
+                .filter(|changed| threshold > 0 && *changed as i64 > threshold)
+        })
+    };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -44,49 +58,60 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map_variables_to_segments(|variable: &str| {
                 let info = Arc::clone(&info);
                 let segments = match variable {
+                    "conflicted" | "deleted" | "renamed" | "modified" | "staged" | "untracked"
+                        if summarized().is_some() =>
+                    {
+                        None
+                    }
+                    "summary" => summarized().and_then(|count| {
+                        format_count(config.summary, "git_status.summary", count, context)
+                    }),
                     "stashed" => info.get_stashed().and_then(|count| {
-                        format_count(config.stashed, "git_status.stashed", count)
+                        format_count(config.stashed, "git_status.stashed", count, context)
                     }),
                     "ahead_behind" => info.get_ahead_behind().and_then(|(ahead, behind)| {
                         if ahead > 0 && behind > 0 {
-                            format_text(config.diverged, "git_status.diverged", |variable| {
-                                match variable {
+                            format_text(
+                                config.diverged,
+                                "git_status.diverged",
+                                context,
+                                |variable| match variable {
                                     "ahead_count" => Some(ahead.to_string()),
                                     "behind_count" => Some(behind.to_string()),
                                     _ => None,
-                                }
-                            })
+                                },
+                            )
                         } else if ahead > 0 && behind == 0 {
-                            format_count(config.ahead, "git_status.ahead", ahead)
+                            format_count(config.ahead, "git_status.ahead", ahead, context)
                         } else if behind > 0 && ahead == 0 {
-                            format_count(config.behind, "git_status.behind", behind)
+                            format_count(config.behind, "git_status.behind", behind, context)
                         } else {
                             None
                         }
                     }),
                     "conflicted" => info.get_conflicted().and_then(|count| {
-                        format_count(config.conflicted, "git_status.conflicted", count)
+                        format_count(config.conflicted, "git_status.conflicted", count, context)
                     }),
                     "deleted" => info.get_deleted().and_then(|count| {
-                        format_count(config.deleted, "git_status.deleted", count)
+                        format_count(config.deleted, "git_status.deleted", count, context)
                     }),
                     "renamed" => info.get_renamed().and_then(|count| {
-                        format_count(config.renamed, "git_status.renamed", count)
+                        format_count(config.renamed, "git_status.renamed", count, context)
                     }),
                     "modified" => info.get_modified().and_then(|count| {
-                        format_count(config.modified, "git_status.modified", count)
+                        format_count(config.modified, "git_status.modified", count, context)
+                    }),
+                    "staged" => info.get_staged().and_then(|count| {
+                        format_count(config.staged, "git_status.staged", count, context)
                     }),
-                    "staged" => info
-                        .get_staged()
-                        .and_then(|count| format_count(config.staged, "git_status.staged", count)),
                     "untracked" => info.get_untracked().and_then(|count| {
-                        format_count(config.untracked, "git_status.untracked", count)
+                        format_count(config.untracked, "git_status.untracked", count, context)
                     }),
                     _ => None,
                 };
                 segments.map(Ok)
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -245,6 +270,19 @@ impl<'a> GitStatusInfo<'a> {
     pub fn get_untracked(&self) -> Option<usize> {
         self.get_repo_status().map(|data| data.untracked)
     }
+
+    pub fn get_changed(&self) -> Option<usize> {
+        self.get_repo_status().map(|data| data.changed)
+    }
+
+    /// The `summarize_above` threshold, which can be overridden for a single
+    /// repository with `git config starship.summarizeAbove <N>`
+    pub fn get_summarize_above(&self, default: i64) -> i64 {
+        self.get_repository()
+            .and_then(|repo| repo.config().ok())
+            .and_then(|config| config.get_i64("starship.summarizeAbove").ok())
+            .unwrap_or(default)
+    }
 }
 
 /// Gets the number of files in various git states (staged, modified, deleted, etc...)
@@ -310,6 +348,7 @@ struct RepoStatus {
     modified: usize,
     staged: usize,
     untracked: usize,
+    changed: usize,
 }
 
 impl RepoStatus {
@@ -337,6 +376,10 @@ impl RepoStatus {
         status.is_wt_new()
     }
 
+    fn is_changed(status: Status) -> bool {
+        !status.is_empty() && !status.is_ignored()
+    }
+
     fn add(&mut self, s: Status) {
         self.conflicted += RepoStatus::is_conflicted(s) as usize;
         self.deleted += RepoStatus::is_deleted(s) as usize;
@@ -344,17 +387,23 @@ impl RepoStatus {
         self.modified += RepoStatus::is_modified(s) as usize;
         self.staged += RepoStatus::is_staged(s) as usize;
         self.untracked += RepoStatus::is_untracked(s) as usize;
+        self.changed += RepoStatus::is_changed(s) as usize;
     }
 }
 
-fn format_text<F>(format_str: &str, config_path: &str, mapper: F) -> Option<Vec<Segment>>
+fn format_text<'a, F>(
+    format_str: &'a str,
+    config_path: &str,
+    context: &Context,
+    mapper: F,
+) -> Option<Vec<Segment<'a>>>
 where
     F: Fn(&str) -> Option<String> + Send + Sync,
 {
     if let Ok(formatter) = StringFormatter::new(format_str) {
         formatter
             .map(|variable| mapper(variable).map(Ok))
-            .parse(None)
+            .parse(None, Some(context))
             .ok()
     } else {
         log::error!("Error parsing format string `{}`", &config_path);
@@ -362,13 +411,23 @@ where
     }
 }
 
-fn format_count(format_str: &str, config_path: &str, count: usize) -> Option<Vec<Segment>> {
+fn format_count<'a>(
+    format_str: &'a str,
+    config_path: &str,
+    count: usize,
+    context: &Context,
+) -> Option<Vec<Segment<'a>>> {
     if count == 0 {
         return None;
     }
 
-    format_text(format_str, config_path, |variable| match variable {
-        "count" => Some(count.to_string()),
-        _ => None,
-    })
+    format_text(
+        format_str,
+        config_path,
+        context,
+        |variable| match variable {
+            "count" => Some(count.to_string()),
+            _ => None,
+        },
+    )
 }
@@ -0,0 +1,122 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::gleam::GleamConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Gleam version
+///
+/// Will display the Gleam version if any of the following criteria are met:
+///     - Current directory contains a file with `.gleam` extension
+///     - Current directory contains a `gleam.toml` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("gleam");
+    let config: GleamConfig = GleamConfig::try_load(module.config);
+
+    let is_gleam_project = context
+        .try_begin_module_scan("gleam")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_gleam_project {
+        return None;
+    }
+
+    let gleam_version = context.exec_cmd("gleam", &["--version"])?.stdout;
+    let module_version = parse_gleam_version(&gleam_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `gleam`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_gleam_version(gleam_version: &str) -> Option<String> {
+    let version = gleam_version
+        // split into ["gleam", "0.12.1"]
+        .split_whitespace()
+        // return "0.12.1"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_gleam_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_gleam_version() {
+        assert_eq!(
+            parse_gleam_version("gleam 0.12.1\n"),
+            Some("0.12.1".to_string())
+        );
+        assert_eq!(parse_gleam_version("gleam"), None);
+    }
+
+    #[test]
+    fn folder_without_gleam_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("gleam", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_gleam_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("hello.gleam"))?.sync_all()?;
+        let actual = render_module("gleam", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::RGB(0xFF, 0xAF, 0xF3).bold().paint("⭐ v0.12.1")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_gleam_toml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("gleam.toml"))?.sync_all()?;
+        let actual = render_module("gleam", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::RGB(0xFF, 0xAF, 0xF3).bold().paint("⭐ v0.12.1")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -1,9 +1,11 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::go::GoConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils;
 
+use once_cell::sync::OnceCell;
+
 /// Creates a module with the current Go version
 ///
 /// Will display the Go version if any of the following criteria are met:
@@ -16,26 +18,34 @@ use crate::utils;
 ///     - Current directory contains a `Godeps` directory
 ///     - Current directory contains a file with the `.go` extension
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("golang");
+    let config = GoConfig::try_load(module.config);
+
     let is_go_project = context
-        .try_begin_scan()?
-        .set_files(&[
-            "go.mod",
-            "go.sum",
-            "glide.yaml",
-            "Gopkg.yml",
-            "Gopkg.lock",
-            ".go-version",
-        ])
-        .set_extensions(&["go"])
-        .set_folders(&["Godeps"])
+        .try_begin_module_scan("golang")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_go_project {
         return None;
     }
 
-    let mut module = context.new_module("golang");
-    let config = GoConfig::try_load(module.config);
+    // The version is only looked up if it is needed, by `$version` or to compare it
+    // with the `go` directive of `go.mod`
+    let go_version = OnceCell::new();
+    let get_go_version = || {
+        go_version
+            .get_or_init(|| get_go_version(context))
+            .as_deref()
+    };
+    let mod_version = get_go_mod_version(context);
+    let is_capable = match (&mod_version, get_go_version()) {
+        (Some(mod_version), Some(go_version)) => is_go_version_capable(go_version, mod_version),
+        _ => true,
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -43,16 +53,22 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if is_capable => Some(Ok(config.style)),
+                "style" => Some(Ok(config.not_capable_style)),
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    format_go_version(&utils::exec_cmd("go", &["version"])?.stdout.as_str()).map(Ok)
-                }
+                "version" => get_go_version().map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        version,
+                        config.version_format,
+                    ))
+                }),
+                "mod_version" => mod_version.clone().map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -66,6 +82,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_go_version(context: &Context) -> Option<String> {
+    match context.get_pinned_version("golang", &["golang", "go"]) {
+        Some(version) => Some(version.to_string()),
+        None => format_go_version(&context.exec_cmd("go", &["version"])?.stdout),
+    }
+}
+
 fn format_go_version(go_stdout: &str) -> Option<String> {
     // go version output looks like this:
     // go version go1.13.3 linux/amd64
@@ -80,7 +103,44 @@ fn format_go_version(go_stdout: &str) -> Option<String> {
         // return "1.12.4"
         .next()?;
 
-    Some(format!("v{}", version))
+    Some(version.to_string())
+}
+
+/// Reads the minimum Go version of the module from the `go` directive of `go.mod`,
+/// e.g. `1.15` for `go 1.15`
+fn get_go_mod_version(context: &Context) -> Option<String> {
+    let go_mod = utils::read_file(context.current_dir.join("go.mod")).ok()?;
+    parse_go_mod_version(&go_mod)
+}
+
+fn parse_go_mod_version(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        let mut words = line.split_whitespace();
+        if words.next()? != "go" {
+            return None;
+        }
+        words.next().map(str::to_string)
+    })
+}
+
+/// Whether the installed Go version is at least the version required by `go.mod`.
+/// Missing numbers count as `0`, so `1.15` requires at least `1.15.0`.
+fn is_go_version_capable(go_version: &str, mod_version: &str) -> bool {
+    fn parse_numbers(version: &str) -> Vec<u64> {
+        let mut numbers: Vec<u64> = version
+            .split('.')
+            .map(|part| {
+                let digits = part
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(part.len());
+                part[..digits].parse().unwrap_or(0)
+            })
+            .collect();
+        numbers.resize(3, 0);
+        numbers
+    }
+
+    parse_numbers(go_version) >= parse_numbers(mod_version)
 }
 
 #[cfg(test)]
@@ -91,6 +151,23 @@ mod tests {
     use std::fs::{self, File};
     use std::io;
 
+    #[test]
+    fn test_parse_go_mod_version() {
+        let go_mod =
+            "module example.com/hello\n\ngo 1.15 // minimum\n\nrequire golang.org/x/text v0.3.0\n";
+        assert_eq!(parse_go_mod_version(go_mod), Some("1.15".to_string()));
+        assert_eq!(parse_go_mod_version("module example.com/hello\n"), None);
+    }
+
+    #[test]
+    fn test_is_go_version_capable() {
+        assert!(is_go_version_capable("1.15.2", "1.15"));
+        assert!(is_go_version_capable("1.16beta1", "1.15"));
+        assert!(is_go_version_capable("1.15", "1.15.0"));
+        assert!(!is_go_version_capable("1.14.9", "1.15"));
+        assert!(!is_go_version_capable("1.21.0", "1.21.1"));
+    }
+
     #[test]
     fn folder_without_go_files() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -126,6 +203,46 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn folder_with_go_mod_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("go.mod"),
+            "module github.com/starship/starship\n\ngo 1.12\n",
+        )?;
+
+        let actual = render_module(
+            "golang",
+            dir.path(),
+            Some(toml::toml! {
+                [golang]
+                format = "via [$symbol$version \\($mod_version\\)]($style) "
+            }),
+        );
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Cyan.bold().paint("🐹 v1.12.1 (1.12)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_newer_go_mod_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("go.mod"),
+            "module github.com/starship/starship\n\ngo 1.15\n",
+        )?;
+
+        let actual = render_module("golang", dir.path(), None);
+
+        let expected = Some(format!("via {} ", Color::Red.bold().paint("🐹 v1.12.1")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_go_sum() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -198,6 +315,6 @@ mod tests {
     #[test]
     fn test_format_go_version() {
         let input = "go version go1.12 darwin/amd64";
-        assert_eq!(format_go_version(input), Some("v1.12".to_string()));
+        assert_eq!(format_go_version(input), Some("1.12".to_string()));
     }
 }
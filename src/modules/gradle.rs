@@ -0,0 +1,188 @@
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::gradle::GradleConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+const WRAPPER_PROPERTIES: &str = "gradle/wrapper/gradle-wrapper.properties";
+
+/// Creates a module with the Gradle version pinned by the Gradle wrapper
+///
+/// Will display the Gradle version if any of the following criteria are met:
+///     - Current directory contains a `build.gradle` or `build.gradle.kts` file
+///     - Current directory contains a `settings.gradle` or `settings.gradle.kts` file
+///
+/// The version is read from `gradle/wrapper/gradle-wrapper.properties` in the current
+/// directory, or a parent directory within the same repository, rather than by starting
+/// Gradle.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("gradle");
+    let config: GradleConfig = GradleConfig::try_load(module.config);
+
+    let is_gradle_project = context
+        .try_begin_module_scan("gradle")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_gradle_project {
+        return None;
+    }
+
+    let repo_root = context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.root.as_deref());
+    let gradle_version = find_wrapper_version(&context.current_dir, repo_root)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &gradle_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `gradle`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Find the wrapper properties of the current directory, or of the root project
+/// of a multi-project build
+fn find_wrapper_version(current_dir: &Path, repo_root: Option<&Path>) -> Option<String> {
+    for dir in current_dir.ancestors() {
+        if let Ok(properties) = utils::read_file(dir.join(WRAPPER_PROPERTIES)) {
+            return parse_wrapper_version(&properties);
+        }
+        if Some(dir) == repo_root {
+            break;
+        }
+    }
+    None
+}
+
+fn parse_wrapper_version(properties: &str) -> Option<String> {
+    // distributionUrl=https\://services.gradle.org/distributions/gradle-6.6.1-bin.zip
+    let url = properties
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("distributionUrl"))?
+        .trim_start()
+        .strip_prefix('=')?
+        .trim();
+    let file_name = url.rsplit('/').next()?;
+    let name = file_name.strip_prefix("gradle-")?;
+    // Drop the distribution type, e.g. `-bin.zip` or `-all.zip`
+    let version = &name[..name.rfind('-')?];
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_wrapper_version, WRAPPER_PROPERTIES};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    fn write_wrapper_properties(dir: &Path, version: &str) -> io::Result<()> {
+        let path = dir.join(WRAPPER_PROPERTIES);
+        fs::create_dir_all(path.parent().unwrap())?;
+        let mut file = File::create(path)?;
+        write!(
+            file,
+            "distributionBase=GRADLE_USER_HOME\n\
+             distributionPath=wrapper/dists\n\
+             distributionUrl=https\\://services.gradle.org/distributions/gradle-{}-bin.zip\n",
+            version
+        )?;
+        file.sync_all()
+    }
+
+    #[test]
+    fn test_parse_wrapper_version() {
+        let properties =
+            "distributionUrl=https\\://services.gradle.org/distributions/gradle-6.6.1-all.zip";
+        assert_eq!(parse_wrapper_version(properties), Some("6.6.1".to_string()));
+        let properties = "distributionUrl = https\\://example.com/gradle-7.0-rc-1-bin.zip\n";
+        assert_eq!(
+            parse_wrapper_version(properties),
+            Some("7.0-rc-1".to_string())
+        );
+        assert_eq!(parse_wrapper_version("zipStoreBase=GRADLE_USER_HOME"), None);
+    }
+
+    #[test]
+    fn folder_without_gradle_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        write_wrapper_properties(dir.path(), "6.6.1")?;
+        let actual = render_module("gradle", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_without_wrapper() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("build.gradle"))?.sync_all()?;
+        let actual = render_module("gradle", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_build_gradle_kts() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("build.gradle.kts"))?.sync_all()?;
+        write_wrapper_properties(dir.path(), "6.6.1")?;
+        let actual = render_module("gradle", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(14).bold().paint("🅶 v6.6.1")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn subproject_uses_root_wrapper() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("settings.gradle"))?.sync_all()?;
+        write_wrapper_properties(dir.path(), "6.6.1")?;
+        let subproject = dir.path().join("app");
+        fs::create_dir(&subproject)?;
+        File::create(subproject.join("build.gradle"))?.sync_all()?;
+        let actual = render_module("gradle", &subproject, None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(14).bold().paint("🅶 v6.6.1")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,48 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::guix_shell::GuixShellConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module showing if inside a guix-shell
+///
+/// The module will use the `$GUIX_ENVIRONMENT` environment variable, set by `guix shell`
+/// and `guix environment` to the profile of the environment, to determine if it's inside
+/// a guix-shell.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("guix_shell");
+    let config: GuixShellConfig = GuixShellConfig::try_load(module.config);
+
+    let guix_environment = env::var("GUIX_ENVIRONMENT").ok()?;
+    if guix_environment.is_empty() {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "profile" => Some(Ok(&guix_environment)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `guix_shell`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
@@ -0,0 +1,280 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::haskell::HaskellConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+use once_cell::sync::OnceCell;
+use std::fs;
+use std::path::Path;
+use yaml_rust::YamlLoader;
+
+/// The build tool a Haskell project expects
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tool {
+    Stack,
+    Cabal,
+}
+
+/// Creates a module with the current GHC version and the build tool of the project
+///
+/// Will display the GHC version if any of the following criteria are met:
+///     - Current directory contains a `stack.yaml` file, for Stack projects
+///     - Current directory contains a `cabal.project` file or a file with `.cabal`
+///       extension, for Cabal projects
+///     - Current directory contains a file with `.hs` or `.lhs` extension
+///
+/// Stack projects usually have a `.cabal` file too, so `stack.yaml` takes precedence.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("haskell");
+    let config: HaskellConfig = HaskellConfig::try_load(module.config);
+
+    let is_haskell_project = context
+        .try_begin_module_scan("haskell")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_haskell_project {
+        return None;
+    }
+
+    let tool = detect_tool(context)?;
+
+    // The GHC version is only looked up if it is needed, as `stack` can be slow to start
+    let ghc_version = OnceCell::new();
+    let get_ghc_version = || {
+        ghc_version
+            .get_or_init(|| get_ghc_version(context, tool))
+            .as_deref()
+    };
+    let snapshot = match tool {
+        Some(Tool::Stack) => get_stack_snapshot(context),
+        _ => None,
+    };
+
+    let (symbol, tool_name) = match tool {
+        Some(Tool::Stack) => (config.stack_symbol, Some("stack")),
+        Some(Tool::Cabal) => (config.cabal_symbol, Some("cabal")),
+        None => (config.symbol, None),
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => get_ghc_version().map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        version,
+                        config.version_format,
+                    ))
+                }),
+                "ghc_version" => get_ghc_version().map(|version| Ok(version.to_string())),
+                "snapshot" => snapshot.clone().map(Ok),
+                "tool" => tool_name.map(|tool| Ok(tool.to_string())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `haskell`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn get_ghc_version(context: &Context, tool: Option<Tool>) -> Option<String> {
+    let output = match tool {
+        // Stack manages its own GHC, which may differ from the one on the `PATH`
+        Some(Tool::Stack) => context.exec_cmd(
+            "stack",
+            &["ghc", "--", "--numeric-version", "--no-install-ghc"],
+        )?,
+        // The GHC set with ghcup is a link named after its version
+        _ => match get_ghcup_ghc_version() {
+            Some(version) => return Some(version),
+            None => context.exec_cmd("ghc", &["--numeric-version"])?,
+        },
+    };
+    Some(output.stdout.trim().to_string())
+}
+
+/// Reads the version of the `ghc` in `$PATH` from the link ghcup created for it,
+/// without starting GHC
+fn get_ghcup_ghc_version() -> Option<String> {
+    let ghc = utils::find_program("ghc")?;
+    let target = fs::read_link(ghc).ok()?;
+    ghc_version_from_link(&target)
+}
+
+/// Extracts the GHC version from the target of a ghcup link, either `ghc-8.10.2` or
+/// `../ghc/8.10.2/bin/ghc`
+fn ghc_version_from_link(target: &Path) -> Option<String> {
+    let file_name = target.file_name()?.to_str()?;
+    let version = match file_name.strip_prefix("ghc-") {
+        Some(version) => version,
+        None => target.parent()?.parent()?.file_name()?.to_str()?,
+    };
+
+    if version.starts_with(|c: char| c.is_ascii_digit()) {
+        Some(version.to_string())
+    } else {
+        None
+    }
+}
+
+/// Reads the snapshot of a Stack project from the `resolver` or `snapshot` of `stack.yaml`
+fn get_stack_snapshot(context: &Context) -> Option<String> {
+    let stack_yaml = utils::read_file(context.current_dir.join("stack.yaml")).ok()?;
+    parse_stack_snapshot(&stack_yaml)
+}
+
+fn parse_stack_snapshot(stack_yaml: &str) -> Option<String> {
+    let documents = YamlLoader::load_from_str(stack_yaml).ok()?;
+    let document = documents.first()?;
+    let snapshot = document["resolver"]
+        .as_str()
+        .or_else(|| document["snapshot"].as_str())?;
+    Some(snapshot.trim().to_string())
+}
+
+fn detect_tool(context: &Context) -> Option<Option<Tool>> {
+    let is_stack_project = context
+        .try_begin_scan()?
+        .set_files(&["stack.yaml"])
+        .is_match();
+    if is_stack_project {
+        return Some(Some(Tool::Stack));
+    }
+
+    let is_cabal_project = context
+        .try_begin_scan()?
+        .set_files(&["cabal.project"])
+        .set_extensions(&["cabal"])
+        .is_match();
+    if is_cabal_project {
+        return Some(Some(Tool::Cabal));
+    }
+
+    Some(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_ghc_version_from_link() {
+        let version = |target: &str| ghc_version_from_link(Path::new(target));
+        assert_eq!(version("ghc-8.10.2"), Some("8.10.2".to_string()));
+        assert_eq!(version("../ghc/9.0.1/bin/ghc"), Some("9.0.1".to_string()));
+        assert_eq!(version("/usr/lib/ghc/bin/ghc"), None);
+    }
+
+    #[test]
+    fn test_parse_stack_snapshot() {
+        assert_eq!(
+            parse_stack_snapshot("resolver: lts-16.12\npackages:\n- .\n"),
+            Some("lts-16.12".to_string())
+        );
+        assert_eq!(
+            parse_stack_snapshot("snapshot: nightly-2020-09-01\n"),
+            Some("nightly-2020-09-01".to_string())
+        );
+        assert_eq!(
+            parse_stack_snapshot("resolver:\n  url: https://example.com/snapshot.yaml\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn folder_without_haskell_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("haskell", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_hs_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Main.hs"))?.sync_all()?;
+        let actual = render_module("haskell", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Red.bold().paint("λ v8.10.2")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_cabal_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("starship.cabal"))?.sync_all()?;
+        let actual = render_module("haskell", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Red.bold().paint("λ v8.10.2 (cabal)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_stack_snapshot() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(dir.path().join("stack.yaml"), "resolver: lts-16.12\n")?;
+        let actual = render_module(
+            "haskell",
+            dir.path(),
+            Some(toml::toml! {
+                [haskell]
+                format = "via [$symbol$snapshot \\(ghc $ghc_version\\)]($style) "
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Red.bold().paint("λ lts-16.12 (ghc 8.8.4)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_stack_yaml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("stack.yaml"))?.sync_all()?;
+        File::create(dir.path().join("starship.cabal"))?.sync_all()?;
+        let actual = render_module(
+            "haskell",
+            dir.path(),
+            Some(toml::toml! {
+                [haskell]
+                stack_symbol = "📚 "
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Red.bold().paint("📚 v8.8.4 (stack)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
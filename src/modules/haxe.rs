@@ -0,0 +1,140 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::haxe::HaxeConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Haxe version
+///
+/// Will display the Haxe version if any of the following criteria are met:
+///     - Current directory contains a file with `.hx` extension
+///     - Current directory contains a `haxe.json`, `hxformat.json` or `.haxerc` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("haxe");
+    let config: HaxeConfig = HaxeConfig::try_load(module.config);
+
+    let is_haxe_project = context
+        .try_begin_module_scan("haxe")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_haxe_project {
+        return None;
+    }
+
+    let haxe_version = context.exec_cmd("haxe", &["--version"])?;
+    // Haxe 3 prints the version to stderr instead of stdout
+    let module_version = parse_haxe_version(&haxe_version.stdout)
+        .or_else(|| parse_haxe_version(&haxe_version.stderr))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `haxe`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_haxe_version(haxe_version: &str) -> Option<String> {
+    let version = haxe_version
+        // e.g. "4.1.4", or "4.2.0-rc.1+354c24d" for development builds
+        .split_whitespace()
+        .next()?
+        // drop the commit hash, "4.2.0-rc.1"
+        .split('+')
+        .next()?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_haxe_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_haxe_version() {
+        assert_eq!(parse_haxe_version("4.1.4\n"), Some("4.1.4".to_string()));
+        assert_eq!(
+            parse_haxe_version("4.2.0-rc.1+354c24d\n"),
+            Some("4.2.0-rc.1".to_string())
+        );
+        assert_eq!(parse_haxe_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_haxe_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("haxe", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_hx_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Main.hx"))?.sync_all()?;
+        let actual = render_module("haxe", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(202).bold().paint("⌘ v4.1.4")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_haxerc_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(".haxerc"))?.sync_all()?;
+        let actual = render_module("haxe", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(202).bold().paint("⌘ v4.1.4")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_haxe_json_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("haxe.json"))?.sync_all()?;
+        let actual = render_module("haxe", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(202).bold().paint("⌘ v4.1.4")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
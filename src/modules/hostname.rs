@@ -1,5 +1,3 @@
-use std::env;
-
 use super::{Context, Module};
 use std::ffi::OsString;
 
@@ -11,13 +9,13 @@ use crate::formatter::StringFormatter;
 ///
 /// Will display the hostname if all of the following criteria are met:
 ///     - hostname.disabled is absent or false
-///     - hostname.ssh_only is false OR the user is currently connected as an SSH session (`$SSH_CONNECTION`)
+///     - hostname.ssh_only is false OR the user is currently connected as an SSH session (`$SSH_CONNECTION` or `$SSH_TTY`)
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("hostname");
     let config: HostnameConfig = HostnameConfig::try_load(module.config);
 
-    let ssh_connection = env::var("SSH_CONNECTION").ok();
-    if config.ssh_only && ssh_connection.is_none() {
+    let is_ssh_session = context.is_ssh_session();
+    if config.ssh_only && !is_ssh_session {
         return None;
     }
 
@@ -45,6 +43,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
+            .map_meta(|variable, _| match variable {
+                "ssh_symbol" if is_ssh_session => Some(config.ssh_symbol),
+                _ => None,
+            })
             .map_style(|variable| match variable {
                 "style" => Some(Ok(config.style)),
                 _ => None,
@@ -53,7 +55,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "hostname" => Some(Ok(host)),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -15,11 +15,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         .trim()
         .parse::<i64>()
         .ok()?;
-    if num_of_jobs == 0 {
+
+    // `threshold` is the former option, showing the number once it is exceeded
+    let number_threshold = match config.threshold {
+        Some(threshold) => threshold + 1,
+        None => config.number_threshold,
+    };
+    let show_symbol = num_of_jobs >= config.symbol_threshold;
+    let show_number = num_of_jobs >= number_threshold;
+    if !show_symbol && !show_number {
         return None;
     }
 
-    let module_number = if num_of_jobs > config.threshold {
+    let module_number = if show_number {
         num_of_jobs.to_string()
     } else {
         "".to_string()
@@ -28,7 +36,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
-                "symbol" => Some(config.symbol),
+                "symbol" if show_symbol => Some(config.symbol),
                 _ => None,
             })
             .map_style(|variable| match variable {
@@ -39,7 +47,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "number" => Some(Ok(module_number.clone())),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -1,8 +1,7 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::julia::JuliaConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current Julia version
 ///
@@ -11,18 +10,20 @@ use crate::utils;
 ///     - Current directory contains a `Manifest.toml` file
 ///     - Current directory contains a file with the `.jl` extension
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("julia");
+    let config = JuliaConfig::try_load(module.config);
+
     let is_julia_project = context
-        .try_begin_scan()?
-        .set_files(&["Project.toml", "Manifest.toml"])
-        .set_extensions(&["jl"])
+        .try_begin_module_scan("julia")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_julia_project {
         return None;
     }
 
-    let mut module = context.new_module("julia");
-    let config = JuliaConfig::try_load(module.config);
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -34,13 +35,19 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    format_julia_version(&utils::exec_cmd("julia", &["--version"])?.stdout.as_str())
-                        .map(Ok)
-                }
+                "version" => format_julia_version(
+                    &context.exec_cmd("julia", &["--version"])?.stdout.as_str(),
+                )
+                .map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    ))
+                }),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -66,7 +73,7 @@ fn format_julia_version(julia_stdout: &str) -> Option<String> {
         .split_whitespace()
         .next()?;
 
-    Some(format!("v{}", version))
+    Some(version.to_string())
 }
 
 #[cfg(test)]
@@ -127,6 +134,6 @@ mod tests {
     #[test]
     fn test_format_julia_version() {
         let input = "julia version 1.4.0";
-        assert_eq!(format_julia_version(input), Some("v1.4.0".to_string()));
+        assert_eq!(format_julia_version(input), Some("1.4.0".to_string()));
     }
 }
@@ -1,3 +1,4 @@
+use regex::Regex;
 use yaml_rust::YamlLoader;
 
 use std::env;
@@ -5,11 +6,21 @@ use std::path;
 
 use super::{Context, Module, RootModuleConfig};
 
+use crate::config::OrderedMap;
 use crate::configs::kubernetes::KubernetesConfig;
 use crate::formatter::StringFormatter;
 use crate::utils;
 
-fn get_kube_context(contents: &str) -> Option<(String, String)> {
+/// The current context of a kubeconfig file, and the fields of its `context` entry
+#[derive(Debug, PartialEq)]
+struct KubeCtx {
+    name: String,
+    namespace: String,
+    user: String,
+    cluster: String,
+}
+
+fn get_kube_context(contents: &str) -> Option<KubeCtx> {
     let yaml_docs = YamlLoader::load_from_str(&contents).ok()?;
     if yaml_docs.is_empty() {
         return None;
@@ -22,21 +33,54 @@ fn get_kube_context(contents: &str) -> Option<(String, String)> {
         return None;
     }
 
-    let ns = conf["contexts"]
-        .as_vec()
-        .and_then(|contexts| {
-            contexts
-                .iter()
-                .filter_map(|ctx| Some((ctx, ctx["name"].as_str()?)))
-                .find(|(_, name)| *name == current_ctx)
-                .and_then(|(ctx, _)| ctx["context"]["namespace"].as_str())
-        })
-        .unwrap_or("");
+    let ctx = conf["contexts"].as_vec().and_then(|contexts| {
+        contexts
+            .iter()
+            .find(|ctx| ctx["name"].as_str() == Some(current_ctx))
+    });
+    let get_field = |field: &str| {
+        ctx.and_then(|ctx| ctx["context"][field].as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+
+    Some(KubeCtx {
+        name: current_ctx.to_string(),
+        namespace: get_field("namespace"),
+        user: get_field("user"),
+        cluster: get_field("cluster"),
+    })
+}
+
+/// Rewrite the name of a context with the first alias matching it, in the order of the
+/// configuration. Aliases are either the exact name of a context, or a regular expression
+/// matching the whole name whose capture groups can be used in the alias, e.g. `$cluster`
+/// or `${1}`
+fn get_aliased_context(context_aliases: &OrderedMap<&str>, kube_ctx: &str) -> String {
+    if let Some((_, alias)) = context_aliases.0.iter().find(|(name, _)| name == kube_ctx) {
+        return (*alias).to_string();
+    }
 
-    Some((current_ctx.to_string(), ns.to_string()))
+    context_aliases
+        .0
+        .iter()
+        .find_map(|(pattern, alias)| {
+            let re = match Regex::new(&format!("^(?:{})$", pattern)) {
+                Ok(re) => re,
+                Err(error) => {
+                    log::warn!("Invalid context alias `{}`:\n{}", pattern, error);
+                    return None;
+                }
+            };
+            if !re.is_match(kube_ctx) {
+                return None;
+            }
+            Some(re.replace(kube_ctx, *alias).into_owned())
+        })
+        .unwrap_or_else(|| kube_ctx.to_string())
 }
 
-fn parse_kubectl_file(filename: &path::PathBuf) -> Option<(String, String)> {
+fn parse_kubectl_file(filename: &path::PathBuf) -> Option<KubeCtx> {
     let contents = utils::read_file(filename).ok()?;
     get_kube_context(&contents)
 }
@@ -54,7 +98,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     match kube_cfg {
         Some(kube_cfg) => {
-            let (kube_ctx, kube_ns) = kube_cfg;
+            let KubeCtx {
+                name: kube_ctx,
+                namespace: kube_ns,
+                user: kube_user,
+                cluster: kube_cluster,
+            } = kube_cfg;
 
             let mut module = context.new_module("kubernetes");
             let config: KubernetesConfig = KubernetesConfig::try_load(module.config);
@@ -62,6 +111,24 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 return None;
             };
 
+            // When detection criteria are configured, only show the context in
+            // directories matching them
+            let is_detect_configured = !config.detect_files.is_empty()
+                || !config.detect_folders.is_empty()
+                || !config.detect_extensions.is_empty();
+            if is_detect_configured
+                && !context
+                    .try_begin_module_scan("kubernetes")?
+                    .set_files(&config.detect_files)
+                    .set_folders(&config.detect_folders)
+                    .set_extensions(&config.detect_extensions)
+                    .is_match()
+            {
+                return None;
+            }
+
+            let kube_ctx = get_aliased_context(&config.context_aliases, &kube_ctx);
+
             let parsed = StringFormatter::new(config.format).and_then(|formatter| {
                 formatter
                     .map_meta(|variable, _| match variable {
@@ -73,10 +140,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                         _ => None,
                     })
                     .map(|variable| match variable {
-                        "context" => match config.context_aliases.get(&kube_ctx) {
-                            None => Some(Ok(kube_ctx.as_str())),
-                            Some(&alias) => Some(Ok(alias)),
-                        },
+                        "context" => Some(Ok(kube_ctx.as_str())),
                         _ => None,
                     })
                     .map(|variable| match variable {
@@ -87,9 +151,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                                 None
                             }
                         }
+                        "user" if !kube_user.is_empty() => Some(Ok(kube_user.as_str())),
+                        "cluster" if !kube_cluster.is_empty() => Some(Ok(kube_cluster.as_str())),
                         _ => None,
                     })
-                    .parse(None)
+                    .parse(None, Some(context))
             });
 
             module.set_segments(match parsed {
@@ -152,7 +218,12 @@ preferences: {}
 users: []
 "#;
         let result = get_kube_context(&input);
-        let expected = Some(("test_context".to_string(), "".to_string()));
+        let expected = Some(KubeCtx {
+            name: "test_context".to_string(),
+            namespace: "".to_string(),
+            user: "test_user".to_string(),
+            cluster: "test_cluster".to_string(),
+        });
 
         assert_eq!(result, expected);
     }
@@ -174,7 +245,12 @@ preferences: {}
 users: []
 "#;
         let result = get_kube_context(&input);
-        let expected = Some(("test_context".to_string(), "test_namespace".to_string()));
+        let expected = Some(KubeCtx {
+            name: "test_context".to_string(),
+            namespace: "test_namespace".to_string(),
+            user: "test_user".to_string(),
+            cluster: "test_cluster".to_string(),
+        });
 
         assert_eq!(result, expected);
     }
@@ -201,11 +277,80 @@ preferences: {}
 users: []
 "#;
         let result = get_kube_context(&input);
-        let expected = Some(("test_context".to_string(), "test_namespace".to_string()));
+        let expected = Some(KubeCtx {
+            name: "test_context".to_string(),
+            namespace: "test_namespace".to_string(),
+            user: "test_user".to_string(),
+            cluster: "test_cluster".to_string(),
+        });
 
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn alias_exact_context() {
+        let aliases = OrderedMap(vec![("dev.local.cluster.k8s".to_string(), "dev")]);
+        assert_eq!(
+            get_aliased_context(&aliases, "dev.local.cluster.k8s"),
+            "dev".to_string()
+        );
+        assert_eq!(
+            get_aliased_context(&aliases, "prod.local.cluster.k8s"),
+            "prod.local.cluster.k8s".to_string()
+        );
+    }
+
+    #[test]
+    fn alias_context_with_capture_groups() {
+        let aliases = OrderedMap(vec![
+            (
+                "arn:aws:eks:[^:]+:[0-9]+:cluster/(?P<cluster>.+)".to_string(),
+                "eks-$cluster",
+            ),
+            ("gke_[^_]+_[^_]+_(.+)".to_string(), "gke-${1}"),
+        ]);
+        assert_eq!(
+            get_aliased_context(
+                &aliases,
+                "arn:aws:eks:us-west-2:123456789012:cluster/production"
+            ),
+            "eks-production".to_string()
+        );
+        assert_eq!(
+            get_aliased_context(&aliases, "gke_my-project_us-central1-a_staging"),
+            "gke-staging".to_string()
+        );
+        assert_eq!(
+            get_aliased_context(&aliases, "minikube"),
+            "minikube".to_string()
+        );
+    }
+
+    #[test]
+    fn alias_context_with_first_matching_pattern() {
+        let aliases = OrderedMap(vec![
+            ("gke_[^_]+_[^_]+_(.+)".to_string(), "gke-${1}"),
+            (".*".to_string(), "other"),
+        ]);
+        assert_eq!(
+            get_aliased_context(&aliases, "gke_my-project_us-central1-a_staging"),
+            "gke-staging".to_string()
+        );
+        assert_eq!(
+            get_aliased_context(&aliases, "minikube"),
+            "other".to_string()
+        );
+
+        let aliases = OrderedMap(vec![
+            (".*".to_string(), "other"),
+            ("gke_[^_]+_[^_]+_(.+)".to_string(), "gke-${1}"),
+        ]);
+        assert_eq!(
+            get_aliased_context(&aliases, "gke_my-project_us-central1-a_staging"),
+            "other".to_string()
+        );
+    }
+
     #[test]
     fn parse_broken_config() {
         let input = r#"
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use super::{Context, Module};
 use crate::segment::Segment;
 
@@ -8,9 +10,10 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("line_break");
 
     module.set_segments(vec![Segment {
-        _name: "line_break".to_string(),
+        _name: Cow::Borrowed("line_break"),
         style: None,
-        value: LINE_ENDING.to_string(),
+        value: Cow::Borrowed(LINE_ENDING),
+        image: None,
     }]);
 
     Some(module)
@@ -21,6 +21,12 @@ fn format_usage_total(usage: u64, total: u64) -> String {
     format!("{}/{}", format_kib(usage), format_kib(total))
 }
 
+/// Whether `$swap` and `$swap_pct` are shown: only while some of the swap is in use,
+/// so that they disappear on systems without swap or with all of it free
+fn is_swap_shown(used_swap_kib: u64, total_swap_kib: u64) -> bool {
+    total_swap_kib > 0 && used_swap_kib > 0
+}
+
 /// Creates a module with system memory usage information
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("memory_usage");
@@ -40,6 +46,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let system = sysinfo::System::new_with_specifics(RefreshKind::new().with_memory());
     let used_memory_kib = system.get_used_memory();
     let total_memory_kib = system.get_total_memory();
+
+    // `/proc/meminfo` may be unreadable (e.g. in some Android sandboxes such as Termux),
+    // in which case there is nothing meaningful to show.
+    if total_memory_kib == 0 {
+        log::debug!("Unable to read total memory, skipping memory_usage module");
+        return None;
+    }
+
     let ram_used = (used_memory_kib as f64 / total_memory_kib as f64) * 100.;
     let ram_pct = format_pct(ram_used, pct_sign);
 
@@ -54,6 +68,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let percent_swap_used = (used_swap_kib as f64 / total_swap_kib as f64) * 100.;
     let swap_pct = format_pct(percent_swap_used, pct_sign);
     let swap = format_usage_total(used_swap_kib, total_swap_kib);
+    let show_swap = is_swap_shown(used_swap_kib, total_swap_kib);
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -68,12 +83,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             .map(|variable| match variable {
                 "ram" => Some(Ok(&ram)),
                 "ram_pct" => Some(Ok(&ram_pct)),
-                // swap only shown if there is swap on the system
-                "swap" if total_swap_kib > 0 => Some(Ok(&swap)),
-                "swap_pct" if total_swap_kib > 0 => Some(Ok(&swap_pct)),
+                "swap" if show_swap => Some(Ok(&swap)),
+                "swap_pct" if show_swap => Some(Ok(&swap_pct)),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -86,3 +100,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     Some(module)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_shown_only_while_in_use() {
+        assert!(is_swap_shown(1024, 2048));
+        assert!(is_swap_shown(2048, 2048));
+        assert!(!is_swap_shown(0, 2048));
+        assert!(!is_swap_shown(0, 0));
+    }
+}
@@ -0,0 +1,165 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::meson::MesonConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+/// Creates a module with the current Meson project and Meson version
+///
+/// Will display the Meson project and version if any of the following criteria are met:
+///     - Current directory contains a `meson.build` file
+///     - Current directory contains a `meson_options.txt` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("meson");
+    let config: MesonConfig = MesonConfig::try_load(module.config);
+
+    let is_meson_project = context
+        .try_begin_module_scan("meson")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_meson_project {
+        return None;
+    }
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "project" => utils::read_file(context.current_dir.join("meson.build"))
+                    .ok()
+                    .and_then(|meson_build| parse_project_name(&meson_build))
+                    .map(Ok),
+                "version" => context
+                    .exec_cmd("meson", &["--version"])
+                    .and_then(|output| parse_meson_version(&output.stdout))
+                    .map(|version| {
+                        Ok(VersionFormatter::format_module_version(
+                            module.get_name(),
+                            &version,
+                            config.version_format,
+                        ))
+                    }),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `meson`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Find the name given to the `project()` call of a `meson.build` file
+fn parse_project_name(meson_build: &str) -> Option<String> {
+    let code = meson_build
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    // Skip calls like `subproject()` which only end with `project`
+    let (start, _) = code.match_indices("project").find(|(index, _)| {
+        !matches!(
+            code[..*index].chars().next_back(),
+            Some(c) if c.is_alphanumeric() || c == '_'
+        )
+    })?;
+    let arguments = code[start + "project".len()..]
+        .trim_start()
+        .strip_prefix('(')?
+        .trim_start();
+
+    let name = arguments.strip_prefix('\'')?;
+    let end = name.find('\'')?;
+    Some(name[..end].to_string())
+}
+
+fn parse_meson_version(meson_version: &str) -> Option<String> {
+    let version = meson_version.trim();
+    if version.is_empty() {
+        return None;
+    }
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_meson_version, parse_project_name};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_parse_project_name() {
+        let meson_build = "\
+# project('commented')
+subproject('zlib')
+project(
+  'starship', 'c',
+  version : '0.44.0',
+)
+";
+        assert_eq!(
+            parse_project_name(meson_build),
+            Some("starship".to_string())
+        );
+        assert_eq!(parse_project_name("executable('demo', 'main.c')"), None);
+    }
+
+    #[test]
+    fn test_parse_meson_version() {
+        assert_eq!(parse_meson_version("0.55.3\n"), Some("0.55.3".to_string()));
+        assert_eq!(parse_meson_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_meson_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("meson", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_meson_build() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("meson.build"))?;
+        file.write_all(b"project('demo', 'c')\n")?;
+        file.sync_all()?;
+        let actual = render_module("meson", dir.path(), None);
+        let expected = Some(format!(
+            "via {}",
+            Color::Blue.bold().paint("⬢ demo v0.55.3 ")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_meson_options() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("meson_options.txt"))?.sync_all()?;
+        let actual = render_module("meson", dir.path(), None);
+        let expected = Some(format!("via {}", Color::Blue.bold().paint("⬢ v0.55.3 ")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -1,23 +1,41 @@
 // While adding out new module add out module to src/module.rs ALL_MODULES const array also.
+mod android;
 mod aws;
+mod azure;
+mod bazel;
+mod buf;
 mod character;
 mod cmake;
 mod cmd_duration;
+mod cobol;
 mod conda;
+mod container;
 mod crystal;
 pub(crate) mod custom;
+mod daml;
+mod dart;
 mod directory;
+mod direnv;
 mod docker_context;
 mod dotnet;
 mod elixir;
 mod elm;
-mod env_var;
+pub(crate) mod env_var;
 mod erlang;
+mod fennel;
+mod flutter;
+mod fortran;
+mod gcloud;
 mod git_branch;
 mod git_commit;
 mod git_state;
 mod git_status;
+mod gleam;
 mod golang;
+mod gradle;
+mod guix_shell;
+mod haskell;
+mod haxe;
 mod hg_branch;
 mod hostname;
 mod java;
@@ -26,21 +44,33 @@ mod julia;
 mod kubernetes;
 mod line_break;
 mod memory_usage;
+mod meson;
+mod mojo;
 mod nim;
 mod nix_shell;
 mod nodejs;
 mod ocaml;
+mod openstack;
 mod package;
 mod php;
+mod project;
 mod purescript;
 mod python;
+mod raku;
+mod red;
+mod rlang;
 mod ruby;
 mod rust;
 mod singularity;
+mod solidity;
+mod spack;
 mod terraform;
 mod time;
+mod typst;
 mod username;
 mod utils;
+mod vagrant;
+mod vlang;
 mod zig;
 
 #[cfg(feature = "battery")]
@@ -54,25 +84,43 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
     match module {
         // Keep these ordered alphabetically.
         // Default ordering is handled in configs/mod.rs
+        "android" => android::module(context),
         "aws" => aws::module(context),
+        "azure" => azure::module(context),
         #[cfg(feature = "battery")]
         "battery" => battery::module(context),
+        "bazel" => bazel::module(context),
+        "buf" => buf::module(context),
         "character" => character::module(context),
         "cmake" => cmake::module(context),
         "cmd_duration" => cmd_duration::module(context),
+        "cobol" => cobol::module(context),
         "conda" => conda::module(context),
+        "container" => container::module(context),
+        "daml" => daml::module(context),
+        "dart" => dart::module(context),
         "directory" => directory::module(context),
+        "direnv" => direnv::module(context),
         "docker_context" => docker_context::module(context),
         "dotnet" => dotnet::module(context),
         "elixir" => elixir::module(context),
         "elm" => elm::module(context),
         "erlang" => erlang::module(context),
         "env_var" => env_var::module(context),
+        "fennel" => fennel::module(context),
+        "flutter" => flutter::module(context),
+        "fortran" => fortran::module(context),
+        "gleam" => gleam::module(context),
+        "gcloud" => gcloud::module(context),
         "git_branch" => git_branch::module(context),
         "git_commit" => git_commit::module(context),
         "git_state" => git_state::module(context),
         "git_status" => git_status::module(context),
         "golang" => golang::module(context),
+        "gradle" => gradle::module(context),
+        "guix_shell" => guix_shell::module(context),
+        "haskell" => haskell::module(context),
+        "haxe" => haxe::module(context),
         "hg_branch" => hg_branch::module(context),
         "hostname" => hostname::module(context),
         "java" => java::module(context),
@@ -81,21 +129,33 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
         "kubernetes" => kubernetes::module(context),
         "line_break" => line_break::module(context),
         "memory_usage" => memory_usage::module(context),
+        "meson" => meson::module(context),
+        "mojo" => mojo::module(context),
         "nim" => nim::module(context),
         "nix_shell" => nix_shell::module(context),
         "nodejs" => nodejs::module(context),
         "ocaml" => ocaml::module(context),
+        "openstack" => openstack::module(context),
         "package" => package::module(context),
         "php" => php::module(context),
+        "project" => project::module(context),
         "purescript" => purescript::module(context),
         "python" => python::module(context),
+        "raku" => raku::module(context),
+        "red" => red::module(context),
+        "rlang" => rlang::module(context),
         "ruby" => ruby::module(context),
         "rust" => rust::module(context),
         "singularity" => singularity::module(context),
+        "solidity" => solidity::module(context),
+        "spack" => spack::module(context),
         "terraform" => terraform::module(context),
         "time" => time::module(context),
         "crystal" => crystal::module(context),
+        "typst" => typst::module(context),
         "username" => username::module(context),
+        "vagrant" => vagrant::module(context),
+        "vlang" => vlang::module(context),
         "zig" => zig::module(context),
         _ => {
             eprintln!("Error: Unknown module {}. Use starship module --list to list out all supported modules.", module);
@@ -106,25 +166,43 @@ pub fn handle<'a>(module: &str, context: &'a Context) -> Option<Module<'a>> {
 
 pub fn description(module: &str) -> &'static str {
     match module {
+        "android" => "The targeted Android SDK and NDK versions",
         "aws" => "The current AWS region and profile",
+        "azure" => "The current Azure subscription",
         "battery" => "The current charge of the device's battery and its current charging status",
+        "bazel" => "The Bazel version pinned by the current workspace",
+        "buf" => "The currently installed version of the buf CLI",
         "character" => {
             "A character (usually an arrow) beside where the text is entered in your terminal"
         }
         "cmake" => "The currently installed version of CMake",
         "cmd_duration" => "How long the last command took to execute",
+        "cobol" => "The currently installed version of COBOL / GnuCOBOL",
         "conda" => "The current conda environment, if $CONDA_DEFAULT_ENV is set",
+        "container" => "The container the shell is running in",
         "crystal" => "The currently installed version of Crystal",
+        "daml" => "The Daml SDK version of the current project",
+        "dart" => "The currently installed version of Dart",
         "directory" => "The current working directory",
+        "direnv" => "The state of the direnv .envrc of the current directory tree",
         "docker_context" => "The current docker context",
         "dotnet" => "The relevant version of the .NET Core SDK for the current directory",
         "env_var" => "Displays the current value of a selected environment variable",
         "erlang" => "Current OTP version",
+        "fennel" => "The currently installed version of Fennel",
+        "flutter" => "The current Flutter SDK version and channel",
+        "fortran" => "The currently installed version of the Fortran compiler",
+        "gleam" => "The currently installed version of Gleam",
+        "gcloud" => "The current GCP client configuration",
         "git_branch" => "The active branch of the repo in your current directory",
         "git_commit" => "The active commit of the repo in your current directory",
         "git_state" => "The current git operation, and it's progress",
         "git_status" => "Symbol representing the state of the repo",
         "golang" => "The currently installed version of Golang",
+        "gradle" => "The Gradle version pinned by the Gradle wrapper",
+        "guix_shell" => "The guix-shell environment",
+        "haskell" => "The current Haskell GHC version and build tool",
+        "haxe" => "The currently installed version of Haxe",
         "hg_branch" => "The active branch of the repo in your current directory",
         "hostname" => "The system hostname",
         "java" => "The currently installed version of Java",
@@ -133,19 +211,31 @@ pub fn description(module: &str) -> &'static str {
         "kubernetes" => "The current Kubernetes context name and, if set, the namespace",
         "line_break" => "Separates the prompt into two lines",
         "memory_usage" => "Current system memory and swap usage",
+        "meson" => "The current Meson project and the installed version of Meson",
+        "mojo" => "The currently installed version of Mojo",
         "nim" => "The currently installed version of Nim",
         "nix_shell" => "The nix-shell environment",
         "nodejs" => "The currently installed version of NodeJS",
         "ocaml" => "The currently installed version of OCaml",
+        "openstack" => "The current OpenStack cloud and project",
         "package" => "The package version of the current directory's project",
         "php" => "The currently installed version of PHP",
+        "project" => "The name of the project the current directory belongs to",
         "purescript" => "The currently installed version of PureScript",
         "python" => "The currently installed version of Python",
+        "raku" => "The currently installed version of Raku",
+        "red" => "The currently installed version of Red",
+        "rlang" => "The currently installed version of R",
         "ruby" => "The currently installed version of Ruby",
         "rust" => "The currently installed version of Rust",
+        "solidity" => "The version of the Solidity compiler",
+        "spack" => "The current spack environment, if $SPACK_ENV is set",
         "terraform" => "The currently selected terraform workspace and version",
         "time" => "The current local time",
+        "typst" => "The current typst compiler version",
         "username" => "The active user's username",
+        "vagrant" => "The currently installed version of Vagrant",
+        "vlang" => "The currently installed version of V",
         "zig" => "The currently installed version of Zig",
         _ => "<no description>",
     }
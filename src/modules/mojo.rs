@@ -0,0 +1,121 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::mojo::MojoConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Mojo version
+///
+/// Will display the Mojo version if any of the following criteria are met:
+///     - Current directory contains a file with `.mojo` or `.🔥` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("mojo");
+    let config: MojoConfig = MojoConfig::try_load(module.config);
+
+    let is_mojo_project = context
+        .try_begin_module_scan("mojo")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_mojo_project {
+        return None;
+    }
+
+    let mojo_version = context.exec_cmd("mojo", &["--version"])?.stdout;
+    let module_version = parse_mojo_version(&mojo_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `mojo`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_mojo_version(mojo_version: &str) -> Option<String> {
+    let version = mojo_version
+        // split into ["mojo", "0.3.0", "(f64f9601)"]
+        .split_whitespace()
+        // return "0.3.0"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_mojo_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_mojo_version() {
+        assert_eq!(
+            parse_mojo_version("mojo 0.3.0 (f64f9601)\n"),
+            Some("0.3.0".to_string())
+        );
+        assert_eq!(parse_mojo_version("mojo"), None);
+    }
+
+    #[test]
+    fn folder_without_mojo_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("mojo", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_mojo_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.mojo"))?.sync_all()?;
+        let actual = render_module("mojo", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(208).bold().paint("🔥 v0.3.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_fire_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("main.🔥"))?.sync_all()?;
+        let actual = render_module("mojo", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(208).bold().paint("🔥 v0.3.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
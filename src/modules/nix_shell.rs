@@ -10,24 +10,30 @@ use crate::formatter::StringFormatter;
 /// The module will use the `$IN_NIX_SHELL` and `$name` environment variable to
 /// determine if it's inside a nix-shell and the name of it.
 ///
+/// `nix shell` and `nix develop` from flakes don't set `$IN_NIX_SHELL`, so these
+/// sessions are told apart by a `$PATH` pointing into the nix store instead.
+///
 /// The following options are availables:
-///     - impure_msg (string) // change the impure msg
-///     - pure_msg (string)   // change the pure msg
+///     - impure_msg (string)  // change the impure msg
+///     - pure_msg (string)    // change the pure msg
+///     - unknown_msg (string) // change the msg of `nix shell` sessions
 ///
 /// Will display the following:
 ///     - pure (name)    // $name == "name" in a pure nix-shell
 ///     - impure (name)  // $name == "name" in an impure nix-shell
 ///     - pure           // $name == "" in a pure nix-shell
 ///     - impure         // $name == "" in an impure nix-shell
+///     -                // in a `nix shell` session
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("nix_shell");
     let config: NixShellConfig = NixShellConfig::try_load(module.config);
 
     let shell_name = env::var("name").ok();
-    let shell_type = env::var("IN_NIX_SHELL").ok()?;
-    let shell_type_format = match shell_type.as_ref() {
-        "impure" => config.impure_msg,
-        "pure" => config.pure_msg,
+    let shell_type = env::var("IN_NIX_SHELL").ok();
+    let shell_type_format = match shell_type.as_deref() {
+        Some("impure") => config.impure_msg,
+        Some("pure") => config.pure_msg,
+        None if is_path_in_nix_store() => config.unknown_msg,
         _ => {
             return None;
         }
@@ -48,7 +54,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "name" => shell_name.as_ref().map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -61,3 +67,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
 
     Some(module)
 }
+
+/// Whether `$PATH` contains a directory of the nix store, as `nix shell` and
+/// `nix develop` prepend the `bin` directories of the packages they provide
+fn is_path_in_nix_store() -> bool {
+    match env::var_os("PATH") {
+        Some(paths) => env::split_paths(&paths).any(|path| path.starts_with("/nix/store")),
+        None => false,
+    }
+}
@@ -1,9 +1,12 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::nodejs::NodejsConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils;
 
+use semver::{Compat, Version, VersionReq};
+use serde_json as json;
+
 /// Creates a module with the current Node.js version
 ///
 /// Will display the Node.js version if any of the following criteria are met:
@@ -12,11 +15,14 @@ use crate::utils;
 ///     - Current directory contains a `package.json` or `.node-version` file
 ///     - Current directory contains a `node_modules` directory
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("nodejs");
+    let config = NodejsConfig::try_load(module.config);
+
     let is_js_project = context
-        .try_begin_scan()?
-        .set_files(&["package.json", ".node-version"])
-        .set_extensions(&["js", "mjs", "cjs", "ts"])
-        .set_folders(&["node_modules"])
+        .try_begin_module_scan("nodejs")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     let is_esy_project = context
@@ -28,9 +34,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     }
 
-    let mut module = context.new_module("nodejs");
-    let config = NodejsConfig::try_load(module.config);
-    let nodejs_version = utils::exec_cmd("node", &["--version"])?.stdout;
+    let nodejs_version = match context.get_pinned_version("nodejs", &["nodejs", "node"]) {
+        Some(version) => version.to_string(),
+        None => context.exec_cmd("node", &["--version"])?.stdout,
+    };
+    let engines_version = get_engines_version(context);
+    let in_engines_range = check_engines_version(&nodejs_version, engines_version.as_deref());
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -38,14 +48,20 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" if in_engines_range => Some(Ok(config.style)),
+                "style" => Some(Ok(config.not_capable_style)),
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(nodejs_version.trim())),
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &nodejs_version,
+                    config.version_format,
+                ))),
+                "engines_version" => engines_version.clone().map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -59,13 +75,58 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Reads the Node.js version range of the project from `engines.node` in `package.json`
+fn get_engines_version(context: &Context) -> Option<String> {
+    let package_json = utils::read_file(context.current_dir.join("package.json")).ok()?;
+    let package_json: json::Value = json::from_str(&package_json).ok()?;
+    let engines_version = package_json.get("engines")?.get("node")?.as_str()?;
+    Some(engines_version.to_string())
+}
+
+/// Whether the Node.js version satisfies the version range of the project. Versions or
+/// ranges which can't be parsed are assumed to match.
+fn check_engines_version(nodejs_version: &str, engines_version: Option<&str>) -> bool {
+    let engines_version = match engines_version {
+        Some(engines_version) => engines_version,
+        None => return true,
+    };
+    let range = match VersionReq::parse_compat(engines_version, Compat::Npm) {
+        Ok(range) => range,
+        Err(error) => {
+            log::debug!("Invalid `engines.node` {:?}: {}", engines_version, error);
+            return true;
+        }
+    };
+    let nodejs_version = nodejs_version.trim();
+    match Version::parse(nodejs_version.strip_prefix('v').unwrap_or(nodejs_version)) {
+        Ok(version) => range.matches(&version),
+        Err(_) => true,
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::modules::utils::test::render_module;
     use ansi_term::Color;
     use std::fs::{self, File};
     use std::io;
 
+    #[test]
+    fn test_check_engines_version() {
+        assert!(check_engines_version("v12.0.0\n", None));
+        assert!(check_engines_version("v12.0.0", Some(">=12")));
+        assert!(check_engines_version(
+            "v12.18.3",
+            Some("^12.13.0 || ^14.15.0")
+        ));
+        assert!(check_engines_version("v14.15.1", Some("12.x || 14.x")));
+        assert!(!check_engines_version("v10.0.0", Some(">=12")));
+        assert!(!check_engines_version("v16.0.0", Some("~14.15.0")));
+        // Invalid ranges don't highlight the version
+        assert!(check_engines_version("v12.0.0", Some("not a range")));
+    }
+
     #[test]
     fn folder_without_node_files() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -86,6 +147,40 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn folder_with_tool_versions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        fs::write(dir.path().join(".tool-versions"), "nodejs 14.15.0\n")?;
+
+        let actual = render_module("nodejs", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ v14.15.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_tool_versions_disabled() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        fs::write(
+            dir.path().join("mise.toml"),
+            "[tools]\nnode = \"14.15.0\"\n",
+        )?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                use_tool_versions = false
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_package_json_and_esy_lock() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -110,6 +205,24 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn version_formatting() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                version_format = "${major}.${minor}"
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ 12.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_js_file() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -165,4 +278,142 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn folder_with_custom_detect_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("deno.json"))?.sync_all()?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                detect_files = ["deno.json"]
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn engines_node_version_match() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"engines": {"node": ">=12.0.0 <13"}}"#,
+        )?;
+
+        let actual = render_module("nodejs", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn engines_node_version_not_match() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"engines": {"node": "^10.13.0 || >=14"}}"#,
+        )?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                format = "via [$symbol$version \\($engines_version\\)]($style) "
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Red.bold().paint("⬢ v12.0.0 (^10.13.0 || >=14)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_excluded_deno_json() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        File::create(dir.path().join("deno.json"))?.sync_all()?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                detect_files = ["package.json", "!deno.json"]
+            }),
+        );
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_js_file_without_detect_extensions() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("index.js"))?.sync_all()?;
+
+        let actual = render_module(
+            "nodejs",
+            dir.path(),
+            Some(toml::toml! {
+                [nodejs]
+                detect_extensions = []
+            }),
+        );
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn repo_root_with_package_json() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        git2::Repository::init(dir.path()).unwrap();
+        File::create(dir.path().join("package.json"))?.sync_all()?;
+        let current_dir = dir.path().join("src/components");
+        fs::create_dir_all(&current_dir)?;
+
+        let actual = render_module("nodejs", &current_dir, None);
+        assert_eq!(None, actual);
+
+        let expected = Some(format!("via {} ", Color::Green.bold().paint("⬢ v12.0.0")));
+        let actual = render_module(
+            "nodejs",
+            &current_dir,
+            Some(toml::toml! {
+                [nodejs]
+                scan_repo_root = true
+            }),
+        );
+        assert_eq!(expected, actual);
+
+        let actual = render_module(
+            "nodejs",
+            &current_dir,
+            Some(toml::toml! {
+                scan_repo_root = true
+            }),
+        );
+        assert_eq!(expected, actual);
+
+        let actual = render_module(
+            "nodejs",
+            &current_dir,
+            Some(toml::toml! {
+                scan_repo_root = true
+
+                [nodejs]
+                scan_repo_root = false
+            }),
+        );
+        assert_eq!(None, actual);
+        dir.close()
+    }
 }
@@ -1,8 +1,9 @@
+use std::path::Path;
+
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::ocaml::OCamlConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current OCaml version
 ///
@@ -13,12 +14,17 @@ use crate::utils;
 ///     - Current directory contains a `jbuild` or `jbuild-ignore` file
 ///     - Current directory contains a `.merlin` file
 ///     - Current directory contains a file with `.ml`, `.mli`, `.re` or `.rei` extension
+///
+/// The active opam switch is shown as well, unless the project is managed by esy
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("ocaml");
+    let config: OCamlConfig = OCamlConfig::try_load(module.config);
+
     let is_ocaml_project = context
-        .try_begin_scan()?
-        .set_files(&["dune", "dune-project", "jbuild", "jbuild-ignore", ".merlin"])
-        .set_folders(&["_opam", "esy.lock"])
-        .set_extensions(&["opam", "ml", "mli", "re", "rei"])
+        .try_begin_module_scan("ocaml")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_ocaml_project {
@@ -31,14 +37,11 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         .is_match();
 
     let ocaml_version = if is_esy_project {
-        utils::exec_cmd("esy", &["ocaml", "-vnum"])?.stdout
+        context.exec_cmd("esy", &["ocaml", "-vnum"])?.stdout
     } else {
-        utils::exec_cmd("ocaml", &["-vnum"])?.stdout
+        context.exec_cmd("ocaml", &["-vnum"])?.stdout
     };
 
-    let mut module = context.new_module("ocaml");
-    let config: OCamlConfig = OCamlConfig::try_load(module.config);
-
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -50,10 +53,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(format!("v{}", &ocaml_version))),
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &ocaml_version,
+                    config.version_format,
+                ))),
+                "switch_name" if !is_esy_project => get_opam_switch(context).map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -67,13 +75,49 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_opam_switch(context: &Context) -> Option<String> {
+    let opam_switch = context
+        .exec_cmd("opam", &["switch", "show", "--safe"])?
+        .stdout;
+    parse_opam_switch(opam_switch.trim())
+}
+
+fn parse_opam_switch(opam_switch: &str) -> Option<String> {
+    if opam_switch.is_empty() {
+        return None;
+    }
+
+    // Local switches are named after the path of the project they live in,
+    // so only keep the name of the project directory
+    let path = Path::new(opam_switch);
+    if path.is_absolute() {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    } else {
+        Some(opam_switch.to_string())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::parse_opam_switch;
     use crate::modules::utils::test::render_module;
     use ansi_term::Color;
     use std::fs::{self, File};
     use std::io;
 
+    #[test]
+    fn test_parse_opam_switch() {
+        assert_eq!(parse_opam_switch("default"), Some("default".to_string()));
+        assert_eq!(parse_opam_switch("4.10.0"), Some("4.10.0".to_string()));
+        assert_eq!(parse_opam_switch(""), None);
+        #[cfg(not(windows))]
+        assert_eq!(
+            parse_opam_switch("/home/user/projects/my-project"),
+            Some("my-project".to_string())
+        );
+    }
+
     #[test]
     fn folder_without_ocaml_file() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -89,7 +133,10 @@ mod tests {
         File::create(dir.path().join("any.opam"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -100,7 +147,10 @@ mod tests {
         fs::create_dir_all(dir.path().join("_opam"))?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -126,7 +176,10 @@ mod tests {
         File::create(dir.path().join("dune"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -137,7 +190,10 @@ mod tests {
         File::create(dir.path().join("dune-project"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -148,7 +204,10 @@ mod tests {
         File::create(dir.path().join("jbuild"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -159,7 +218,10 @@ mod tests {
         File::create(dir.path().join("jbuild-ignore"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -170,7 +232,10 @@ mod tests {
         File::create(dir.path().join(".merlin"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -181,7 +246,10 @@ mod tests {
         File::create(dir.path().join("any.ml"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -192,7 +260,10 @@ mod tests {
         File::create(dir.path().join("any.mli"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -203,6 +274,27 @@ mod tests {
         File::create(dir.path().join("any.re"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_ml_file_without_switch_name() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("any.ml"))?.sync_all()?;
+
+        let actual = render_module(
+            "ocaml",
+            dir.path(),
+            Some(toml::toml! {
+                [ocaml]
+                format = "via [$symbol$version]($style) "
+            }),
+        );
         let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
         assert_eq!(expected, actual);
         dir.close()
@@ -214,7 +306,10 @@ mod tests {
         File::create(dir.path().join("any.rei"))?.sync_all()?;
 
         let actual = render_module("ocaml", dir.path(), None);
-        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐫 v4.10.0")));
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐫 v4.10.0 (default)")
+        ));
         assert_eq!(expected, actual);
         dir.close()
     }
@@ -0,0 +1,109 @@
+use std::env;
+use std::path::PathBuf;
+use yaml_rust::YamlLoader;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::openstack::OpenStackConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// The places `clouds.yaml` is looked up in, in the order the OpenStack client uses
+fn get_clouds_yaml_paths(context: &Context) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Ok(path) = env::var("OS_CLIENT_CONFIG_FILE") {
+        paths.push(PathBuf::from(path));
+    }
+    paths.push(context.current_dir.join("clouds.yaml"));
+    if let Some(home_dir) = dirs_next::home_dir() {
+        paths.push(home_dir.join(".config/openstack/clouds.yaml"));
+    }
+    paths.push(PathBuf::from("/etc/openstack/clouds.yaml"));
+    paths
+}
+
+/// Find the project of `cloud` in the contents of a `clouds.yaml` file
+fn parse_cloud_project(contents: &str, cloud: &str) -> Option<String> {
+    let clouds = YamlLoader::load_from_str(contents).ok()?;
+    let auth = &clouds.first()?["clouds"][cloud]["auth"];
+    auth["project_name"]
+        .as_str()
+        .or_else(|| auth["project_id"].as_str())
+        .map(str::to_string)
+}
+
+/// Creates a module with the current OpenStack cloud and project
+///
+/// Will display the cloud selected with `$OS_CLOUD`, and the project it authenticates to
+/// in the first `clouds.yaml` defining that cloud.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("openstack");
+    let config: OpenStackConfig = OpenStackConfig::try_load(module.config);
+
+    let cloud = env::var("OS_CLOUD")
+        .ok()
+        .filter(|cloud| !cloud.is_empty())?;
+    let project = get_clouds_yaml_paths(context)
+        .iter()
+        .filter_map(|path| utils::read_file(path).ok())
+        .find_map(|contents| parse_cloud_project(&contents, &cloud));
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "cloud" => Some(Ok(&cloud)),
+                "project" => project.as_ref().map(Ok),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `openstack`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLOUDS_YAML: &str = "\
+clouds:
+  corp:
+    region_name: RegionOne
+    auth:
+      auth_url: https://identity.example.com:5000/v3
+      username: alice
+      project_name: infra
+  lab:
+    auth:
+      project_id: 0c2f4ee4d7b14d3b8b3e6f3a47f5a9e2
+";
+
+    #[test]
+    fn project_of_cloud() {
+        assert_eq!(
+            parse_cloud_project(CLOUDS_YAML, "corp"),
+            Some("infra".to_string())
+        );
+        assert_eq!(
+            parse_cloud_project(CLOUDS_YAML, "lab"),
+            Some("0c2f4ee4d7b14d3b8b3e6f3a47f5a9e2".to_string())
+        );
+        assert_eq!(parse_cloud_project(CLOUDS_YAML, "missing"), None);
+    }
+}
@@ -1,20 +1,28 @@
-use std::path::PathBuf;
+use std::path::Path;
 
 use super::{Context, Module, RootModuleConfig};
 use crate::configs::package::PackageConfig;
 use crate::formatter::StringFormatter;
 use crate::utils;
 
+use quick_xml::events::Event;
+use quick_xml::Reader;
 use regex::Regex;
 use serde_json as json;
+use yaml_rust::{Yaml, YamlLoader};
 
 /// Creates a module with the current package version
 ///
-/// Will display if a version is defined for your Node.js or Rust project (if one exists)
+/// Will display if a version is defined in one of the supported package manifests,
+/// looking up to the root of the git repository for the nearest manifest
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let mut module = context.new_module("package");
     let config: PackageConfig = PackageConfig::try_load(module.config);
-    let module_version = get_package_version(&context.current_dir, &config)?;
+    let repo_root = context
+        .get_repo()
+        .ok()
+        .and_then(|repo| repo.root.as_deref());
+    let module_version = get_package_version(&context.current_dir, repo_root, &config)?;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -30,7 +38,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "version" => Some(Ok(&module_version)),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -44,98 +52,339 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn extract_cargo_version(file_contents: &str) -> Option<String> {
-    let cargo_toml: toml::Value = toml::from_str(file_contents).ok()?;
-    let raw_version = cargo_toml.get("package")?.get("version")?.as_str()?;
-
-    let formatted_version = format_version(raw_version);
-    Some(formatted_version)
+/// Reads the version of a package from one kind of manifest file
+trait VersionExtractor {
+    /// Name of the manifest file in the current directory
+    fn file_name(&self) -> &'static str;
+
+    /// Extracts the raw package version from the contents of the manifest found in `dir`
+    fn extract_version(
+        &self,
+        dir: &Path,
+        file_contents: &str,
+        config: &PackageConfig,
+    ) -> Option<String>;
 }
 
-fn extract_package_version(file_contents: &str, display_private: bool) -> Option<String> {
-    let package_json: json::Value = json::from_str(file_contents).ok()?;
+/// The supported manifests, in order of precedence
+const EXTRACTORS: &[&dyn VersionExtractor] = &[
+    &Cargo, &Npm, &Pyproject, &Composer, &Gradle, &Julia, &Mix, &Shards, &Helm, &Maven,
+];
+
+struct Cargo;
 
-    if !display_private && package_json.get("private").and_then(json::Value::as_bool) == Some(true)
-    {
-        return None;
+impl VersionExtractor for Cargo {
+    fn file_name(&self) -> &'static str {
+        "Cargo.toml"
     }
 
-    let raw_version = package_json.get("version")?.as_str()?;
-    if raw_version == "null" {
-        return None;
-    };
+    fn extract_version(
+        &self,
+        dir: &Path,
+        file_contents: &str,
+        config: &PackageConfig,
+    ) -> Option<String> {
+        let cargo_toml: toml::Value = toml::from_str(file_contents).ok()?;
+
+        // A virtual manifest only defines the version shared by the workspace members
+        let package = match cargo_toml.get("package") {
+            Some(package) => package,
+            None => return extract_cargo_workspace_version(&cargo_toml),
+        };
+
+        // `publish = false` and `publish = []` both prevent publishing to any registry
+        let is_private = match package.get("publish") {
+            Some(toml::Value::Boolean(publish)) => !publish,
+            Some(toml::Value::Array(registries)) => registries.is_empty(),
+            _ => false,
+        };
+        if !config.display_private && is_private {
+            return None;
+        }
+
+        let version = package.get("version")?;
+        if version.get("workspace").and_then(toml::Value::as_bool) == Some(true) {
+            return find_cargo_workspace_version(dir);
+        }
 
-    let formatted_version = format_version(raw_version);
-    Some(formatted_version)
+        Some(version.as_str()?.to_string())
+    }
 }
 
-fn extract_poetry_version(file_contents: &str) -> Option<String> {
-    let poetry_toml: toml::Value = toml::from_str(file_contents).ok()?;
-    let raw_version = poetry_toml
-        .get("tool")?
-        .get("poetry")?
+fn extract_cargo_workspace_version(cargo_toml: &toml::Value) -> Option<String> {
+    let raw_version = cargo_toml
+        .get("workspace")?
+        .get("package")?
         .get("version")?
         .as_str()?;
 
-    let formatted_version = format_version(raw_version);
-    Some(formatted_version)
+    Some(raw_version.to_string())
+}
+
+/// Finds the version a workspace member inherits from the nearest workspace root above it
+fn find_cargo_workspace_version(member_dir: &Path) -> Option<String> {
+    member_dir.ancestors().skip(1).find_map(|dir| {
+        let file_contents = utils::read_file(dir.join("Cargo.toml")).ok()?;
+        let cargo_toml: toml::Value = toml::from_str(&file_contents).ok()?;
+        cargo_toml.get("workspace")?;
+        extract_cargo_workspace_version(&cargo_toml)
+    })
+}
+
+struct Npm;
+
+impl VersionExtractor for Npm {
+    fn file_name(&self) -> &'static str {
+        "package.json"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        config: &PackageConfig,
+    ) -> Option<String> {
+        let package_json: json::Value = json::from_str(file_contents).ok()?;
+
+        if !config.display_private
+            && package_json.get("private").and_then(json::Value::as_bool) == Some(true)
+        {
+            return None;
+        }
+
+        let raw_version = package_json.get("version")?.as_str()?;
+        if raw_version == "null" {
+            return None;
+        };
+
+        Some(raw_version.to_string())
+    }
+}
+
+struct Pyproject;
+
+impl VersionExtractor for Pyproject {
+    fn file_name(&self) -> &'static str {
+        "pyproject.toml"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let pyproject_toml: toml::Value = toml::from_str(file_contents).ok()?;
+        // PEP 621 metadata takes precedence over the Poetry specific table. Projects
+        // listing `version` in `dynamic` have no static version in `[project]`, and
+        // the version is only shown if Poetry has one.
+        let raw_version = pyproject_toml
+            .get("project")
+            .and_then(|project| project.get("version"))
+            .or_else(|| pyproject_toml.get("tool")?.get("poetry")?.get("version"))?
+            .as_str()?;
+
+        Some(raw_version.to_string())
+    }
+}
+
+struct Composer;
+
+impl VersionExtractor for Composer {
+    fn file_name(&self) -> &'static str {
+        "composer.json"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let composer_json: json::Value = json::from_str(file_contents).ok()?;
+        let raw_version = composer_json.get("version")?.as_str()?;
+        if raw_version == "null" {
+            return None;
+        };
+
+        Some(raw_version.to_string())
+    }
 }
 
-fn extract_gradle_version(file_contents: &str) -> Option<String> {
-    let re = Regex::new(r#"(?m)^version ['"](?P<version>[^'"]+)['"]$"#).unwrap();
-    let caps = re.captures(file_contents)?;
+struct Gradle;
 
-    let formatted_version = format_version(&caps["version"]);
-    Some(formatted_version)
+impl VersionExtractor for Gradle {
+    fn file_name(&self) -> &'static str {
+        "build.gradle"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let re = Regex::new(r#"(?m)^version ['"](?P<version>[^'"]+)['"]$"#).unwrap();
+        let caps = re.captures(file_contents)?;
+
+        Some(caps["version"].to_string())
+    }
 }
 
-fn extract_composer_version(file_contents: &str) -> Option<String> {
-    let composer_json: json::Value = json::from_str(file_contents).ok()?;
-    let raw_version = composer_json.get("version")?.as_str()?;
-    if raw_version == "null" {
-        return None;
-    };
+struct Julia;
+
+impl VersionExtractor for Julia {
+    fn file_name(&self) -> &'static str {
+        "Project.toml"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let project_toml: toml::Value = toml::from_str(file_contents).ok()?;
+        let raw_version = project_toml.get("version")?.as_str()?;
+
+        Some(raw_version.to_string())
+    }
+}
+
+struct Mix;
+
+impl VersionExtractor for Mix {
+    fn file_name(&self) -> &'static str {
+        "mix.exs"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let re = Regex::new(r#"(?m)version: "(?P<version>[^"]+)""#).unwrap();
+        let caps = re.captures(file_contents)?;
 
-    let formatted_version = format_version(raw_version);
-    Some(formatted_version)
+        Some(caps["version"].to_string())
+    }
 }
 
-fn extract_project_version(file_contents: &str) -> Option<String> {
-    let project_toml: toml::Value = toml::from_str(file_contents).ok()?;
-    let raw_version = project_toml.get("version")?.as_str()?;
+struct Shards;
+
+impl VersionExtractor for Shards {
+    fn file_name(&self) -> &'static str {
+        "shard.yml"
+    }
 
-    let formatted_version = format_version(raw_version);
-    Some(formatted_version)
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        extract_yaml_version(file_contents)
+    }
 }
 
-fn extract_mix_version(file_contents: &str) -> Option<String> {
-    let re = Regex::new(r#"(?m)version: "(?P<version>[^"]+)""#).unwrap();
-    let caps = re.captures(file_contents)?;
+struct Helm;
+
+impl VersionExtractor for Helm {
+    fn file_name(&self) -> &'static str {
+        "Chart.yaml"
+    }
 
-    let formatted_version = format_version(&caps["version"]);
-    Some(formatted_version)
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        extract_yaml_version(file_contents)
+    }
 }
 
-fn get_package_version(base_dir: &PathBuf, config: &PackageConfig) -> Option<String> {
-    if let Ok(cargo_toml) = utils::read_file(base_dir.join("Cargo.toml")) {
-        extract_cargo_version(&cargo_toml)
-    } else if let Ok(package_json) = utils::read_file(base_dir.join("package.json")) {
-        extract_package_version(&package_json, config.display_private)
-    } else if let Ok(poetry_toml) = utils::read_file(base_dir.join("pyproject.toml")) {
-        extract_poetry_version(&poetry_toml)
-    } else if let Ok(composer_json) = utils::read_file(base_dir.join("composer.json")) {
-        extract_composer_version(&composer_json)
-    } else if let Ok(build_gradle) = utils::read_file(base_dir.join("build.gradle")) {
-        extract_gradle_version(&build_gradle)
-    } else if let Ok(project_toml) = utils::read_file(base_dir.join("Project.toml")) {
-        extract_project_version(&project_toml)
-    } else if let Ok(mix_file) = utils::read_file(base_dir.join("mix.exs")) {
-        extract_mix_version(&mix_file)
-    } else {
-        None
+struct Maven;
+
+impl VersionExtractor for Maven {
+    fn file_name(&self) -> &'static str {
+        "pom.xml"
+    }
+
+    fn extract_version(
+        &self,
+        _dir: &Path,
+        file_contents: &str,
+        _config: &PackageConfig,
+    ) -> Option<String> {
+        let mut reader = Reader::from_str(file_contents);
+        reader.trim_text(true);
+
+        // The `version` of the project itself, not the one of its parent or dependencies
+        let mut path: Vec<Vec<u8>> = Vec::new();
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event(&mut buf) {
+                Ok(Event::Start(ref e)) => path.push(e.name().to_vec()),
+                Ok(Event::End(_)) => {
+                    path.pop();
+                }
+                Ok(Event::Text(e)) if path == [&b"project"[..], &b"version"[..]] => {
+                    let raw_version = e.unescape_and_decode(&reader).ok()?;
+                    // Versions set through properties can't be resolved without Maven
+                    if raw_version.contains("${") {
+                        return None;
+                    }
+                    return Some(raw_version);
+                }
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => (),
+            }
+            buf.clear();
+        }
     }
 }
 
+/// Reads the top-level `version` key of a YAML manifest
+fn extract_yaml_version(file_contents: &str) -> Option<String> {
+    let yaml_docs = YamlLoader::load_from_str(file_contents).ok()?;
+
+    // Unquoted versions like `1.0` are parsed as numbers
+    match &yaml_docs.first()?["version"] {
+        Yaml::String(version) | Yaml::Real(version) => Some(version.to_string()),
+        Yaml::Integer(version) => Some(version.to_string()),
+        _ => None,
+    }
+}
+
+fn get_package_version(
+    current_dir: &Path,
+    repo_root: Option<&Path>,
+    config: &PackageConfig,
+) -> Option<String> {
+    // Outside of a repository only the current directory is checked, as manifests
+    // further up are unlikely to describe it
+    let search_dirs = current_dir.ancestors().take_while(|dir| match repo_root {
+        Some(repo_root) => dir.starts_with(repo_root),
+        None => *dir == current_dir,
+    });
+
+    // The nearest manifest decides the version, even if it doesn't define one
+    let raw_version = search_dirs
+        .flat_map(|dir| EXTRACTORS.iter().map(move |extractor| (dir, extractor)))
+        .find_map(|(dir, extractor)| {
+            let file_contents = utils::read_file(dir.join(extractor.file_name())).ok()?;
+            log::debug!(
+                "Found package manifest {:?}",
+                dir.join(extractor.file_name())
+            );
+            Some(extractor.extract_version(dir, &file_contents, config))
+        })??;
+
+    Some(format_version(&raw_version))
+}
+
 fn format_version(version: &str) -> String {
     let cleaned = version.replace('"', "").trim().to_string();
     if cleaned.starts_with('v') {
@@ -150,7 +399,7 @@ mod tests {
     use super::*;
     use crate::modules::utils::test::render_module;
     use ansi_term::Color;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io;
     use std::io::Write;
     use tempfile::TempDir;
@@ -312,6 +561,57 @@ mod tests {
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_pep621_version() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            version = "0.1.0"
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pep621_version_before_poetry_version() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            version = "0.2.0"
+
+            [tool.poetry]
+            version = "0.1.0"
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.2.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_pep621_dynamic_version() -> io::Result<()> {
+        let config_name = "pyproject.toml";
+        let config_content = toml::toml! {
+            [project]
+            name = "starship"
+            dynamic = ["version"]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
     #[test]
     fn test_extract_gradle_version_single_quote() -> io::Result<()> {
         let config_name = "build.gradle";
@@ -521,6 +821,280 @@ end";
         project_dir.close()
     }
 
+    #[test]
+    fn test_extract_shards_version() -> io::Result<()> {
+        let config_name = "shard.yml";
+        let config_content = "name: starship\nversion: 0.1.0\n";
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_shards_version_without_version() -> io::Result<()> {
+        let config_name = "shard.yml";
+        let config_content = "name: starship\n";
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_helm_version() -> io::Result<()> {
+        let config_name = "Chart.yaml";
+        let config_content = "apiVersion: v2\nname: starship\nversion: 0.1.0\nappVersion: 1.16.0\n";
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_helm_version_unquoted_number() -> io::Result<()> {
+        let config_name = "Chart.yaml";
+        let config_content = "apiVersion: v2\nname: starship\nversion: 1.0\n";
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, Some("v1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_maven_version() -> io::Result<()> {
+        let config_name = "pom.xml";
+        let config_content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+    <modelVersion>4.0.0</modelVersion>
+    <parent>
+        <groupId>org.example</groupId>
+        <artifactId>parent</artifactId>
+        <version>1.0.0</version>
+    </parent>
+    <artifactId>starship</artifactId>
+    <version>0.1.0</version>
+    <dependencies>
+        <dependency>
+            <groupId>junit</groupId>
+            <artifactId>junit</artifactId>
+            <version>4.13</version>
+        </dependency>
+    </dependencies>
+</project>
+"#;
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_maven_version_from_property() -> io::Result<()> {
+        let config_name = "pom.xml";
+        let config_content = r#"<project>
+    <artifactId>starship</artifactId>
+    <version>${revision}</version>
+</project>
+"#;
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_maven_version_without_version() -> io::Result<()> {
+        let config_name = "pom.xml";
+        let config_content = r#"<project>
+    <parent>
+        <artifactId>parent</artifactId>
+        <version>1.0.0</version>
+    </parent>
+    <artifactId>starship</artifactId>
+</project>
+"#;
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_first_manifest_takes_precedence() -> io::Result<()> {
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, "Cargo.toml", Some("[workspace]\n"))?;
+        fill_config(
+            &project_dir,
+            "package.json",
+            Some(r#"{"version": "0.1.0"}"#),
+        )?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_private_cargo_version_with_default_config() -> io::Result<()> {
+        let config_name = "Cargo.toml";
+        let config_content = toml::toml! {
+            [package]
+            name = "starship"
+            version = "0.1.0"
+            publish = false
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_private_cargo_version_with_display_private() -> io::Result<()> {
+        let config_name = "Cargo.toml";
+        let config_content = toml::toml! {
+            [package]
+            name = "starship"
+            version = "0.1.0"
+            publish = []
+        }
+        .to_string();
+        let starship_config = toml::toml! {
+            [package]
+            display_private = true
+        };
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), Some(starship_config))?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_cargo_version_published_to_registry() -> io::Result<()> {
+        let config_name = "Cargo.toml";
+        let config_content = toml::toml! {
+            [package]
+            name = "starship"
+            version = "0.1.0"
+            publish = ["internal"]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_cargo_workspace_version() -> io::Result<()> {
+        let config_name = "Cargo.toml";
+        let config_content = toml::toml! {
+            [workspace]
+            members = ["engine"]
+
+            [workspace.package]
+            version = "0.1.0"
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, Some("v0.1.0"), None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_cargo_workspace_version_without_version() -> io::Result<()> {
+        let config_name = "Cargo.toml";
+        let config_content = toml::toml! {
+            [workspace]
+            members = ["engine"]
+        }
+        .to_string();
+
+        let project_dir = create_project_dir()?;
+        fill_config(&project_dir, config_name, Some(&config_content))?;
+        expect_output(&project_dir, None, None)?;
+        project_dir.close()
+    }
+
+    #[test]
+    fn test_extract_cargo_version_inherited_from_workspace() -> io::Result<()> {
+        let workspace_content = toml::toml! {
+            [workspace]
+            members = ["engine"]
+
+            [workspace.package]
+            version = "0.1.0"
+        }
+        .to_string();
+        let member_content = toml::toml! {
+            [package]
+            name = "engine"
+            version = { workspace = true }
+        }
+        .to_string();
+
+        let workspace_dir = create_project_dir()?;
+        let member_dir = workspace_dir.path().join("engine");
+        fs::create_dir(&member_dir)?;
+        fill_config(&workspace_dir, "Cargo.toml", Some(&workspace_content))?;
+        fs::write(member_dir.join("Cargo.toml"), member_content)?;
+
+        let actual = render_module("package", &member_dir, None);
+        let expected = Some(format!(
+            "is {} ",
+            Color::Fixed(208).bold().paint("📦 v0.1.0")
+        ));
+        assert_eq!(actual, expected);
+        workspace_dir.close()
+    }
+
+    #[test]
+    fn test_nearest_manifest_in_repository() -> io::Result<()> {
+        let repo_dir = create_project_dir()?;
+        git2::Repository::init(repo_dir.path()).unwrap();
+        let package_dir = repo_dir.path().join("packages/ui");
+        let current_dir = package_dir.join("src/components");
+        fs::create_dir_all(&current_dir)?;
+        fill_config(&repo_dir, "package.json", Some(r#"{"version": "1.0.0"}"#))?;
+        fs::write(package_dir.join("package.json"), r#"{"version": "0.1.0"}"#)?;
+
+        let actual = render_module("package", &current_dir, None);
+        let expected = Some(format!(
+            "is {} ",
+            Color::Fixed(208).bold().paint("📦 v0.1.0")
+        ));
+        assert_eq!(actual, expected);
+        repo_dir.close()
+    }
+
+    #[test]
+    fn test_manifest_above_current_dir_outside_repository() -> io::Result<()> {
+        let project_dir = create_project_dir()?;
+        let current_dir = project_dir.path().join("src");
+        fs::create_dir(&current_dir)?;
+        fill_config(
+            &project_dir,
+            "package.json",
+            Some(r#"{"version": "0.1.0"}"#),
+        )?;
+
+        let actual = render_module("package", &current_dir, None);
+        assert_eq!(actual, None);
+        project_dir.close()
+    }
+
     fn create_project_dir() -> io::Result<TempDir> {
         Ok(tempfile::tempdir()?)
     }
@@ -0,0 +1,272 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use serde_json as json;
+use std::path::Path;
+
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::project::ProjectConfig;
+use crate::formatter::StringFormatter;
+use crate::utils;
+
+/// Reads the project name from the contents of a project file
+type NameParser = fn(&str) -> Option<String>;
+
+/// The files marking the root of a project, with a parser for the project name.
+/// When a file doesn't name the project, the name of its directory is used.
+const PROJECT_FILES: &[(&str, NameParser)] = &[
+    ("package.json", parse_package_json),
+    ("Cargo.toml", parse_cargo_toml),
+    ("go.mod", parse_go_mod),
+    ("go.work", |_| None),
+    ("pom.xml", parse_pom_xml),
+];
+
+/// Creates a module with the name of the project the current directory belongs to
+///
+/// Will walk up from the current directory, up to the root of the git repository
+/// if there is one or the home directory otherwise, and display the name of the
+/// nearest project found in:
+///     - `package.json`
+///     - `Cargo.toml`
+///     - `go.mod` or `go.work`
+///     - `pom.xml`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("project");
+    let config: ProjectConfig = ProjectConfig::try_load(module.config);
+
+    // Projects outside of the repository, or above the home directory when there is no
+    // repository, are unrelated to the current directory
+    let stop_dir = match context.get_repo().ok().and_then(|repo| repo.root.as_ref()) {
+        Some(repo_root) => Some(repo_root.clone()),
+        None => dirs_next::home_dir(),
+    };
+    let project_name = find_project_name(&context.current_dir, stop_dir.as_deref())?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "name" => Some(Ok(&project_name)),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `project`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn find_project_name(current_dir: &Path, stop_dir: Option<&Path>) -> Option<String> {
+    // Compare canonical paths, so that the stop directory is still found when the
+    // current directory is reached through a symlink
+    let canonicalize = |dir: &Path| dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let current_dir = canonicalize(current_dir);
+    let stop_dir = stop_dir.map(canonicalize);
+
+    for dir in current_dir.ancestors() {
+        for (file_name, parse_name) in PROJECT_FILES {
+            if let Ok(contents) = utils::read_file(dir.join(file_name)) {
+                log::debug!("Found project file {:?}", dir.join(file_name));
+                return parse_name(&contents)
+                    .or_else(|| Some(dir.file_name()?.to_string_lossy().into_owned()));
+            }
+        }
+
+        if Some(dir) == stop_dir.as_deref() {
+            break;
+        }
+    }
+    None
+}
+
+fn parse_package_json(contents: &str) -> Option<String> {
+    let package_json: json::Value = json::from_str(contents).ok()?;
+    let name = package_json.get("name")?.as_str()?;
+    Some(name.to_string())
+}
+
+fn parse_cargo_toml(contents: &str) -> Option<String> {
+    // A workspace root without a package is named after its directory
+    let cargo_toml: toml::Value = toml::from_str(contents).ok()?;
+    let name = cargo_toml.get("package")?.get("name")?.as_str()?;
+    Some(name.to_string())
+}
+
+fn parse_go_mod(contents: &str) -> Option<String> {
+    // "module github.com/starship/starship/tools" is named "tools"
+    let module_path = contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("module "))?;
+    let name = module_path.trim().trim_matches('"').rsplit('/').next()?;
+    Some(name.to_string())
+}
+
+fn parse_pom_xml(contents: &str) -> Option<String> {
+    let mut reader = Reader::from_str(contents);
+    reader.trim_text(true);
+
+    // The `artifactId` of the project itself, not the one of its parent
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event(&mut buf) {
+            Ok(Event::Start(ref e)) => path.push(e.name().to_vec()),
+            Ok(Event::End(_)) => {
+                path.pop();
+            }
+            Ok(Event::Text(e)) if path == [&b"project"[..], &b"artifactId"[..]] => {
+                return e.unescape_and_decode(&reader).ok();
+            }
+            Ok(Event::Eof) | Err(_) => return None,
+            _ => (),
+        }
+        buf.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io::{self, Write};
+
+    fn write_file(path: &Path, contents: &str) -> io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()
+    }
+
+    fn render_project(dir: &Path) -> Option<String> {
+        render_module(
+            "project",
+            dir,
+            Some(toml::toml! {
+                [project]
+                disabled = false
+            }),
+        )
+    }
+
+    #[test]
+    fn test_parse_project_names() {
+        assert_eq!(
+            parse_package_json("{\"name\": \"@starship/ui\"}"),
+            Some("@starship/ui".to_string())
+        );
+        assert_eq!(parse_package_json("{}"), None);
+        assert_eq!(
+            parse_cargo_toml("[package]\nname = \"starship\"\n"),
+            Some("starship".to_string())
+        );
+        assert_eq!(parse_cargo_toml("[workspace]\nmembers = [\"a\"]\n"), None);
+        assert_eq!(
+            parse_go_mod("module github.com/starship/tools\n\ngo 1.15\n"),
+            Some("tools".to_string())
+        );
+        assert_eq!(parse_go_mod("go 1.15\n"), None);
+
+        let pom = "\
+<project>
+  <parent>
+    <artifactId>platform</artifactId>
+  </parent>
+  <artifactId>billing</artifactId>
+</project>";
+        assert_eq!(parse_pom_xml(pom), Some("billing".to_string()));
+    }
+
+    #[test]
+    fn folder_without_project() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir(dir.path().join(".git"))?;
+        let actual = render_project(dir.path());
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn nested_package_in_monorepo() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let package_dir = dir.path().join("packages/ui/src/components");
+        fs::create_dir_all(&package_dir)?;
+        write_file(&dir.path().join("package.json"), "{\"name\": \"monorepo\"}")?;
+        write_file(
+            &dir.path().join("packages/ui/package.json"),
+            "{\"name\": \"@monorepo/ui\"}",
+        )?;
+
+        let actual = render_project(&package_dir);
+        let expected = Some(format!(
+            "in {} ",
+            Color::White.bold().paint("⌂ @monorepo/ui")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn workspace_root_is_named_after_its_directory() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let workspace_dir = dir.path().join("rocket");
+        fs::create_dir_all(workspace_dir.join("docs"))?;
+        write_file(
+            &workspace_dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"engine\"]\n",
+        )?;
+
+        let actual = render_project(&workspace_dir.join("docs"));
+        let expected = Some(format!("in {} ", Color::White.bold().paint("⌂ rocket")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn stops_at_stop_dir() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let home_dir = dir.path().join("home");
+        fs::create_dir_all(home_dir.join("notes"))?;
+        write_file(&dir.path().join("package.json"), "{\"name\": \"stray\"}")?;
+
+        assert_eq!(
+            find_project_name(&home_dir.join("notes"), Some(&home_dir)),
+            None
+        );
+        assert_eq!(
+            find_project_name(&home_dir.join("notes"), None),
+            Some("stray".to_string())
+        );
+        dir.close()
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn stops_at_repo_root_through_symlink() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let repo_dir = dir.path().join("repo");
+        fs::create_dir_all(repo_dir.join("src"))?;
+        write_file(&dir.path().join("package.json"), "{\"name\": \"stray\"}")?;
+        std::os::unix::fs::symlink(&repo_dir, dir.path().join("link"))?;
+
+        let current_dir = dir.path().join("link").join("src");
+        assert_eq!(find_project_name(&current_dir, Some(&repo_dir)), None);
+        dir.close()
+    }
+}
@@ -1,29 +1,30 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::purescript::PureScriptConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current PureScript version
 ///
 /// Will display the PureScript version if any of the following criteria are met:
-///     - Current directory contains a `spago.dhall` file
+///     - Current directory contains a `spago.dhall` or `spago.yaml` file
 ///     - Current directory contains a `*.purs` files
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("purescript");
+    let config: PureScriptConfig = PureScriptConfig::try_load(module.config);
+
     let is_purs_project = context
-        .try_begin_scan()?
-        .set_files(&["spago.dhall"])
-        .set_extensions(&["purs"])
+        .try_begin_module_scan("purescript")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_purs_project {
         return None;
     }
 
-    let purs_version = utils::exec_cmd("purs", &["--version"])?.stdout;
-
-    let mut module = context.new_module("purescript");
-    let config: PureScriptConfig = PureScriptConfig::try_load(module.config);
+    let purs_output = context.exec_cmd("purs", &["--version"])?.stdout;
+    let purs_version = parse_purs_version(&purs_output)?;
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -36,10 +37,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(format!("v{}", purs_version.trim()))),
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    purs_version,
+                    config.version_format,
+                ))),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -53,8 +58,15 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+/// Development builds of `purs` append build details after the version number,
+/// e.g. `0.14.0 [development build; commit: ...]`
+fn parse_purs_version(purs_version: &str) -> Option<&str> {
+    purs_version.split_whitespace().next()
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::modules::utils::test::render_module;
     use ansi_term::Color;
     use std::fs::File;
@@ -90,4 +102,25 @@ mod tests {
         assert_eq!(expected, actual);
         dir.close()
     }
+
+    #[test]
+    fn folder_with_spago_yaml_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("spago.yaml"))?.sync_all()?;
+
+        let actual = render_module("purescript", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::White.bold().paint("<=> v0.13.5")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn test_parse_purs_version() {
+        assert_eq!(parse_purs_version("0.13.5\n"), Some("0.13.5"));
+        assert_eq!(
+            parse_purs_version("0.14.0 [development build; commit: 1234abcd]\n"),
+            Some("0.14.0")
+        );
+        assert_eq!(parse_purs_version(""), None);
+    }
 }
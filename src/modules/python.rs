@@ -3,7 +3,7 @@ use std::path::Path;
 
 use super::{Context, Module, RootModuleConfig};
 use crate::configs::python::PythonConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils;
 
 /// Creates a module with the current Python version
@@ -20,17 +20,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let config: PythonConfig = PythonConfig::try_load(module.config);
 
     let is_py_project = {
-        let base = context.try_begin_scan()?.set_files(&[
-            "requirements.txt",
-            ".python-version",
-            "pyproject.toml",
-            "Pipfile",
-            "tox.ini",
-            "setup.py",
-            "__init__.py",
-        ]);
+        let base = context
+            .try_begin_module_scan("python")?
+            .set_files(&config.detect_files)
+            .set_folders(&config.detect_folders);
         if config.scan_for_pyfiles {
-            base.set_extensions(&["py"]).is_match()
+            base.set_extensions(&config.detect_extensions).is_match()
         } else {
             base.is_match()
         }
@@ -43,10 +38,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     }
 
     let python_version = if config.pyenv_version_name {
-        utils::exec_cmd("pyenv", &["version-name"])?.stdout
+        context.exec_cmd("pyenv", &["version-name"])?.stdout
     } else {
-        let version = get_python_version(&config.python_binary)?;
-        format_python_version(&version)
+        let version = match context.get_pinned_version("python", &["python"]) {
+            // The interpreter of a virtual environment may not be the pinned one
+            Some(version) if !is_venv => version.to_string(),
+            _ => format_python_version(&get_python_version(context, &config.python_binary.0)?),
+        };
+        VersionFormatter::format_module_version(module.get_name(), &version, config.version_format)
     };
     let virtual_env = get_python_virtual_env();
 
@@ -61,11 +60,12 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
+                "pyenv_prefix" if config.pyenv_version_name => Some(Ok(config.pyenv_prefix)),
                 "version" => Some(Ok(python_version.trim())),
                 "virtualenv" => virtual_env.as_ref().map(|e| Ok(e.trim())),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -79,34 +79,56 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_python_version(python_binary: &str) -> Option<String> {
-    match utils::exec_cmd(python_binary, &["--version"]) {
-        Some(output) => {
-            if output.stdout.is_empty() {
-                Some(output.stderr)
-            } else {
-                Some(output.stdout)
-            }
+/// Gets the version from the first of the `python_binaries` which can be executed
+fn get_python_version(context: &Context, python_binaries: &[&str]) -> Option<String> {
+    python_binaries.iter().find_map(|binary| {
+        let output = context.exec_cmd(binary, &["--version"])?;
+        if output.stdout.is_empty() {
+            Some(output.stderr)
+        } else {
+            Some(output.stdout)
         }
-        None => None,
-    }
+    })
 }
 
 fn format_python_version(python_stdout: &str) -> String {
-    format!(
-        "v{}",
-        python_stdout
-            .trim_start_matches("Python ")
-            .trim_end_matches(":: Anaconda, Inc.")
-            .trim()
-    )
+    python_stdout
+        .trim_start_matches("Python ")
+        .trim_end_matches(":: Anaconda, Inc.")
+        .trim()
+        .to_string()
 }
 
+/// The name of the active virtual environment: the prompt it was created with, e.g. with
+/// `python -m venv --prompt`, or the name of its directory
 fn get_python_virtual_env() -> Option<String> {
     env::var("VIRTUAL_ENV").ok().and_then(|venv| {
-        Path::new(&venv)
-            .file_name()
-            .map(|filename| String::from(filename.to_str().unwrap_or("")))
+        let venv = Path::new(&venv);
+        get_prompt_from_venv(venv).or_else(|| {
+            venv.file_name()
+                .map(|filename| String::from(filename.to_str().unwrap_or("")))
+        })
+    })
+}
+
+/// Reads the `prompt` of a virtual environment from its `pyvenv.cfg`
+fn get_prompt_from_venv(venv: &Path) -> Option<String> {
+    let pyvenv_cfg = utils::read_file(venv.join("pyvenv.cfg")).ok()?;
+    parse_venv_prompt(&pyvenv_cfg)
+}
+
+fn parse_venv_prompt(pyvenv_cfg: &str) -> Option<String> {
+    pyvenv_cfg.lines().find_map(|line| {
+        let mut parts = line.splitn(2, '=');
+        if parts.next()?.trim() != "prompt" {
+            return None;
+        }
+        let prompt = parts.next()?.trim().trim_matches(|c| c == '\'' || c == '"');
+        if prompt.is_empty() {
+            None
+        } else {
+            Some(prompt.to_string())
+        }
     })
 }
 
@@ -121,13 +143,25 @@ mod tests {
     #[test]
     fn test_format_python_version() {
         let input = "Python 3.7.2";
-        assert_eq!(format_python_version(input), "v3.7.2");
+        assert_eq!(format_python_version(input), "3.7.2");
     }
 
     #[test]
     fn test_format_python_version_anaconda() {
         let input = "Python 3.6.10 :: Anaconda, Inc.";
-        assert_eq!(format_python_version(input), "v3.6.10");
+        assert_eq!(format_python_version(input), "3.6.10");
+    }
+
+    #[test]
+    fn test_parse_venv_prompt() {
+        let pyvenv_cfg =
+            "home = /usr/bin\ninclude-system-site-packages = false\nprompt = 'my-project'\n";
+        assert_eq!(
+            parse_venv_prompt(pyvenv_cfg),
+            Some("my-project".to_string())
+        );
+        assert_eq!(parse_venv_prompt("home = /usr/bin\n"), None);
+        assert_eq!(parse_venv_prompt("prompt =\n"), None);
     }
 
     #[test]
@@ -150,6 +184,24 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn version_formatting() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(".python-version"))?.sync_all()?;
+
+        let actual = render_module(
+            "python",
+            dir.path(),
+            Some(toml::toml! {
+                [python]
+                version_format = "v${major}.${minor}"
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐍 v2.7")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_requirements_txt() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
@@ -180,6 +232,52 @@ mod tests {
         dir.close()
     }
 
+    #[test]
+    fn with_python_binary_list() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("requirements.txt"))?.sync_all()?;
+
+        let config = toml::toml! {
+            [python]
+            python_binary = ["python-not-installed", "python3", "python"]
+        };
+        check_python3_renders(&dir, Some(config));
+        dir.close()
+    }
+
+    #[test]
+    fn with_pyenv_version_name() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(".python-version"))?.sync_all()?;
+
+        let actual = render_module(
+            "python",
+            dir.path(),
+            Some(toml::toml! {
+                [python]
+                pyenv_version_name = true
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Yellow.bold().paint("🐍 pyenv system")
+        ));
+        assert_eq!(expected, actual);
+
+        let actual = render_module(
+            "python",
+            dir.path(),
+            Some(toml::toml! {
+                [python]
+                pyenv_version_name = true
+                pyenv_prefix = ""
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Yellow.bold().paint("🐍 system")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
     #[test]
     fn folder_with_tox() -> io::Result<()> {
         let dir = tempfile::tempdir()?;
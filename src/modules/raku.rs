@@ -0,0 +1,183 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::raku::RakuConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Raku version
+///
+/// Will display the Raku version if any of the following criteria are met:
+///     - Current directory contains a `META6.json` file
+///     - Current directory contains a file with `.p6`, `.pm6`, `.raku`, `.rakumod` or `.pod6` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("raku");
+    let config: RakuConfig = RakuConfig::try_load(module.config);
+
+    let is_raku_project = context
+        .try_begin_module_scan("raku")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_raku_project {
+        return None;
+    }
+
+    let raku_version = context.exec_cmd("raku", &["--version"])?.stdout;
+    let (version, vm_version) = parse_raku_version(&raku_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &version,
+                    config.version_format,
+                ))),
+                "vm_version" => Some(Ok(vm_version.clone())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `raku`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+/// Parses the language version and the name of the virtual machine from `raku --version`,
+/// e.g. `v6.d` and `moar`
+fn parse_raku_version(raku_version: &str) -> Option<(String, String)> {
+    let mut lines = raku_version.lines();
+    // e.g. "This is Rakudo version 2020.07 built on MoarVM version 2020.07"
+    let build_line = lines.next()?;
+    // e.g. "implementing Raku 6.d."
+    let language_line = lines.next()?;
+
+    let vm_name = build_line
+        .split_whitespace()
+        .skip_while(|word| *word != "on")
+        .nth(1)?;
+    // Use the names of `$*VM.name`, e.g. "moar" for "MoarVM"
+    let vm_version = match vm_name {
+        "MoarVM" => String::from("moar"),
+        vm_name => vm_name.to_lowercase(),
+    };
+
+    let version = language_line
+        .split_whitespace()
+        .nth(2)?
+        .trim_end_matches('.');
+
+    Some((version.to_string(), vm_version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_raku_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_raku_version() {
+        let moar_input = "\
+This is Rakudo version 2020.07 built on MoarVM version 2020.07
+implementing Raku 6.d.";
+        assert_eq!(
+            parse_raku_version(moar_input),
+            Some(("6.d".to_string(), "moar".to_string()))
+        );
+
+        let jvm_input = "\
+This is Rakudo version 2020.07 built on JVM
+implementing Raku 6.d.";
+        assert_eq!(
+            parse_raku_version(jvm_input),
+            Some(("6.d".to_string(), "jvm".to_string()))
+        );
+
+        assert_eq!(parse_raku_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_raku_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("raku", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_meta6_json_file() -> io::Result<()> {
+        check_raku_render("META6.json")
+    }
+
+    #[test]
+    fn folder_with_raku_file() -> io::Result<()> {
+        check_raku_render("any.raku")
+    }
+
+    #[test]
+    fn folder_with_rakumod_file() -> io::Result<()> {
+        check_raku_render("any.rakumod")
+    }
+
+    #[test]
+    fn folder_with_p6_file() -> io::Result<()> {
+        check_raku_render("any.p6")
+    }
+
+    #[test]
+    fn folder_with_pm6_file() -> io::Result<()> {
+        check_raku_render("any.pm6")
+    }
+
+    #[test]
+    fn folder_with_raku_version_format() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("any.raku"))?.sync_all()?;
+        let actual = render_module(
+            "raku",
+            dir.path(),
+            Some(toml::toml! {
+                [raku]
+                format = "via [$symbol$version on $vm_version]($style) "
+            }),
+        );
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(149).bold().paint("🦋 v6.d on moar")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    fn check_raku_render(file_name: &str) -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(file_name))?.sync_all()?;
+        let actual = render_module("raku", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(149).bold().paint("🦋 v6.d-moar")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
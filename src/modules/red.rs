@@ -0,0 +1,119 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::red::RedConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Red version
+///
+/// Will display the Red version if any of the following criteria are met:
+///     - Current directory contains a file with `.red` or `.reds` extension
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("red");
+    let config: RedConfig = RedConfig::try_load(module.config);
+
+    let is_red_project = context
+        .try_begin_module_scan("red")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_red_project {
+        return None;
+    }
+
+    let red_version = context.exec_cmd("red", &["--version"])?.stdout;
+    let module_version = parse_red_version(&red_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `red`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_red_version(red_version: &str) -> Option<String> {
+    let version = red_version
+        // split into ["0.6.4", "for", "Linux", ...], or ["Red", "0.6.4", ...] for red-toolchain
+        .split_whitespace()
+        // return "0.6.4"
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_red_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_red_version() {
+        assert_eq!(
+            parse_red_version("0.6.4 for Linux built 26-Nov-2020/9:08:06+01:00\n"),
+            Some("0.6.4".to_string())
+        );
+        assert_eq!(
+            parse_red_version("Red 0.6.4 for Windows built 26-Nov-2020/9:08:06+01:00\n"),
+            Some("0.6.4".to_string())
+        );
+        assert_eq!(parse_red_version("Red"), None);
+    }
+
+    #[test]
+    fn folder_without_red_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("red", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_red_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("hello.red"))?.sync_all()?;
+        let actual = render_module("red", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Red.bold().paint("🔺 v0.6.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_reds_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("hello.reds"))?.sync_all()?;
+        let actual = render_module("red", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Red.bold().paint("🔺 v0.6.4")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
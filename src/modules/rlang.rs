@@ -0,0 +1,146 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::rlang::RLangConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current R version
+///
+/// Will display the R version if any of the following criteria are met:
+///     - Current directory contains a file with `.R`, `.Rmd` or `.Rproj` extension
+///     - Current directory contains a `DESCRIPTION` or `.Rprofile` file
+///     - Current directory contains a `.Rproj.user` folder
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("rlang");
+    let config: RLangConfig = RLangConfig::try_load(module.config);
+
+    let is_r_project = context
+        .try_begin_module_scan("rlang")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_r_project {
+        return None;
+    }
+
+    let r_version = context.exec_cmd("R", &["--version"])?;
+    // Some builds of R print the version to stderr
+    let module_version =
+        parse_r_version(&r_version.stdout).or_else(|| parse_r_version(&r_version.stderr))?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `rlang`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_r_version(r_version: &str) -> Option<String> {
+    let version = r_version
+        // take the first line, e.g. `R version 4.0.2 (2020-06-22) -- "Taking Off Again"`
+        .lines()
+        .next()?
+        // split into ["R", "version", "4.0.2", ...]
+        .split_whitespace()
+        // return "4.0.2"
+        .nth(2)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_r_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::{self, File};
+    use std::io;
+
+    #[test]
+    fn test_parse_r_version() {
+        let r_version = r#"R version 4.0.2 (2020-06-22) -- "Taking Off Again"
+Copyright (C) 2020 The R Foundation for Statistical Computing
+Platform: x86_64-pc-linux-gnu (64-bit)"#;
+        assert_eq!(parse_r_version(r_version), Some("4.0.2".to_string()));
+        assert_eq!(parse_r_version(""), None);
+    }
+
+    #[test]
+    fn folder_without_r_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("rlang", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_r_file() -> io::Result<()> {
+        check_r_render("analysis.R")
+    }
+
+    #[test]
+    fn folder_with_rmd_file() -> io::Result<()> {
+        check_r_render("report.Rmd")
+    }
+
+    #[test]
+    fn folder_with_rproj_file() -> io::Result<()> {
+        check_r_render("project.Rproj")
+    }
+
+    #[test]
+    fn folder_with_description_file() -> io::Result<()> {
+        check_r_render("DESCRIPTION")
+    }
+
+    #[test]
+    fn folder_with_rprofile_file() -> io::Result<()> {
+        check_r_render(".Rprofile")
+    }
+
+    #[test]
+    fn folder_with_rproj_user_folder() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::create_dir_all(dir.path().join(".Rproj.user"))?;
+        let actual = render_module("rlang", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("📐 v4.0.2")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    fn check_r_render(file_name: &str) -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join(file_name))?.sync_all()?;
+        let actual = render_module("rlang", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("📐 v4.0.2")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -1,8 +1,7 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::ruby::RubyConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current Ruby version
 ///
@@ -10,18 +9,20 @@ use crate::utils;
 ///     - Current directory contains a `.rb` file
 ///     - Current directory contains a `Gemfile` or `.ruby-version` file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("ruby");
+    let config = RubyConfig::try_load(module.config);
+
     let is_rb_project = context
-        .try_begin_scan()?
-        .set_files(&["Gemfile", ".ruby-version"])
-        .set_extensions(&["rb"])
+        .try_begin_module_scan("ruby")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_rb_project {
         return None;
     }
 
-    let mut module = context.new_module("ruby");
-    let config = RubyConfig::try_load(module.config);
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -33,12 +34,16 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => {
-                    format_ruby_version(&utils::exec_cmd("ruby", &["-v"])?.stdout.as_str()).map(Ok)
-                }
+                "version" => get_ruby_version(context).map(|version| {
+                    Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    ))
+                }),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -52,6 +57,13 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
+fn get_ruby_version(context: &Context) -> Option<String> {
+    match context.get_pinned_version("ruby", &["ruby"]) {
+        Some(version) => Some(version.to_string()),
+        None => format_ruby_version(&context.exec_cmd("ruby", &["-v"])?.stdout),
+    }
+}
+
 fn format_ruby_version(ruby_version: &str) -> Option<String> {
     let version = ruby_version
         // split into ["ruby", "2.6.0p0", "linux/amd64"]
@@ -63,10 +75,7 @@ fn format_ruby_version(ruby_version: &str) -> Option<String> {
         // return "2.6.0"
         .next()?;
 
-    let mut formatted_version = String::with_capacity(version.len() + 1);
-    formatted_version.push('v');
-    formatted_version.push_str(version);
-    Some(formatted_version)
+    Some(version.to_string())
 }
 
 #[cfg(test)]
@@ -128,17 +137,17 @@ mod tests {
     fn test_format_ruby_version() -> io::Result<()> {
         assert_eq!(
             format_ruby_version("ruby 2.1.10p492 (2016-04-01 revision 54464) [x86_64-darwin19.0]"),
-            Some("v2.1.10".to_string())
+            Some("2.1.10".to_string())
         );
         assert_eq!(
             format_ruby_version("ruby 2.5.1p57 (2018-03-29 revision 63029) [x86_64-linux-gnu]"),
-            Some("v2.5.1".to_string())
+            Some("2.5.1".to_string())
         );
         assert_eq!(
             format_ruby_version(
                 "ruby 2.7.0p0 (2019-12-25 revision 647ee6f091) [x86_64-linux-musl]"
             ),
-            Some("v2.7.0".to_string())
+            Some("2.7.0".to_string())
         );
 
         Ok(())
@@ -1,11 +1,12 @@
-use std::path::Path;
+use once_cell::sync::OnceCell;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use std::{env, fs};
 
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::rust::RustConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current Rust version
 ///
@@ -13,18 +14,28 @@ use crate::formatter::StringFormatter;
 ///     - Current directory contains a file with a `.rs` extension
 ///     - Current directory contains a `Cargo.toml` file
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("rust");
+    let config = RustConfig::try_load(module.config);
+
     let is_rs_project = context
-        .try_begin_scan()?
-        .set_files(&["Cargo.toml"])
-        .set_extensions(&["rs"])
+        .try_begin_module_scan("rust")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_rs_project {
         return None;
     }
 
-    let mut module = context.new_module("rust");
-    let config = RustConfig::try_load(module.config);
+    // The version is only looked up if it is needed, as `rustc` can be slow to start
+    let rust_version = OnceCell::new();
+    let get_rust_version = || {
+        rust_version
+            .get_or_init(|| get_module_version(context))
+            .as_ref()
+    };
+
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|var, _| match var {
@@ -32,16 +43,37 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map_style(|variable| match variable {
-                "style" => Some(Ok(config.style)),
+                "style" => Some(Ok(match config.nightly_style {
+                    Some(nightly_style)
+                        if get_rust_version()
+                            .filter(|rust| rust.is_nightly())
+                            .is_some() =>
+                    {
+                        nightly_style
+                    }
+                    _ => config.style,
+                })),
                 _ => None,
             })
             .map(|variable| match variable {
-                // This may result in multiple calls to `get_module_version` when a user have
-                // multiple `$version` variables defined in `format`.
-                "version" => get_module_version(context).map(Ok),
+                "version" => get_rust_version().map(|rust| match rust.numver {
+                    // Toolchain names aren't version numbers, and are shown as they are
+                    Some(_) => Ok(VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &rust.version,
+                        config.version_format,
+                    )),
+                    None => Ok(rust.version.clone()),
+                }),
+                "numver" => get_rust_version()
+                    .and_then(|rust| rust.numver.clone())
+                    .map(Ok),
+                "toolchain" => get_rust_version()
+                    .and_then(|rust| rust.toolchain.clone())
+                    .map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -55,39 +87,116 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     Some(module)
 }
 
-fn get_module_version(context: &Context) -> Option<String> {
-    // `$CARGO_HOME/bin/rustc(.exe) --version` may attempt installing a rustup toolchain.
+/// The version of the Rust toolchain used in the current directory
+#[derive(Debug, PartialEq)]
+struct RustVersion {
+    /// The version reported by `rustc`, e.g. `1.34.0-nightly`, or the toolchain name
+    version: String,
+    /// The version number, without the release channel, e.g. `1.34.0`
+    numver: Option<String>,
+    /// The release channel of the toolchain, e.g. `stable`, `beta` or `nightly-2019-04-10`
+    toolchain: Option<String>,
+}
+
+impl RustVersion {
+    fn from_rustc_version(rustc_stdout: String, toolchain: Option<&str>) -> Self {
+        let version = format_rustc_version(rustc_stdout);
+        let numver = version
+            .split('-')
+            .next()
+            .filter(|numver| !numver.is_empty())
+            .map(str::to_owned);
+        let toolchain = match toolchain {
+            Some(toolchain) => format_toolchain_channel(toolchain),
+            None if version.contains("-nightly") => String::from("nightly"),
+            None if version.contains("-beta") => String::from("beta"),
+            None => String::from("stable"),
+        };
+
+        RustVersion {
+            version,
+            numver,
+            toolchain: Some(toolchain),
+        }
+    }
+
+    fn from_toolchain_name(toolchain: String) -> Self {
+        RustVersion {
+            numver: None,
+            toolchain: Some(format_toolchain_channel(&toolchain)),
+            version: toolchain,
+        }
+    }
+
+    fn is_nightly(&self) -> bool {
+        match &self.toolchain {
+            Some(toolchain) => toolchain.starts_with("nightly"),
+            None => false,
+        }
+    }
+}
+
+fn get_module_version(context: &Context) -> Option<RustVersion> {
+    // `$CARGO_HOME/bin/rustc(.exe) --version` may attempt installing a rustup toolchain,
+    // and every run of the rustup proxy takes tens of milliseconds.
     // https://github.com/starship/starship/issues/417
     //
-    // To display appropriate versions preventing `rustc` from downloading toolchains, we have to
-    // check
+    // To avoid running `rustup` or `rustc`, the toolchain is resolved by reading the files
+    // of rustup in the order of its override precedence:
     // 1. `$RUSTUP_TOOLCHAIN`
-    // 2. `rustup override list`
-    // 3. `rust-toolchain` in `.` or parent directories
-    // as `rustup` does.
-    // https://github.com/rust-lang/rustup.rs/tree/eb694fcada7becc5d9d160bf7c623abe84f8971d#override-precedence
+    // 2. directory overrides in `settings.toml` of rustup
+    // 3. `rust-toolchain` or `rust-toolchain.toml` in `.` or parent directories
+    // 4. the default toolchain in `settings.toml`
+    // https://github.com/rust-lang/rustup/tree/1.22.1#override-precedence
     //
-    // Probably we have no other way to know whether any toolchain override is specified for the
-    // current directory. The following commands also cause toolchain installations.
-    // - `rustup show`
-    // - `rustup show active-toolchain`
-    // - `rustup which`
-    let module_version = if let Some(toolchain) = env_rustup_toolchain()
-        .or_else(|| execute_rustup_override_list(&context.current_dir))
-        .or_else(|| find_rust_toolchain_file(&context))
-    {
-        match execute_rustup_run_rustc_version(&toolchain) {
-            RustupRunRustcVersionOutcome::RustcVersion(stdout) => format_rustc_version(stdout),
-            RustupRunRustcVersionOutcome::ToolchainName(toolchain) => toolchain,
-            RustupRunRustcVersionOutcome::RustupNotWorking => {
-                // If `rustup` is not in `$PATH` or cannot be executed for other reasons, we can
-                // safely execute `rustc --version`.
-                format_rustc_version(execute_rustc_version()?)
-            }
-            RustupRunRustcVersionOutcome::Err => return None,
+    // The version is then read from the manifest of the installed toolchain. Only if that
+    // fails, e.g. for custom toolchains or toolchains which aren't installed, `rustup run` is
+    // executed instead, as it doesn't install toolchains, unlike `rustup show` or `rustup which`.
+    let rustup_home = rustup_home();
+    let settings = rustup_home
+        .as_ref()
+        .and_then(|rustup_home| read_rustup_settings(rustup_home));
+
+    let toolchain = env_rustup_toolchain()
+        .or_else(|| {
+            settings
+                .as_ref()
+                .and_then(|settings| find_override_toolchain(settings, &context.current_dir))
+        })
+        .or_else(|| find_rust_toolchain_file(context))
+        .or_else(|| settings.as_ref().and_then(default_toolchain));
+
+    let toolchain = match toolchain {
+        Some(toolchain) => toolchain,
+        // Without rustup, `rustc` is a real compiler and not a proxy
+        None => {
+            return Some(RustVersion::from_rustc_version(
+                execute_rustc_version()?,
+                None,
+            ))
         }
-    } else {
-        format_rustc_version(execute_rustc_version()?)
+    };
+
+    let manifest_version = rustup_home.as_ref().and_then(|rustup_home| {
+        read_toolchain_manifest_version(rustup_home, &toolchain, settings.as_ref())
+    });
+    if let Some(version) = manifest_version {
+        return Some(RustVersion::from_rustc_version(version, Some(&toolchain)));
+    }
+
+    let module_version = match execute_rustup_run_rustc_version(&toolchain) {
+        RustupRunRustcVersionOutcome::RustcVersion(stdout) => {
+            RustVersion::from_rustc_version(stdout, Some(&toolchain))
+        }
+        RustupRunRustcVersionOutcome::ToolchainName(toolchain) => {
+            RustVersion::from_toolchain_name(toolchain)
+        }
+        RustupRunRustcVersionOutcome::RustupNotWorking => {
+            // If `rustup` is not in `$PATH` or cannot be executed for other reasons, we can
+            // safely execute `rustc --version`.
+            RustVersion::from_rustc_version(execute_rustc_version()?, None)
+        }
+        RustupRunRustcVersionOutcome::Err => return None,
     };
 
     Some(module_version)
@@ -98,59 +207,93 @@ fn env_rustup_toolchain() -> Option<String> {
     Some(val.trim().to_owned())
 }
 
-fn execute_rustup_override_list(cwd: &Path) -> Option<String> {
-    let Output { stdout, .. } = Command::new("rustup")
-        .args(&["override", "list"])
-        .output()
-        .ok()?;
-    let stdout = String::from_utf8(stdout).ok()?;
-    extract_toolchain_from_rustup_override_list(&stdout, cwd)
+/// The directory of rustup, `$RUSTUP_HOME` or `~/.rustup`
+fn rustup_home() -> Option<PathBuf> {
+    env::var_os("RUSTUP_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(dirs_next::home_dir()?.join(".rustup")))
 }
 
-fn extract_toolchain_from_rustup_override_list(stdout: &str, cwd: &Path) -> Option<String> {
-    if stdout == "no overrides\n" {
-        return None;
-    }
-    stdout
-        .lines()
-        .flat_map(|line| {
-            let mut words = line.split_whitespace();
-            let dir = words.next()?;
-            let toolchain = words.next()?;
-            Some((dir, toolchain))
-        })
-        .find(|(dir, _)| cwd.starts_with(dir))
-        .map(|(_, toolchain)| toolchain.to_owned())
+fn read_rustup_settings(rustup_home: &Path) -> Option<toml::Value> {
+    let contents = fs::read_to_string(rustup_home.join("settings.toml")).ok()?;
+    contents.parse().ok()
 }
 
-fn find_rust_toolchain_file(context: &Context) -> Option<String> {
-    // Look for 'rust-toolchain' as rustup does.
-    // https://github.com/rust-lang/rustup.rs/blob/d84e6e50126bccd84649e42482fc35a11d019401/src/config.rs#L320-L358
+/// Finds the toolchain set with `rustup override set` for `cwd` or one of its parents
+fn find_override_toolchain(settings: &toml::Value, cwd: &Path) -> Option<String> {
+    let overrides = settings.get("overrides")?.as_table()?;
+    cwd.ancestors().find_map(|dir| {
+        let toolchain = overrides.get(dir.to_str()?)?.as_str()?;
+        Some(toolchain.to_owned())
+    })
+}
 
-    fn read_first_line(path: &Path) -> Option<String> {
-        let content = fs::read_to_string(path).ok()?;
-        let line = content.lines().next()?;
-        Some(line.trim().to_owned())
-    }
+fn default_toolchain(settings: &toml::Value) -> Option<String> {
+    let toolchain = settings.get("default_toolchain")?.as_str()?;
+    Some(toolchain.to_owned())
+}
 
-    if let Ok(true) = context
-        .dir_contents()
-        .map(|dir| dir.has_file("rust-toolchain"))
-    {
-        if let Some(toolchain) = read_first_line(Path::new("rust-toolchain")) {
-            return Some(toolchain);
-        }
-    }
+fn find_rust_toolchain_file(context: &Context) -> Option<String> {
+    // Look for `rust-toolchain` and `rust-toolchain.toml` as rustup does.
+    // https://github.com/rust-lang/rustup/blob/1.24.1/src/config.rs#L560-L606
+    context.current_dir.ancestors().find_map(|dir| {
+        ["rust-toolchain", "rust-toolchain.toml"]
+            .iter()
+            .find_map(|file_name| {
+                let contents = fs::read_to_string(dir.join(file_name)).ok()?;
+                parse_toolchain_file(&contents)
+            })
+    })
+}
 
-    let mut dir = &*context.current_dir;
-    loop {
-        if let Some(toolchain) = read_first_line(&dir.join("rust-toolchain")) {
-            return Some(toolchain);
+/// Reads the toolchain from a toolchain file, either in the TOML format with a
+/// `toolchain.channel` key, or in the legacy format of a single line with the toolchain name
+fn parse_toolchain_file(contents: &str) -> Option<String> {
+    match contents.parse::<toml::Value>() {
+        Ok(toml) => {
+            let channel = toml.get("toolchain")?.get("channel")?.as_str()?;
+            Some(channel.to_owned())
+        }
+        Err(_) => {
+            let line = contents.lines().next()?.trim();
+            if line.is_empty() {
+                None
+            } else {
+                Some(line.to_owned())
+            }
         }
-        dir = dir.parent()?;
     }
 }
 
+/// The path of the manifest of a toolchain, relative to its directory
+const RUSTUP_MANIFEST_PATH: &str = "lib/rustlib/multirust-channel-manifest.toml";
+
+/// Reads the `rustc` version from the manifest rustup installed along with the toolchain,
+/// e.g. `1.45.0 (5c1f21c3b 2020-07-13)`
+fn read_toolchain_manifest_version(
+    rustup_home: &Path,
+    toolchain: &str,
+    settings: Option<&toml::Value>,
+) -> Option<String> {
+    let toolchains = rustup_home.join("toolchains");
+    // Toolchains are installed in directories named after their full name, with the host triple
+    let host_triple = settings
+        .and_then(|settings| settings.get("default_host_triple")?.as_str())
+        .map(|host_triple| format!("{}-{}", toolchain, host_triple));
+    let manifest = std::iter::once(toolchain.to_owned())
+        .chain(host_triple)
+        .map(|name| toolchains.join(name).join(RUSTUP_MANIFEST_PATH))
+        .find_map(|path| fs::read_to_string(path).ok())?;
+
+    let manifest: toml::Value = manifest.parse().ok()?;
+    let version = manifest
+        .get("pkg")?
+        .get("rustc")?
+        .get("version")?
+        .as_str()?;
+    Some(version.to_owned())
+}
+
 fn execute_rustup_run_rustc_version(toolchain: &str) -> RustupRunRustcVersionOutcome {
     Command::new("rustup")
         .args(&["run", toolchain, "rustc", "--version"])
@@ -186,7 +329,33 @@ fn format_rustc_version(mut rustc_stdout: String) -> String {
     let offset = &rustc_stdout.find('(').unwrap_or_else(|| rustc_stdout.len());
     let formatted_version: String = rustc_stdout.drain(..offset).collect();
 
-    format!("v{}", formatted_version.replace("rustc", "").trim())
+    formatted_version.replace("rustc", "").trim().to_string()
+}
+
+/// Reduces a rustup toolchain name to its release channel, dropping the host triple,
+/// e.g. `nightly-2020-06-01-x86_64-unknown-linux-gnu` becomes `nightly-2020-06-01`.
+/// Toolchains pinned to a version number are on the stable channel.
+fn format_toolchain_channel(toolchain: &str) -> String {
+    let mut parts = toolchain.split('-');
+    let channel = parts.next().unwrap_or_default();
+
+    match channel {
+        "stable" | "beta" | "nightly" => {
+            let date = parts.take(3).collect::<Vec<_>>();
+            let is_date = date.len() == 3
+                && date[0].len() == 4
+                && date
+                    .iter()
+                    .all(|part| part.chars().all(|c| c.is_ascii_digit()));
+            if is_date {
+                format!("{}-{}", channel, date.join("-"))
+            } else {
+                channel.to_owned()
+            }
+        }
+        _ if channel.starts_with(|c: char| c.is_ascii_digit()) => String::from("stable"),
+        _ => toolchain.to_owned(),
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -205,35 +374,75 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_extract_toolchain_from_rustup_override_list() {
-        static NO_OVERRIDES_INPUT: &str = "no overrides\n";
-        static NO_OVERRIDES_CWD: &str = "";
+    fn test_find_override_toolchain() {
+        let settings: toml::Value = toml::toml! {
+            default_toolchain = "stable-x86_64-unknown-linux-gnu"
+
+            [overrides]
+            "/home/user/src/a" = "beta-x86_64-unknown-linux-gnu"
+            "/home/user/src/b" = "nightly-x86_64-unknown-linux-gnu"
+        };
+        assert_eq!(
+            find_override_toolchain(&settings, "/home/user/src/a/src".as_ref()),
+            Some("beta-x86_64-unknown-linux-gnu".to_owned()),
+        );
+        assert_eq!(
+            find_override_toolchain(&settings, "/home/user/src/b".as_ref()),
+            Some("nightly-x86_64-unknown-linux-gnu".to_owned()),
+        );
         assert_eq!(
-            extract_toolchain_from_rustup_override_list(
-                NO_OVERRIDES_INPUT,
-                NO_OVERRIDES_CWD.as_ref(),
-            ),
+            find_override_toolchain(&settings, "/home/user/src/c/examples".as_ref()),
             None,
         );
+        assert_eq!(
+            default_toolchain(&settings),
+            Some("stable-x86_64-unknown-linux-gnu".to_owned()),
+        );
+    }
 
-        static OVERRIDES_INPUT: &str =
-            "/home/user/src/a                                beta-x86_64-unknown-linux-gnu\n\
-             /home/user/src/b                                nightly-x86_64-unknown-linux-gnu\n";
-        static OVERRIDES_CWD_A: &str = "/home/user/src/a/src";
-        static OVERRIDES_CWD_B: &str = "/home/user/src/b/tests";
-        static OVERRIDES_CWD_C: &str = "/home/user/src/c/examples";
+    #[test]
+    fn test_parse_toolchain_file() {
         assert_eq!(
-            extract_toolchain_from_rustup_override_list(OVERRIDES_INPUT, OVERRIDES_CWD_A.as_ref()),
-            Some("beta-x86_64-unknown-linux-gnu".to_owned()),
+            parse_toolchain_file("nightly-2020-06-01\n"),
+            Some("nightly-2020-06-01".to_owned()),
         );
         assert_eq!(
-            extract_toolchain_from_rustup_override_list(OVERRIDES_INPUT, OVERRIDES_CWD_B.as_ref()),
-            Some("nightly-x86_64-unknown-linux-gnu".to_owned()),
+            parse_toolchain_file("[toolchain]\nchannel = \"1.45.0\"\n"),
+            Some("1.45.0".to_owned()),
         );
         assert_eq!(
-            extract_toolchain_from_rustup_override_list(OVERRIDES_INPUT, OVERRIDES_CWD_C.as_ref()),
-            None,
+            parse_toolchain_file("[toolchain]\ncomponents = [\"rustfmt\"]\n"),
+            None
         );
+        assert_eq!(parse_toolchain_file(""), None);
+    }
+
+    #[test]
+    fn test_read_toolchain_manifest_version() -> std::io::Result<()> {
+        let rustup_home = tempfile::tempdir()?;
+        let toolchain_dir = rustup_home
+            .path()
+            .join("toolchains/stable-x86_64-unknown-linux-gnu");
+        let manifest_path = toolchain_dir.join(RUSTUP_MANIFEST_PATH);
+        fs::create_dir_all(manifest_path.parent().unwrap())?;
+        fs::write(
+            &manifest_path,
+            "[pkg.rustc]\nversion = \"1.45.0 (5c1f21c3b 2020-07-13)\"\n",
+        )?;
+        let settings: toml::Value = toml::toml! {
+            default_host_triple = "x86_64-unknown-linux-gnu"
+        };
+
+        let version = |toolchain, settings| {
+            read_toolchain_manifest_version(rustup_home.path(), toolchain, settings)
+        };
+        let expected = Some("1.45.0 (5c1f21c3b 2020-07-13)".to_owned());
+        assert_eq!(version("stable-x86_64-unknown-linux-gnu", None), expected);
+        assert_eq!(version("stable", Some(&settings)), expected);
+        assert_eq!(version("stable", None), None);
+        assert_eq!(version("nightly", Some(&settings)), None);
+
+        rustup_home.close()
     }
 
     #[cfg(any(unix, windows))]
@@ -298,15 +507,66 @@ mod tests {
     #[test]
     fn test_format_rustc_version() {
         let nightly_input = String::from("rustc 1.34.0-nightly (b139669f3 2019-04-10)");
-        assert_eq!(format_rustc_version(nightly_input), "v1.34.0-nightly");
+        assert_eq!(format_rustc_version(nightly_input), "1.34.0-nightly");
 
         let beta_input = String::from("rustc 1.34.0-beta.1 (2bc1d406d 2019-04-10)");
-        assert_eq!(format_rustc_version(beta_input), "v1.34.0-beta.1");
+        assert_eq!(format_rustc_version(beta_input), "1.34.0-beta.1");
 
         let stable_input = String::from("rustc 1.34.0 (91856ed52 2019-04-10)");
-        assert_eq!(format_rustc_version(stable_input), "v1.34.0");
+        assert_eq!(format_rustc_version(stable_input), "1.34.0");
 
         let version_without_hash = String::from("rustc 1.34.0");
-        assert_eq!(format_rustc_version(version_without_hash), "v1.34.0");
+        assert_eq!(format_rustc_version(version_without_hash), "1.34.0");
+    }
+
+    #[test]
+    fn test_format_toolchain_channel() {
+        assert_eq!(format_toolchain_channel("stable"), "stable");
+        assert_eq!(
+            format_toolchain_channel("beta-x86_64-unknown-linux-gnu"),
+            "beta"
+        );
+        assert_eq!(
+            format_toolchain_channel("nightly-2020-06-01-x86_64-unknown-linux-gnu"),
+            "nightly-2020-06-01"
+        );
+        assert_eq!(
+            format_toolchain_channel("nightly-2020-06-01"),
+            "nightly-2020-06-01"
+        );
+        assert_eq!(format_toolchain_channel("1.43.0"), "stable");
+        assert_eq!(format_toolchain_channel("my-toolchain"), "my-toolchain");
+    }
+
+    #[test]
+    fn test_rust_version_from_rustc_version() {
+        let nightly_input = String::from("rustc 1.34.0-nightly (b139669f3 2019-04-10)");
+        let nightly = RustVersion::from_rustc_version(nightly_input, None);
+        assert_eq!(nightly.version, "1.34.0-nightly");
+        assert_eq!(nightly.numver, Some("1.34.0".to_owned()));
+        assert_eq!(nightly.toolchain, Some("nightly".to_owned()));
+        assert!(nightly.is_nightly());
+
+        let beta_input = String::from("rustc 1.34.0-beta.1 (2bc1d406d 2019-04-10)");
+        let beta = RustVersion::from_rustc_version(beta_input, None);
+        assert_eq!(beta.numver, Some("1.34.0".to_owned()));
+        assert_eq!(beta.toolchain, Some("beta".to_owned()));
+        assert!(!beta.is_nightly());
+
+        let stable_input = String::from("rustc 1.34.0 (91856ed52 2019-04-10)");
+        let stable = RustVersion::from_rustc_version(stable_input, None);
+        assert_eq!(stable.toolchain, Some("stable".to_owned()));
+
+        let pinned_input = String::from("rustc 1.46.0-nightly (f455e46ea 2020-06-20)");
+        let pinned = RustVersion::from_rustc_version(pinned_input, Some("nightly-2020-06-21"));
+        assert_eq!(pinned.toolchain, Some("nightly-2020-06-21".to_owned()));
+    }
+
+    #[test]
+    fn test_rust_version_from_toolchain_name() {
+        let rust = RustVersion::from_toolchain_name(String::from("nightly-2020-06-21"));
+        assert_eq!(rust.version, "nightly-2020-06-21");
+        assert_eq!(rust.numver, None);
+        assert!(rust.is_nightly());
     }
 }
@@ -0,0 +1,196 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::solidity::SolidityConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+use crate::utils;
+
+/// Creates a module with the current Solidity compiler version
+///
+/// Will display the Solidity compiler version if the current directory contains
+/// a file with `.sol` extension. The version is taken from:
+///     - `solc --version` or `solcjs --version`, depending on the `compiler` option
+///     - The `solc_version` of the default profile in `foundry.toml`, if `compiler`
+///       is set to `forge`
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("solidity");
+    let config: SolidityConfig = SolidityConfig::try_load(module.config);
+
+    let is_solidity_project = context
+        .try_begin_module_scan("solidity")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_solidity_project {
+        return None;
+    }
+
+    let module_version = match config.compiler {
+        "forge" => {
+            let foundry_toml = utils::read_file(context.current_dir.join("foundry.toml")).ok()?;
+            parse_foundry_solc_version(&foundry_toml)?
+        }
+        compiler => {
+            let compiler_version = context.exec_cmd(compiler, &["--version"])?.stdout;
+            parse_solc_version(&compiler_version)?
+        }
+    };
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `solidity`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_solc_version(compiler_version: &str) -> Option<String> {
+    let version = compiler_version
+        // "Version: 0.8.16+commit.07a7930e.Linux.g++" for solc,
+        // "0.8.16+commit.07a7930e.Emscripten.clang" for solcjs
+        .split_whitespace()
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))?
+        // return "0.8.16"
+        .split('+')
+        .next()?;
+
+    Some(version.to_string())
+}
+
+fn parse_foundry_solc_version(foundry_toml: &str) -> Option<String> {
+    let foundry_toml: toml::Value = toml::from_str(foundry_toml).ok()?;
+    let profile = foundry_toml.get("profile")?.get("default")?;
+    let version = profile
+        .get("solc_version")
+        .or_else(|| profile.get("solc"))?
+        .as_str()?;
+
+    // `solc` may also be the path to a compiler
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_foundry_solc_version, parse_solc_version};
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io::{self, Write};
+
+    #[test]
+    fn test_parse_solc_version() {
+        let solc = "\
+solc, the solidity compiler commandline interface
+Version: 0.8.16+commit.07a7930e.Linux.g++";
+        assert_eq!(parse_solc_version(solc), Some("0.8.16".to_string()));
+
+        let solcjs = "0.8.15+commit.e14f2714.Emscripten.clang\n";
+        assert_eq!(parse_solc_version(solcjs), Some("0.8.15".to_string()));
+
+        assert_eq!(parse_solc_version("solc"), None);
+    }
+
+    #[test]
+    fn test_parse_foundry_solc_version() {
+        let foundry_toml = "[profile.default]\nsrc = \"src\"\nsolc_version = \"0.8.19\"\n";
+        assert_eq!(
+            parse_foundry_solc_version(foundry_toml),
+            Some("0.8.19".to_string())
+        );
+
+        let foundry_toml = "[profile.default]\nsolc = \"0.8.17\"\n";
+        assert_eq!(
+            parse_foundry_solc_version(foundry_toml),
+            Some("0.8.17".to_string())
+        );
+
+        let foundry_toml = "[profile.default]\nsolc = \"/usr/bin/solc\"\n";
+        assert_eq!(parse_foundry_solc_version(foundry_toml), None);
+        assert_eq!(parse_foundry_solc_version("[profile.default]\n"), None);
+    }
+
+    #[test]
+    fn folder_without_solidity_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("solidity", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_sol_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Token.sol"))?.sync_all()?;
+        let actual = render_module("solidity", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("S v0.8.16")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn solcjs_compiler() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Token.sol"))?.sync_all()?;
+        let actual = render_module(
+            "solidity",
+            dir.path(),
+            Some(toml::toml! {
+                [solidity]
+                compiler = "solcjs"
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("S v0.8.15")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn forge_compiler() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Token.sol"))?.sync_all()?;
+        let mut foundry_toml = File::create(dir.path().join("foundry.toml"))?;
+        foundry_toml.write_all(b"[profile.default]\nsolc_version = \"0.8.19\"\n")?;
+        foundry_toml.sync_all()?;
+        let actual = render_module(
+            "solidity",
+            dir.path(),
+            Some(toml::toml! {
+                [solidity]
+                compiler = "forge"
+            }),
+        );
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("S v0.8.19")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+
+use super::{Context, Module, RootModuleConfig};
+
+use super::utils::directory::truncate;
+use crate::configs::spack::SpackConfig;
+use crate::formatter::StringFormatter;
+
+/// Creates a module with the current Spack environment
+///
+/// Will display the Spack environment iff `$SPACK_ENV` is set.
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let spack_env = env::var("SPACK_ENV").unwrap_or_else(|_| "".into());
+    if spack_env.trim().is_empty() {
+        return None;
+    }
+
+    let mut module = context.new_module("spack");
+    let config: SpackConfig = SpackConfig::try_load(module.config);
+
+    let spack_env = truncate(spack_env, config.truncation_length);
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "environment" => Some(Ok(spack_env.as_str())),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `spack`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
@@ -1,7 +1,7 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::terraform::TerraformConfig;
-use crate::formatter::StringFormatter;
+use crate::formatter::{StringFormatter, VersionFormatter};
 use crate::utils;
 
 use std::env;
@@ -13,20 +13,24 @@ use std::path::PathBuf;
 /// Will display the Terraform version and workspace if any of the following criteria are met:
 ///     - Current directory contains a `.terraform` directory
 ///     - Current directory contains a file with the `.tf` extension
+///
+/// The `required_version` constraint of the configuration is read from the `.tf` files,
+/// as a faster alternative to running `terraform version`.
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("terraform");
+    let config: TerraformConfig = TerraformConfig::try_load(module.config);
+
     let is_terraform_project = context
-        .try_begin_scan()?
-        .set_folders(&[".terraform"])
-        .set_extensions(&["tf"])
+        .try_begin_module_scan("terraform")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_terraform_project {
         return None;
     }
 
-    let mut module = context.new_module("terraform");
-    let config: TerraformConfig = TerraformConfig::try_load(module.config);
-
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
             .map_meta(|variable, _| match variable {
@@ -39,13 +43,21 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
             })
             .map(|variable| match variable {
                 "version" => format_terraform_version(
-                    &utils::exec_cmd("terraform", &["version"])?.stdout.as_str(),
+                    &context.exec_cmd("terraform", &["version"])?.stdout.as_str(),
                 )
-                .map(Ok),
+                .map(|version| {
+                    let version = VersionFormatter::format_module_version(
+                        module.get_name(),
+                        &version,
+                        config.version_format,
+                    );
+                    Ok(version + " ")
+                }),
+                "required_version" => get_required_version(context).map(Ok),
                 "workspace" => get_terraform_workspace(&context.current_dir).map(Ok),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -74,11 +86,45 @@ fn get_terraform_workspace(cwd: &PathBuf) -> Option<String> {
     };
     match utils::read_file(datadir.join("environment")) {
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => Some("default".to_string()),
-        Ok(s) => Some(s),
+        Ok(s) => Some(s.trim().to_string()),
         _ => None,
     }
 }
 
+/// Find the `required_version` constraint in the `.tf` files of the current directory
+fn get_required_version(context: &Context) -> Option<String> {
+    let mut tf_files = context
+        .dir_contents()
+        .ok()?
+        .files()
+        .filter(|file| file.extension().map_or(false, |ext| ext == "tf"))
+        .collect::<Vec<_>>();
+    // Files are read in the same order as terraform loads them
+    tf_files.sort();
+
+    tf_files
+        .into_iter()
+        .filter_map(|file| utils::read_file(context.current_dir.join(file)).ok())
+        .find_map(|contents| parse_required_version(&contents))
+}
+
+fn parse_required_version(contents: &str) -> Option<String> {
+    // terraform {
+    //   required_version = ">= 0.12, < 0.14"
+    // }
+    contents.lines().find_map(|line| {
+        let constraint = line
+            .trim()
+            .strip_prefix("required_version")?
+            .trim_start()
+            .strip_prefix('=')?
+            .trim()
+            .strip_prefix('"')?;
+        let end = constraint.find('"')?;
+        Some(constraint[..end].to_string() + " ")
+    })
+}
+
 fn format_terraform_version(version: &str) -> Option<String> {
     // `terraform version` output looks like this
     // Terraform v0.12.14
@@ -89,8 +135,8 @@ fn format_terraform_version(version: &str) -> Option<String> {
             .next()?
             .trim_start_matches("Terraform ")
             .trim()
-            .to_owned()
-            + " ",
+            .trim_start_matches('v')
+            .to_owned(),
     )
 }
 
@@ -105,10 +151,7 @@ mod tests {
     #[test]
     fn test_format_terraform_version_release() {
         let input = "Terraform v0.12.14";
-        assert_eq!(
-            format_terraform_version(input),
-            Some("v0.12.14 ".to_string())
-        );
+        assert_eq!(format_terraform_version(input), Some("0.12.14".to_string()));
     }
 
     #[test]
@@ -116,7 +159,7 @@ mod tests {
         let input = "Terraform v0.12.14-rc1";
         assert_eq!(
             format_terraform_version(input),
-            Some("v0.12.14-rc1 ".to_string())
+            Some("0.12.14-rc1".to_string())
         );
     }
 
@@ -125,7 +168,7 @@ mod tests {
         let input = "Terraform v0.12.14-dev (cca89f74)";
         assert_eq!(
             format_terraform_version(input),
-            Some("v0.12.14-dev (cca89f74) ".to_string())
+            Some("0.12.14-dev (cca89f74)".to_string())
         );
     }
 
@@ -137,10 +180,25 @@ Your version of Terraform is out of date! The latest version
 is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
 
 ";
+        assert_eq!(format_terraform_version(input), Some("0.12.13".to_string()));
+    }
+
+    #[test]
+    fn test_parse_required_version() {
+        let input = r#"
+terraform {
+  required_version = ">= 0.12, < 0.14"
+
+  required_providers {
+    aws = ">= 2.7.0"
+  }
+}
+"#;
         assert_eq!(
-            format_terraform_version(input),
-            Some("v0.12.13 ".to_string())
+            parse_required_version(input),
+            Some(">= 0.12, < 0.14 ".to_string())
         );
+        assert_eq!(parse_required_version("provider \"aws\" {}"), None);
     }
 
     #[test]
@@ -191,4 +249,28 @@ is 0.12.14. You can update by downloading from www.terraform.io/downloads.html
         assert_eq!(expected, actual);
         Ok(())
     }
+
+    #[test]
+    fn folder_with_required_version() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut file = File::create(dir.path().join("versions.tf"))?;
+        file.write_all(b"terraform {\n  required_version = \"~> 0.13.0\"\n}\n")?;
+        file.sync_all()?;
+
+        let actual = render_module(
+            "terraform",
+            dir.path(),
+            Some(toml::toml! {
+                [terraform]
+                format = "via [$symbol$required_version$workspace]($style) "
+            }),
+        );
+
+        let expected = Some(format!(
+            "via {} ",
+            Color::Fixed(105).bold().paint("💠 ~> 0.13.0 default")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
 }
@@ -1,3 +1,4 @@
+use chrono::format::{Item, StrftimeItems};
 use chrono::{DateTime, FixedOffset, Local, NaiveTime, Utc};
 
 use super::{Context, Module, RootModuleConfig};
@@ -12,15 +13,31 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         return None;
     };
 
-    // Hide prompt if current time is not inside time_range
+    // Hide prompt if current time is not inside time_range, in the timezone of the
+    // displayed time
     let (display_start, display_end) = parse_time_range(config.time_range);
-    let time_now = Local::now().time();
+    let time_now = match parse_utc_offset(config.utc_time_offset) {
+        Some(timezone_offset) => Utc::now().with_timezone(&timezone_offset).time(),
+        None => Local::now().time(),
+    };
     if !is_inside_time_range(time_now, display_start, display_end) {
         return None;
     }
 
     let default_format = if config.use_12hr { "%r" } else { "%T" };
-    let time_format = config.time_format.unwrap_or(default_format);
+    let time_format = match config.time_format {
+        // chrono panics while formatting a time with an invalid format string
+        Some(time_format) if !is_valid_time_format(time_format) => {
+            log::warn!(
+                "Invalid time_format {:?} provided! Falling back to {:?}.",
+                time_format,
+                default_format
+            );
+            default_format
+        }
+        Some(time_format) => time_format,
+        None => default_format,
+    };
 
     log::trace!(
         "Timer module is enabled with format string: {}",
@@ -51,7 +68,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 "time" => Some(Ok(&formatted_time_string)),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -70,26 +87,54 @@ fn create_offset_time_string(
     utc_time_offset_str: &str,
     time_format: &str,
 ) -> Result<String, &'static str> {
+    match parse_utc_offset(utc_time_offset_str) {
+        Some(timezone_offset) => {
+            log::trace!("Target timezone offset is {}", timezone_offset);
+
+            let target_time = utc_time.with_timezone(&timezone_offset);
+            log::trace!("Time in target timezone now is {}", target_time);
+
+            Ok(format_time_fixed_offset(&time_format, target_time))
+        }
+        None => Err("Invalid timezone offset."),
+    }
+}
+
+/// Parses a UTC offset in hours, either as a number (`-3`, `+5.5`) or as hours and
+/// minutes (`+05:30`). Offsets must be between -24 and 24 hours, excluded.
+fn parse_utc_offset(utc_time_offset_str: &str) -> Option<FixedOffset> {
+    let utc_time_offset_str = utc_time_offset_str.trim();
     // Using floats to allow 30/45 minute offsets: https://www.timeanddate.com/time/time-zones-interesting.html
-    let utc_time_offset_in_hours = match utc_time_offset_str.parse::<f32>() {
-        Ok(parsed_value) => parsed_value,
-        // Passing out of range value to force falling back to "local"
-        Err(_) => 25_f32,
+    let utc_time_offset_in_hours = match utc_time_offset_str.find(':') {
+        Some(colon) => {
+            let hours = utc_time_offset_str[..colon].parse::<i32>().ok()?;
+            let minutes = utc_time_offset_str[colon + 1..].parse::<u32>().ok()?;
+            if minutes >= 60 {
+                return None;
+            }
+            let minutes = minutes as f32 / 60_f32;
+            if utc_time_offset_str.starts_with('-') {
+                hours as f32 - minutes
+            } else {
+                hours as f32 + minutes
+            }
+        }
+        None => utc_time_offset_str.parse::<f32>().ok()?,
     };
-    if utc_time_offset_in_hours < 24_f32 && utc_time_offset_in_hours > -24_f32 {
-        let utc_offset_in_seconds: i32 = (utc_time_offset_in_hours * 3600_f32) as i32;
-        let timezone_offset = FixedOffset::east(utc_offset_in_seconds);
-        log::trace!("Target timezone offset is {}", timezone_offset);
-
-        let target_time = utc_time.with_timezone(&timezone_offset);
-        log::trace!("Time in target timezone now is {}", target_time);
 
-        Ok(format_time_fixed_offset(&time_format, target_time))
+    if utc_time_offset_in_hours < 24_f32 && utc_time_offset_in_hours > -24_f32 {
+        let utc_offset_in_seconds = (utc_time_offset_in_hours * 3600_f32).round() as i32;
+        Some(FixedOffset::east(utc_offset_in_seconds))
     } else {
-        Err("Invalid timezone offset.")
+        None
     }
 }
 
+/// Whether a chrono format string only contains valid specifiers
+fn is_valid_time_format(time_format: &str) -> bool {
+    StrftimeItems::new(time_format).all(|item| item != Item::Error)
+}
+
 /// Format a given time into the given string. This function should be referentially
 /// transparent, which makes it easy to test (unlike anything involving the actual time)
 fn format_time(time_format: &str, local_time: DateTime<Local>) -> String {
@@ -100,7 +145,7 @@ fn format_time_fixed_offset(time_format: &str, utc_time: DateTime<FixedOffset>)
     utc_time.format(time_format).to_string()
 }
 
-/// Returns true if time_now is between time_start and time_end.
+/// Returns true if time_now is between time_start (included) and time_end (excluded).
 /// If one of these values is not given, then it is ignored.
 /// It also handles cases where time_start and time_end have a midnight in between
 fn is_inside_time_range(
@@ -110,13 +155,13 @@ fn is_inside_time_range(
 ) -> bool {
     match (time_start, time_end) {
         (None, None) => true,
-        (Some(i), None) => time_now > i,
+        (Some(i), None) => time_now >= i,
         (None, Some(i)) => time_now < i,
         (Some(i), Some(j)) => {
             if i < j {
-                i < time_now && time_now < j
+                i <= time_now && time_now < j
             } else {
-                time_now > i || time_now < j
+                time_now >= i || time_now < j
             }
         }
     }
@@ -124,6 +169,7 @@ fn is_inside_time_range(
 
 /// Parses the config's time_range field and returns the starting time and ending time.
 /// The range is in the format START_TIME-END_TIME, with START_TIME and END_TIME being optional.
+/// Times are in the 24-hour format, either HH:MM:SS or HH:MM.
 ///
 /// If one of the ranges is invalid or not provided, then the corresponding field in the output
 /// tuple is None
@@ -139,11 +185,14 @@ fn parse_time_range(time_range: &str) -> (Option<NaiveTime>, Option<NaiveTime>)
     let (start, end) = value.split_at(value.find('-').unwrap());
     let end = &end[1..];
 
-    // Parse the ranges
-    let start_time = NaiveTime::parse_from_str(start, "%H:%M:%S").ok();
-    let end_time = NaiveTime::parse_from_str(end, "%H:%M:%S").ok();
+    (parse_range_time(start), parse_range_time(end))
+}
 
-    (start_time, end_time)
+fn parse_range_time(time: &str) -> Option<NaiveTime> {
+    let time = time.trim();
+    NaiveTime::parse_from_str(time, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+        .ok()
 }
 
 /* Because we cannot make acceptance tests for the time module, these unit
@@ -368,6 +417,30 @@ mod tests {
             .expect("Invalid timezone offset.");
     }
 
+    #[test]
+    fn test_create_formatted_time_string_with_hours_and_minutes() {
+        let utc_time: DateTime<Utc> = Utc.ymd(2014, 7, 8).and_hms(15, 36, 47);
+
+        let actual = create_offset_time_string(utc_time, "+05:30", FMT_24).unwrap();
+        assert_eq!(actual, "21:06:47");
+        let actual = create_offset_time_string(utc_time, "-03:45", FMT_24).unwrap();
+        assert_eq!(actual, "11:51:47");
+    }
+
+    #[test]
+    fn test_parse_invalid_utc_offset() {
+        assert_eq!(parse_utc_offset("local"), None);
+        assert_eq!(parse_utc_offset("+05:75"), None);
+        assert_eq!(parse_utc_offset("+24:00"), None);
+    }
+
+    #[test]
+    fn test_is_valid_time_format() {
+        assert!(is_valid_time_format("%d/%m %R"));
+        assert!(is_valid_time_format("%T"));
+        assert!(!is_valid_time_format("%Q"));
+    }
+
     #[test]
     fn test_parse_invalid_time_range() {
         let time_range = "10:00:00-12:00:00-13:00:00";
@@ -410,6 +483,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_time_ranges_without_seconds() {
+        let time_range = "22:30 - 06:00";
+
+        assert_eq!(
+            parse_time_range(time_range),
+            (
+                Some(NaiveTime::from_hms(22, 30, 00)),
+                Some(NaiveTime::from_hms(6, 00, 00))
+            )
+        );
+    }
+
     #[test]
     fn test_is_inside_time_range_with_no_range() {
         let time_start = None;
@@ -464,4 +550,19 @@ mod tests {
         assert_eq!(is_inside_time_range(time_now2, time_start, time_end), false);
         assert_eq!(is_inside_time_range(time_now3, time_start, time_end), true);
     }
+
+    #[test]
+    fn test_is_inside_time_range_at_bounds() {
+        let time_start = Some(NaiveTime::from_hms(22, 00, 00));
+        let time_end = Some(NaiveTime::from_hms(6, 00, 00));
+
+        assert_eq!(
+            is_inside_time_range(NaiveTime::from_hms(22, 00, 00), time_start, time_end),
+            true
+        );
+        assert_eq!(
+            is_inside_time_range(NaiveTime::from_hms(6, 00, 00), time_start, time_end),
+            false
+        );
+    }
 }
@@ -0,0 +1,122 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::typst::TypstConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Typst version
+///
+/// Will display the Typst version if any of the following criteria are met:
+///     - Current directory contains a file with `.typ` extension
+///     - Current directory contains a `typst.toml` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("typst");
+    let config: TypstConfig = TypstConfig::try_load(module.config);
+
+    let is_typst_project = context
+        .try_begin_module_scan("typst")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_typst_project {
+        return None;
+    }
+
+    let typst_version = context.exec_cmd("typst", &["--version"])?.stdout;
+    let module_version = parse_typst_version(&typst_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `typst`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_typst_version(typst_version: &str) -> Option<String> {
+    let version = typst_version
+        // split into ["typst", "0.10.0", "(70ca0d25)"]
+        .split_whitespace()
+        // return "0.10.0"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_typst_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_typst_version() {
+        assert_eq!(
+            parse_typst_version("typst 0.10.0 (70ca0d25)\n"),
+            Some("0.10.0".to_string())
+        );
+        assert_eq!(parse_typst_version("typst"), None);
+    }
+
+    #[test]
+    fn folder_without_typst_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("typst", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_typ_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("paper.typ"))?.sync_all()?;
+        let actual = render_module("typst", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::RGB(0x00, 0x93, 0xA7).bold().paint("t v0.10.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_typst_toml() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("typst.toml"))?.sync_all()?;
+        let actual = render_module("typst", dir.path(), None);
+        let expected = Some(format!(
+            "via {} ",
+            Color::RGB(0x00, 0x93, 0xA7).bold().paint("t v0.10.0")
+        ));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
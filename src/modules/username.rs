@@ -4,34 +4,35 @@ use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::username::UsernameConfig;
 use crate::formatter::StringFormatter;
-use crate::utils;
 
 /// Creates a module with the current user's username
 ///
 /// Will display the username if any of the following criteria are met:
 ///     - The current user isn't the same as the one that is logged in (`$LOGNAME` != `$USER`)
 ///     - The current user is root (UID = 0)
-///     - The user is currently connected as an SSH session (`$SSH_CONNECTION`)
+///     - The user is currently connected as an SSH session (`$SSH_CONNECTION` or `$SSH_TTY`)
+///     - username.show_always is true
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
     let user = env::var("USER").ok();
     let logname = env::var("LOGNAME").ok();
-    let ssh_connection = env::var("SSH_CONNECTION").ok();
-
-    const ROOT_UID: Option<u32> = Some(0);
-    let user_uid = get_uid();
+    let is_root = context.is_root();
 
     let mut module = context.new_module("username");
     let config: UsernameConfig = UsernameConfig::try_load(module.config);
 
-    if user != logname || ssh_connection.is_some() || user_uid == ROOT_UID || config.show_always {
+    // Without `$LOGNAME`, the user can't be told apart from the one that is logged in
+    let is_not_login = logname.is_some() && user != logname;
+
+    if is_not_login || context.is_ssh_session() || is_root || config.show_always {
         let username = user?;
         let parsed = StringFormatter::new(config.format).and_then(|formatter| {
             formatter
                 .map_style(|variable| match variable {
                     "style" => {
-                        let module_style = match user_uid {
-                            Some(0) => config.style_root,
-                            _ => config.style_user,
+                        let module_style = if is_root {
+                            config.style_root
+                        } else {
+                            config.style_user
                         };
                         Some(Ok(module_style))
                     }
@@ -41,7 +42,7 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                     "user" => Some(Ok(&username)),
                     _ => None,
                 })
-                .parse(None)
+                .parse(None, Some(context))
         });
         module.set_segments(match parsed {
             Ok(segments) => segments,
@@ -56,11 +57,3 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
         None
     }
 }
-
-fn get_uid() -> Option<u32> {
-    utils::exec_cmd("id", &["-u"])?
-        .stdout
-        .trim()
-        .parse::<u32>()
-        .ok()
-}
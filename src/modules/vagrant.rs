@@ -0,0 +1,105 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::vagrant::VagrantConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current Vagrant version
+///
+/// Will display the Vagrant version if any of the following criteria are met:
+///     - Current directory contains a `Vagrantfile` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("vagrant");
+    let config: VagrantConfig = VagrantConfig::try_load(module.config);
+
+    let is_vagrant_project = context
+        .try_begin_module_scan("vagrant")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_vagrant_project {
+        return None;
+    }
+
+    let vagrant_version = context.exec_cmd("vagrant", &["--version"])?.stdout;
+    let module_version = parse_vagrant_version(&vagrant_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|variable, _| match variable {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `vagrant`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_vagrant_version(vagrant_version: &str) -> Option<String> {
+    let version = vagrant_version
+        // split into ["Vagrant", "2.2.10"]
+        .split_whitespace()
+        // return "2.2.10"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_vagrant_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_vagrant_version() {
+        assert_eq!(
+            parse_vagrant_version("Vagrant 2.2.10\n"),
+            Some("2.2.10".to_string())
+        );
+        assert_eq!(parse_vagrant_version("Vagrant"), None);
+    }
+
+    #[test]
+    fn folder_without_vagrantfile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("vagrant", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_vagrantfile() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("Vagrantfile"))?.sync_all()?;
+        let actual = render_module("vagrant", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Cyan.bold().paint("⍱ v2.2.10")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
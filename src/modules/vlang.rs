@@ -0,0 +1,126 @@
+use super::{Context, Module, RootModuleConfig};
+
+use crate::configs::vlang::VLangConfig;
+use crate::formatter::{StringFormatter, VersionFormatter};
+
+/// Creates a module with the current V version
+///
+/// Will display the V version if any of the following criteria are met:
+///     - Current directory contains a file with `.v` extension
+///     - Current directory contains a `v.mod` or `vpkg.json` file
+pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("vlang");
+    let config: VLangConfig = VLangConfig::try_load(module.config);
+
+    let is_v_project = context
+        .try_begin_module_scan("vlang")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
+        .is_match();
+
+    if !is_v_project {
+        return None;
+    }
+
+    let v_version = context.exec_cmd("v", &["version"])?.stdout;
+    let module_version = parse_v_version(&v_version)?;
+
+    let parsed = StringFormatter::new(config.format).and_then(|formatter| {
+        formatter
+            .map_meta(|var, _| match var {
+                "symbol" => Some(config.symbol),
+                _ => None,
+            })
+            .map_style(|variable| match variable {
+                "style" => Some(Ok(config.style)),
+                _ => None,
+            })
+            .map(|variable| match variable {
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &module_version,
+                    config.version_format,
+                ))),
+                _ => None,
+            })
+            .parse(None, Some(context))
+    });
+
+    module.set_segments(match parsed {
+        Ok(segments) => segments,
+        Err(error) => {
+            log::warn!("Error in module `vlang`:\n{}", error);
+            return None;
+        }
+    });
+
+    Some(module)
+}
+
+fn parse_v_version(v_version: &str) -> Option<String> {
+    let version = v_version
+        // split into ["V", "0.1.29", "41b6ca5"]
+        .split_whitespace()
+        // return "0.1.29"
+        .nth(1)?;
+
+    Some(version.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_v_version;
+    use crate::modules::utils::test::render_module;
+    use ansi_term::Color;
+    use std::fs::File;
+    use std::io;
+
+    #[test]
+    fn test_parse_v_version() {
+        assert_eq!(
+            parse_v_version("V 0.1.29 41b6ca5"),
+            Some("0.1.29".to_string())
+        );
+        assert_eq!(parse_v_version("V"), None);
+    }
+
+    #[test]
+    fn folder_without_v_files() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let actual = render_module("vlang", dir.path(), None);
+        let expected = None;
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_v_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("hello.v"))?.sync_all()?;
+        let actual = render_module("vlang", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("V v0.1.29")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_vmod_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("v.mod"))?.sync_all()?;
+        let actual = render_module("vlang", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("V v0.1.29")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+
+    #[test]
+    fn folder_with_vpkg_file() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        File::create(dir.path().join("vpkg.json"))?.sync_all()?;
+        let actual = render_module("vlang", dir.path(), None);
+        let expected = Some(format!("via {} ", Color::Blue.bold().paint("V v0.1.29")));
+        assert_eq!(expected, actual);
+        dir.close()
+    }
+}
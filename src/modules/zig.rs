@@ -1,31 +1,35 @@
 use super::{Context, Module, RootModuleConfig};
 
 use crate::configs::zig::ZigConfig;
-use crate::formatter::StringFormatter;
-use crate::utils;
+use crate::formatter::{StringFormatter, VersionFormatter};
 
 /// Creates a module with the current Zig version
 ///
 /// Will display the Zig version if any of the following criteria are met:
 ///     - The current directory contains a file with extension `.zig`
 pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
+    let mut module = context.new_module("zig");
+    let config = ZigConfig::try_load(module.config);
+
     let is_zig_project = context
-        .try_begin_scan()?
-        .set_extensions(&["zig"])
+        .try_begin_module_scan("zig")?
+        .set_files(&config.detect_files)
+        .set_extensions(&config.detect_extensions)
+        .set_folders(&config.detect_folders)
         .is_match();
 
     if !is_zig_project {
         return None;
     }
 
-    let zig_version_output = utils::exec_cmd("zig", &["version"])?
-        .stdout
-        .trim()
-        .to_string();
-    let zig_version = format!("v{}", zig_version_output);
-
-    let mut module = context.new_module("zig");
-    let config = ZigConfig::try_load(module.config);
+    let zig_version_output = match context.get_pinned_version("zig", &["zig"]) {
+        Some(version) => version.to_string(),
+        None => context
+            .exec_cmd("zig", &["version"])?
+            .stdout
+            .trim()
+            .to_string(),
+    };
 
     let parsed = StringFormatter::new(config.format).and_then(|formatter| {
         formatter
@@ -38,10 +42,14 @@ pub fn module<'a>(context: &'a Context) -> Option<Module<'a>> {
                 _ => None,
             })
             .map(|variable| match variable {
-                "version" => Some(Ok(zig_version.clone())),
+                "version" => Some(Ok(VersionFormatter::format_module_version(
+                    module.get_name(),
+                    &zig_version_output,
+                    config.version_format,
+                ))),
                 _ => None,
             })
-            .parse(None)
+            .parse(None, Some(context))
     });
 
     module.set_segments(match parsed {
@@ -0,0 +1,156 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The prompt printed instead of the configured one when rendering it panicked
+pub const FALLBACK_PROMPT: &str = "> ";
+
+thread_local! {
+    /// The module being rendered on the current thread, to tell where a panic happened
+    static CURRENT_MODULE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Replace the default panic hook, which prints to the terminal, with one that appends
+/// the panic to the session log. The prompt is then free to fall back to `FALLBACK_PROMPT`
+/// instead of leaving a backtrace where the prompt should be.
+pub fn install() {
+    panic::set_hook(Box::new(|info| {
+        let module = CURRENT_MODULE.with(|module| module.borrow().clone());
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.as_secs())
+            .unwrap_or_default();
+        let location = info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line()));
+        let message = format_panic(
+            module.as_deref(),
+            location,
+            panic_reason(info.payload()),
+            timestamp,
+        );
+
+        match session_log_path() {
+            Some(path) => {
+                if let Err(error) = append_to_log(&path, &message) {
+                    eprintln!(
+                        "Unable to write to {}: {}\n{}",
+                        path.display(),
+                        error,
+                        message
+                    );
+                }
+            }
+            None => eprintln!("{}", message),
+        }
+    }));
+}
+
+/// Run `render` with `name` as the module any panic on this thread is attributed to
+pub fn in_module<T, F>(name: &str, render: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    let previous = CURRENT_MODULE.with(|module| module.replace(Some(name.to_string())));
+    let result = render();
+    CURRENT_MODULE.with(|module| module.replace(previous));
+    result
+}
+
+/// The log panics are written to, `$STARSHIP_SESSION_LOG` or `session.log` in starship's
+/// cache directory
+pub fn session_log_path() -> Option<PathBuf> {
+    match env::var_os("STARSHIP_SESSION_LOG") {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(dirs_next::cache_dir()?.join("starship").join("session.log")),
+    }
+}
+
+fn append_to_log(path: &Path, message: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", message)
+}
+
+fn panic_reason(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<unknown reason>")
+}
+
+fn format_panic(
+    module: Option<&str>,
+    location: Option<String>,
+    reason: &str,
+    timestamp: u64,
+) -> String {
+    let mut message = format!(
+        "[{}] starship {} panicked",
+        timestamp,
+        clap::crate_version!()
+    );
+    if let Some(module) = module {
+        message.push_str(&format!(" in module `{}`", module));
+    }
+    if let Some(location) = location {
+        message.push_str(&format!(" at {}", location));
+    }
+    message.push_str(&format!(": {}", reason));
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_panic_with_module() {
+        assert_eq!(
+            format_panic(
+                Some("git_status"),
+                Some("src/modules/git_status.rs:42".to_string()),
+                "index out of bounds",
+                1_602_849_600
+            ),
+            format!(
+                "[1602849600] starship {} panicked in module `git_status` at src/modules/git_status.rs:42: index out of bounds",
+                clap::crate_version!()
+            )
+        );
+        assert_eq!(
+            format_panic(None, None, "oops", 0),
+            format!("[0] starship {} panicked: oops", clap::crate_version!())
+        );
+    }
+
+    #[test]
+    fn panic_reason_from_payload() {
+        let literal: Box<dyn Any + Send> = Box::new("literal");
+        let formatted: Box<dyn Any + Send> = Box::new(format!("formatted {}", 1));
+        let other: Box<dyn Any + Send> = Box::new(1);
+        assert_eq!(panic_reason(literal.as_ref()), "literal");
+        assert_eq!(panic_reason(formatted.as_ref()), "formatted 1");
+        assert_eq!(panic_reason(other.as_ref()), "<unknown reason>");
+    }
+
+    #[test]
+    fn in_module_restores_previous_module() {
+        let current = || CURRENT_MODULE.with(|module| module.borrow().clone());
+
+        let inner = in_module("custom.outer", || {
+            in_module("rust", current);
+            current()
+        });
+        assert_eq!(inner, Some("custom.outer".to_string()));
+        assert_eq!(current(), None);
+    }
+}
@@ -1,77 +1,172 @@
-use ansi_term::ANSIStrings;
+use ansi_term::{unstyle, ANSIStrings, Style};
 use clap::ArgMatches;
 use rayon::prelude::*;
 use std::collections::BTreeSet;
 use std::fmt::{self, Debug, Write as FmtWrite};
 use std::io::{self, Write};
-use unicode_width::UnicodeWidthChar;
-
-use crate::configs::PROMPT_ORDER;
-use crate::context::{Context, Shell};
-use crate::formatter::{StringFormatter, VariableHolder};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::config::parse_style_string;
+use crate::configs::{StarshipRootConfig, PROMPT_ORDER};
+use crate::context::{CommandStatus, Context, Shell};
+use crate::formatter::{GroupDecoration, StringFormatter, VariableHolder};
+use crate::graphics;
 use crate::module::Module;
 use crate::module::ALL_MODULES;
 use crate::modules;
+use crate::panic_hook;
 use crate::segment::Segment;
+use crate::utils;
+
+/// Name of the placeholder segment standing in for a module that rendered nothing,
+/// used to find the lines to remove when `collapse_empty_lines` is enabled
+const EMPTY_MODULE_SEGMENT: &str = "_empty_module";
 
 pub fn prompt(args: ArgMatches) {
-    let context = Context::new(args);
+    let start = Instant::now();
+    let metrics_file = args.value_of("metrics_file").map(PathBuf::from);
+    // Neither a panic while loading the context and configuration, nor one in a module
+    // or formatter, must leave the shell without a prompt. The panic itself is written to
+    // the session log by the panic hook, which is only installed here so that other
+    // subcommands still report panics on the terminal.
+    panic_hook::install();
+    let context = panic::catch_unwind(AssertUnwindSafe(|| Context::new(args))).ok();
+    let prompt = context
+        .as_ref()
+        .and_then(|context| panic::catch_unwind(AssertUnwindSafe(|| get_prompt(context))).ok())
+        .unwrap_or_else(|| panic_hook::FALLBACK_PROMPT.to_string());
     let stdout = io::stdout();
     let mut handle = stdout.lock();
-    write!(handle, "{}", get_prompt(context)).unwrap();
+    write!(handle, "{}", prompt).unwrap();
+
+    if let (Some(metrics_file), Some(context)) = (metrics_file, context) {
+        if let Err(error) = context.metrics.append_to(&metrics_file, start.elapsed()) {
+            log::warn!(
+                "Unable to write metrics to {}:\n{}",
+                metrics_file.display(),
+                error
+            );
+        }
+    }
+}
+
+/// How the prompt is encoded, selected with `--escape`
+#[derive(Clone, Copy, PartialEq)]
+enum Escape {
+    /// Escape sequences wrapped for the prompt variable of the shell, like `PS1` in bash
+    Ps1,
+    /// Escape sequences without any shell-specific wrapping
+    Raw,
+    /// Plain text, without any escape sequences
+    Plain,
+}
+
+impl Escape {
+    fn from_context(context: &Context) -> Self {
+        match context.properties.get("escape").map(String::as_str) {
+            Some("raw") => Escape::Raw,
+            Some("none") => Escape::Plain,
+            _ => Escape::Ps1,
+        }
+    }
 }
 
-pub fn get_prompt(context: Context) -> String {
+pub fn get_prompt(context: &Context) -> String {
     let config = context.config.get_root_config();
     let mut buf = String::new();
+    let escape = Escape::from_context(context);
+    let shell = match escape {
+        Escape::Ps1 => context.shell,
+        _ => Shell::Unknown,
+    };
 
     // A workaround for a fish bug (see #739,#279). Applying it to all shells
     // breaks things (see #808,#824,#834). Should only be printed in fish.
-    if let Shell::Fish = context.shell {
+    if let Shell::Fish = shell {
         buf.push_str("\x1b[J"); // An ASCII control code to clear screen
     }
 
-    let formatter = if let Ok(formatter) = StringFormatter::new(config.format) {
+    let formatter = if let Some(formatter) = root_formatter(&config) {
         formatter
     } else {
-        log::error!("Error parsing `format`");
         buf.push_str(">");
         return buf;
     };
     let modules = formatter.get_variables();
+    let mark_empty_modules = config.collapse_empty_lines;
+    let error_overlay = error_style_overlay(context, config.error_style_overlay);
+    // Without a list of modules, the overlay is applied to the whole prompt at once
+    let module_overlay =
+        |module: &str| error_overlay.filter(|_| config.error_style_modules.contains(&module));
+    let formatter = formatter.map_groups(|name| {
+        config.groups.get(name).map(|group| GroupDecoration {
+            prefix: group.prefix.into(),
+            separator: group.separator.into(),
+            suffix: group.suffix.into(),
+        })
+    });
     let formatter = formatter.map_variables_to_segments(|module| {
         // Make $all display all modules
         if module == "all" {
             Some(Ok(PROMPT_ORDER
                 .par_iter()
                 .flat_map(|module| {
-                    handle_module(module, &context, &modules)
-                        .into_iter()
-                        .flat_map(|module| module.segments)
-                        .collect::<Vec<Segment>>()
+                    module_segments(
+                        module,
+                        context,
+                        &modules,
+                        mark_empty_modules,
+                        module_overlay(module),
+                    )
                 })
                 .collect::<Vec<_>>()))
         } else if context.is_module_disabled_in_config(&module) {
-            None
+            if mark_empty_modules {
+                Some(Ok(vec![Segment::new(EMPTY_MODULE_SEGMENT)]))
+            } else {
+                None
+            }
         } else {
             // Get segments from module
-            Some(Ok(handle_module(module, &context, &modules)
-                .into_iter()
-                .flat_map(|module| module.segments)
-                .collect::<Vec<Segment>>()))
+            Some(Ok(module_segments(
+                module,
+                context,
+                &modules,
+                mark_empty_modules,
+                module_overlay(module),
+            )))
         }
     });
 
+    let segments = formatter
+        .parse(None, Some(context))
+        .expect("Unexpected error returned in root format variables");
+
     // Creates a root module and prints it.
     let mut root_module = Module::new("Starship Root", "The root module", None);
-    root_module.set_segments(
-        formatter
-            .parse(None)
-            .expect("Unexpected error returned in root format variables"),
-    );
+    let segments = if config.collapse_empty_lines {
+        collapse_empty_lines(segments)
+    } else {
+        segments
+    };
+    let segments = match error_overlay {
+        Some(overlay) if config.error_style_modules.is_empty() => {
+            apply_style_overlay(segments, overlay)
+        }
+        _ => segments,
+    };
+    root_module.set_segments(match escape {
+        Escape::Plain => segments,
+        _ => graphics::apply_symbol_images(segments, context),
+    });
 
-    let module_strings = root_module.ansi_strings_for_shell(context.shell);
-    write!(buf, "{}", ANSIStrings(&module_strings)).unwrap();
+    let module_strings = root_module.ansi_strings_for_shell(shell);
+    match escape {
+        Escape::Plain => buf.push_str(&unstyle(&ANSIStrings(&module_strings))),
+        _ => write!(buf, "{}", ANSIStrings(&module_strings)).unwrap(),
+    }
 
     buf
 }
@@ -88,6 +183,7 @@ pub fn get_module(module_name: &str, context: Context) -> Option<String> {
 
 pub fn explain(args: ArgMatches) {
     let context = Context::new(args);
+    let ambiguous_width = context.ambiguous_width();
 
     struct ModuleInfo {
         value: String,
@@ -104,7 +200,7 @@ pub fn explain(args: ArgMatches) {
             let value = module.get_segments().join("");
             ModuleInfo {
                 value: ansi_term::ANSIStrings(&module.ansi_strings()).to_string(),
-                value_len: value.chars().count() + count_wide_chars(&value),
+                value_len: value.chars().count() + count_wide_chars(&value, ambiguous_width),
                 desc: module.get_description().to_owned(),
             }
         })
@@ -116,7 +212,7 @@ pub fn explain(args: ArgMatches) {
     for info in &modules {
         max_ansi_module_width = std::cmp::max(
             max_ansi_module_width,
-            info.value.chars().count() + count_wide_chars(&info.value),
+            info.value.chars().count() + count_wide_chars(&info.value, ambiguous_width),
         );
         max_module_width = std::cmp::max(max_module_width, info.value_len);
     }
@@ -127,7 +223,7 @@ pub fn explain(args: ArgMatches) {
 
     println!("\n Here's a breakdown of your prompt:");
     for info in modules {
-        let wide_chars = count_wide_chars(&info.value);
+        let wide_chars = count_wide_chars(&info.value, ambiguous_width);
 
         if let Some(desc_width) = desc_width {
             let wrapped = textwrap::fill(&info.desc, desc_width);
@@ -157,10 +253,9 @@ fn compute_modules<'a>(context: &'a Context) -> Vec<Module<'a>> {
     let mut prompt_order: Vec<Module<'a>> = Vec::new();
 
     let config = context.config.get_root_config();
-    let formatter = if let Ok(formatter) = StringFormatter::new(config.format) {
+    let formatter = if let Some(formatter) = root_formatter(&config) {
         formatter
     } else {
-        log::error!("Error parsing `format`");
         return Vec::new();
     };
     let modules = formatter.get_variables();
@@ -181,6 +276,140 @@ fn compute_modules<'a>(context: &'a Context) -> Vec<Module<'a>> {
     prompt_order
 }
 
+/// Parses the root `format`, expanding the aliases defined in the `aliases` table
+fn root_formatter<'a, 'v>(config: &StarshipRootConfig<'a>) -> Option<StringFormatter<'a, 'v>> {
+    let formatter = StringFormatter::new(config.format)
+        .and_then(|formatter| formatter.map_aliases(|alias| config.aliases.get(alias).copied()));
+    match formatter {
+        Ok(formatter) => Some(formatter),
+        Err(error) => {
+            log::error!("Error parsing `format`:\n{}", error);
+            None
+        }
+    }
+}
+
+/// Renders a module into its segments. If `mark_empty` is set, a module rendering
+/// nothing is replaced by a placeholder segment for `collapse_empty_lines`.
+fn module_segments<'a>(
+    module: &str,
+    context: &'a Context,
+    module_list: &BTreeSet<String>,
+    mark_empty: bool,
+    overlay: Option<Style>,
+) -> Vec<Segment<'a>> {
+    let segments = handle_module(module, context, module_list)
+        .into_iter()
+        .flat_map(|module| module.segments)
+        .collect::<Vec<Segment>>();
+    let segments = match overlay {
+        Some(overlay) => apply_style_overlay(segments, overlay),
+        None => segments,
+    };
+
+    if mark_empty && segments.is_empty() {
+        vec![Segment::new(EMPTY_MODULE_SEGMENT)]
+    } else {
+        segments
+    }
+}
+
+/// Returns the style of `error_style_overlay` if the previous command failed, including
+/// a PowerShell cmdlet which failed without changing the exit-code.
+fn error_style_overlay(context: &Context, overlay: Option<&str>) -> Option<Style> {
+    if context.command_status() != CommandStatus::Success {
+        parse_style_string(overlay?, Some(context))
+    } else {
+        None
+    }
+}
+
+/// Tints segments with the colors and attributes set in `overlay`, keeping the
+/// ones it leaves unset from the segment's own style.
+fn apply_style_overlay(segments: Vec<Segment>, overlay: Style) -> Vec<Segment> {
+    segments
+        .into_iter()
+        .map(|mut segment| {
+            if !segment.value.is_empty() {
+                let style = segment.style.unwrap_or_default();
+                segment.style = Some(Style {
+                    foreground: overlay.foreground.or(style.foreground),
+                    background: overlay.background.or(style.background),
+                    is_bold: style.is_bold || overlay.is_bold,
+                    is_dimmed: style.is_dimmed || overlay.is_dimmed,
+                    is_italic: style.is_italic || overlay.is_italic,
+                    is_underline: style.is_underline || overlay.is_underline,
+                    is_blink: style.is_blink || overlay.is_blink,
+                    is_reverse: style.is_reverse || overlay.is_reverse,
+                    is_hidden: style.is_hidden || overlay.is_hidden,
+                    is_strikethrough: style.is_strikethrough || overlay.is_strikethrough,
+                });
+            }
+            segment
+        })
+        .collect()
+}
+
+/// Removes the lines of the prompt on which every module rendered nothing.
+///
+/// Only lines containing at least one empty module and no other text than whitespace
+/// are removed, so that blank lines put in `format` on purpose are kept.
+fn collapse_empty_lines(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut collapsed = Vec::new();
+    let mut line = Vec::new();
+    let mut line_has_empty_module = false;
+    let mut line_has_content = false;
+
+    for segment in segments {
+        if segment._name == EMPTY_MODULE_SEGMENT {
+            line_has_empty_module = true;
+            continue;
+        }
+
+        let mut rest = segment.value.as_ref();
+        while let Some(index) = rest.find('\n') {
+            let (current, next) = rest.split_at(index + 1);
+            line_has_content |= !current.trim().is_empty();
+            line.push(Segment {
+                value: current.to_string().into(),
+                ..segment.clone()
+            });
+
+            if line_has_content || !line_has_empty_module {
+                collapsed.append(&mut line);
+            } else {
+                line.clear();
+            }
+            line_has_empty_module = false;
+            line_has_content = false;
+            rest = next;
+        }
+
+        if !rest.is_empty() {
+            line_has_content |= !rest.trim().is_empty();
+            line.push(Segment {
+                value: rest.to_string().into(),
+                ..segment.clone()
+            });
+        }
+    }
+
+    collapsed.append(&mut line);
+    collapsed
+}
+
+/// Render a module, recording how long it took in the context's metrics, and
+/// attributing any panic to the module
+fn timed_module<'a, F>(context: &'a Context, name: &str, render: F) -> Option<Module<'a>>
+where
+    F: FnOnce() -> Option<Module<'a>>,
+{
+    let start = Instant::now();
+    let module = panic_hook::in_module(name, render);
+    context.metrics.record_module(name, start.elapsed());
+    module
+}
+
 fn handle_module<'a>(
     module: &str,
     context: &'a Context,
@@ -199,16 +428,42 @@ fn handle_module<'a>(
     if ALL_MODULES.contains(&module) {
         // Write out a module if it isn't disabled
         if !context.is_module_disabled_in_config(module) {
-            modules.push(modules::handle(module, &context));
+            modules.push(timed_module(context, module, || {
+                modules::handle(module, &context)
+            }));
+
+            // Write out all env_var instances, except for those that are explicitly set
+            if module == "env_var" {
+                let instances = context
+                    .config
+                    .get_env_var_modules()
+                    .into_iter()
+                    .filter(|(name, config)| {
+                        should_add_implicit_module("env_var", name, config, &module_list)
+                    })
+                    .map(|(name, _)| {
+                        timed_module(context, &format!("env_var.{}", name), || {
+                            modules::env_var::instance_module(name, &context)
+                        })
+                    });
+                modules.extend(instances);
+            }
         }
+    } else if module.starts_with("env_var.") {
+        // Write out an env_var instance if it exists and isn't disabled
+        modules.push(timed_module(context, module, || {
+            modules::env_var::instance_module(&module[8..], &context)
+        }));
     } else if module == "custom" {
         // Write out all custom modules, except for those that are explicitly set
         if let Some(custom_modules) = context.config.get_custom_modules() {
             let custom_modules = custom_modules
                 .iter()
                 .map(|(custom_module, config)| {
-                    if should_add_implicit_custom_module(custom_module, config, &module_list) {
-                        modules::custom::module(custom_module, &context)
+                    if should_add_implicit_module("custom", custom_module, config, &module_list) {
+                        timed_module(context, &format!("custom.{}", custom_module), || {
+                            modules::custom::module(custom_module, &context)
+                        })
                     } else {
                         None
                     }
@@ -220,7 +475,9 @@ fn handle_module<'a>(
         // Write out a custom module if it isn't disabled (and it exists...)
         match context.is_custom_module_disabled_in_config(&module[7..]) {
             Some(true) => (), // Module is disabled, we don't add it to the prompt
-            Some(false) => modules.push(modules::custom::module(&module[7..], &context)),
+            Some(false) => modules.push(timed_module(context, module, || {
+                modules::custom::module(&module[7..], &context)
+            })),
             None => match context.config.get_custom_modules() {
                 Some(modules) => log::debug!(
                     "prompt_order contains custom module \"{}\", but no configuration was provided. Configuration for the following modules were provided: {:?}",
@@ -244,12 +501,15 @@ fn handle_module<'a>(
     modules.into_iter().flatten().collect()
 }
 
-fn should_add_implicit_custom_module(
-    custom_module: &str,
+/// Whether a custom module or an env_var instance should be written out by `$custom` or
+/// `$env_var`, as it isn't disabled or already written out as `$<kind>.<name>`
+fn should_add_implicit_module(
+    kind: &str,
+    name: &str,
     config: &toml::Value,
     module_list: &BTreeSet<String>,
 ) -> bool {
-    let explicit_module_name = format!("custom.{}", custom_module);
+    let explicit_module_name = format!("{}.{}", kind, name);
     let is_explicitly_specified = module_list.contains(&explicit_module_name);
 
     if is_explicitly_specified {
@@ -266,6 +526,78 @@ fn should_add_implicit_custom_module(
         .unwrap_or(false)
 }
 
-fn count_wide_chars(value: &str) -> usize {
-    value.chars().filter(|c| c.width().unwrap_or(0) > 1).count()
+fn count_wide_chars(value: &str, ambiguous_width: usize) -> usize {
+    value
+        .chars()
+        .filter(|c| utils::char_width(*c, ambiguous_width) > 1)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ansi_term::Color;
+
+    fn segments<'a>(values: &[&'a str]) -> Vec<Segment<'a>> {
+        values
+            .iter()
+            .map(|value| {
+                if *value == EMPTY_MODULE_SEGMENT {
+                    Segment::new(EMPTY_MODULE_SEGMENT)
+                } else {
+                    let mut segment = Segment::new("_text");
+                    segment.set_value(*value);
+                    segment
+                }
+            })
+            .collect()
+    }
+
+    fn render(segments: Vec<Segment>) -> String {
+        segments.iter().map(Segment::get_value).collect()
+    }
+
+    #[test]
+    fn collapse_line_with_only_empty_modules() {
+        let input = segments(&[
+            "~",
+            "\n",
+            EMPTY_MODULE_SEGMENT,
+            " ",
+            EMPTY_MODULE_SEGMENT,
+            "\n",
+            "❯ ",
+        ]);
+        assert_eq!(render(collapse_empty_lines(input)), "~\n❯ ");
+    }
+
+    #[test]
+    fn keep_line_with_rendered_module() {
+        let input = segments(&["~", "\n", EMPTY_MODULE_SEGMENT, "on master", "\n", "❯ "]);
+        assert_eq!(render(collapse_empty_lines(input)), "~\non master\n❯ ");
+    }
+
+    #[test]
+    fn keep_blank_line_without_modules() {
+        let input = segments(&["\n", "~", "\n\n", "❯ "]);
+        assert_eq!(render(collapse_empty_lines(input)), "\n~\n\n❯ ");
+    }
+
+    #[test]
+    fn collapse_line_split_inside_text() {
+        let input = segments(&["~\n", EMPTY_MODULE_SEGMENT, " \n❯ "]);
+        assert_eq!(render(collapse_empty_lines(input)), "~\n❯ ");
+    }
+
+    #[test]
+    fn style_overlay_keeps_unset_attributes() {
+        let mut input = segments(&["~", EMPTY_MODULE_SEGMENT, "❯"]);
+        input[0].set_style(Color::Blue.bold());
+        let overlay = Style::new().on(Color::Red);
+
+        let result = apply_style_overlay(input, overlay);
+        assert_eq!(result[0].style, Some(Color::Blue.bold().on(Color::Red)));
+        assert_eq!(result[1].style, None);
+        assert_eq!(result[2].style, Some(Style::new().on(Color::Red)));
+    }
 }
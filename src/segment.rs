@@ -1,28 +1,40 @@
 use ansi_term::{ANSIString, Style};
+use std::borrow::Cow;
 use std::fmt;
 
 /// A segment is a single configurable element in a module. This will usually
 /// contain a data point to provide context for the prompt's user
 /// (e.g. The version that software is running).
+///
+/// The name and value are borrowed where possible, e.g. for texts from format strings
+/// in the configuration, so that rendering a prompt doesn't copy them.
 #[derive(Clone)]
-pub struct Segment {
+pub struct Segment<'a> {
     /// The segment's name, to be used in configuration and logging.
-    pub _name: String,
+    pub _name: Cow<'a, str>,
 
     /// The segment's style. If None, will inherit the style of the module containing it.
     pub style: Option<Style>,
 
     /// The string value of the current segment.
-    pub value: String,
+    pub value: Cow<'a, str>,
+
+    /// An escape sequence drawing an image before the value, which only contains the
+    /// spaces the image is drawn over. Set for symbols with an image in `symbol_images`.
+    pub image: Option<String>,
 }
 
-impl Segment {
+impl<'a> Segment<'a> {
     /// Creates a new segment with default fields.
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'a, str>>,
+    {
         Self {
-            _name: name.to_string(),
+            _name: name.into(),
             style: None,
-            value: "".to_string(),
+            value: Cow::Borrowed(""),
+            image: None,
         }
     }
 
@@ -45,7 +57,7 @@ impl Segment {
     /// Sets the value of the segment.
     pub fn set_value<T>(&mut self, value: T) -> &mut Self
     where
-        T: Into<String>,
+        T: Into<Cow<'a, str>>,
     {
         self.value = value.into();
         self
@@ -59,18 +71,18 @@ impl Segment {
     // Returns the ANSIString of the segment value, not including its prefix and suffix
     pub fn ansi_string(&self) -> ANSIString {
         match self.style {
-            Some(style) => style.paint(&self.value),
-            None => ANSIString::from(&self.value),
+            Some(style) => style.paint(self.value.as_ref()),
+            None => ANSIString::from(self.value.as_ref()),
         }
     }
 
     /// Determines if the segment contains a value.
     pub fn is_empty(&self) -> bool {
-        self.value.trim().is_empty()
+        self.value.trim().is_empty() && self.image.is_none()
     }
 }
 
-impl fmt::Display for Segment {
+impl<'a> fmt::Display for Segment<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.ansi_string())
     }
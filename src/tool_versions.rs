@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::utils;
+
+/// The files pinning the versions of tools, in order of precedence within a directory
+const TOOL_VERSIONS_FILES: &[&str] = &["mise.toml", ".mise.toml", ".tool-versions"];
+
+/// The versions of tools pinned with asdf's `.tool-versions` or mise's `mise.toml`
+#[derive(Debug, Default)]
+pub struct ToolVersions {
+    /// The pinned versions, with the precedence of the file they come from, lowest first
+    versions: HashMap<String, (usize, String)>,
+}
+
+impl ToolVersions {
+    /// Reads the versions pinned in `dir` and its parents. Like asdf and mise, the version
+    /// of a tool comes from the nearest file pinning it.
+    pub fn from_dir(dir: &Path) -> Self {
+        let mut versions = HashMap::new();
+        let files = dir.ancestors().flat_map(|ancestor| {
+            TOOL_VERSIONS_FILES
+                .iter()
+                .map(move |file_name| (ancestor, *file_name))
+        });
+        for (precedence, (ancestor, file_name)) in files.enumerate() {
+            let contents = match utils::read_file(ancestor.join(file_name)) {
+                Ok(contents) => contents,
+                Err(_) => continue,
+            };
+            let pinned = if file_name.ends_with(".toml") {
+                parse_mise_toml(&contents)
+            } else {
+                parse_tool_versions(&contents)
+            };
+            for (tool, version) in pinned {
+                versions.entry(tool).or_insert((precedence, version));
+            }
+        }
+        ToolVersions { versions }
+    }
+
+    /// The version pinned for any of `tools`, as the same tool may be named differently
+    /// by asdf and mise plugins, e.g. `nodejs` and `node`. `None` if the nearest pin isn't
+    /// a version number, like `system`, `latest` or `ref:<commit>`, which only the tool
+    /// itself can resolve.
+    pub fn get(&self, tools: &[&str]) -> Option<&str> {
+        tools
+            .iter()
+            .filter_map(|tool| self.versions.get(*tool))
+            .min_by_key(|(precedence, _)| *precedence)
+            .map(|(_, version)| version.as_str())
+            .filter(|version| is_pinned_version(version))
+    }
+}
+
+/// Parses `.tool-versions`, made of `<tool> <version> [<fallback version>...]` lines
+fn parse_tool_versions(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.split('#').next()?;
+            let mut words = line.split_whitespace();
+            let tool = words.next()?;
+            let version = words.next()?;
+            Some((tool.to_string(), version.to_string()))
+        })
+        .collect()
+}
+
+/// Parses the `[tools]` table of `mise.toml`, whose values are a version, a list of
+/// versions or a table with a `version`
+fn parse_mise_toml(contents: &str) -> Vec<(String, String)> {
+    let mise_toml: toml::Value = match toml::from_str(contents) {
+        Ok(mise_toml) => mise_toml,
+        Err(error) => {
+            log::warn!("Error parsing mise.toml:\n{}", error);
+            return Vec::new();
+        }
+    };
+    let tools = match mise_toml.get("tools").and_then(toml::Value::as_table) {
+        Some(tools) => tools,
+        None => return Vec::new(),
+    };
+
+    tools
+        .iter()
+        .filter_map(|(tool, value)| {
+            let version = match value {
+                toml::Value::Array(versions) => versions.first()?,
+                toml::Value::Table(table) => table.get("version")?,
+                version => version,
+            };
+            Some((tool.to_string(), version.as_str()?.to_string()))
+        })
+        .collect()
+}
+
+/// Whether a version is a version number, rather than e.g. `system` or `lts`
+fn is_pinned_version(version: &str) -> bool {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    version.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io;
+
+    #[test]
+    fn test_parse_tool_versions() {
+        let contents =
+            "nodejs 14.15.0 12.19.0\n# comment\npython 3.8.6 # trailing\nruby system\n\n";
+        assert_eq!(
+            parse_tool_versions(contents),
+            vec![
+                ("nodejs".to_string(), "14.15.0".to_string()),
+                ("python".to_string(), "3.8.6".to_string()),
+                ("ruby".to_string(), "system".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_mise_toml() {
+        let contents = r#"
+            [env]
+            NODE_ENV = "production"
+
+            [tools]
+            node = "14"
+            python = ["3.8.6", "2.7.18"]
+            ruby = { version = "2.7.2" }
+            go = "latest"
+        "#;
+        let mut tools = parse_mise_toml(contents);
+        tools.sort();
+        assert_eq!(
+            tools,
+            vec![
+                ("go".to_string(), "latest".to_string()),
+                ("node".to_string(), "14".to_string()),
+                ("python".to_string(), "3.8.6".to_string()),
+                ("ruby".to_string(), "2.7.2".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nearest_file_takes_precedence() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let project = dir.path().join("project");
+        fs::create_dir(&project)?;
+        fs::write(
+            dir.path().join(".tool-versions"),
+            "nodejs 12.19.0\npython 3.8.6\nruby 2.7.2\n",
+        )?;
+        fs::write(
+            project.join(".tool-versions"),
+            "nodejs 14.15.0\nruby system\n",
+        )?;
+        fs::write(project.join("mise.toml"), "[tools]\nnode = \"15.0.1\"\n")?;
+
+        let tool_versions = ToolVersions::from_dir(&project);
+        assert_eq!(tool_versions.get(&["nodejs"]), Some("14.15.0"));
+        assert_eq!(tool_versions.get(&["nodejs", "node"]), Some("15.0.1"));
+        assert_eq!(tool_versions.get(&["python"]), Some("3.8.6"));
+        assert_eq!(tool_versions.get(&["ruby"]), None);
+        dir.close()
+    }
+}
@@ -1,7 +1,9 @@
-use std::fs::File;
+use std::env;
+use std::fs::{self, File};
 use std::io::{Read, Result};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use unicode_width::UnicodeWidthChar;
 
 use crate::context::Shell;
 
@@ -32,6 +34,18 @@ pub fn exec_cmd(cmd: &str, args: &[&str]) -> Option<CommandOutput> {
     internal_exec_cmd(&cmd, &args)
 }
 
+/// Check whether a program can be found in `$PATH`, or at the given path if it has one
+#[cfg(not(test))]
+pub fn is_program_available(program: &str) -> bool {
+    internal_is_program_available(program)
+}
+
+/// Programs are mocked by `exec_cmd` in tests, so they are always available
+#[cfg(test)]
+pub fn is_program_available(_program: &str) -> bool {
+    true
+}
+
 #[cfg(test)]
 pub fn exec_cmd(cmd: &str, args: &[&str]) -> Option<CommandOutput> {
     let command = match args.len() {
@@ -39,10 +53,36 @@ pub fn exec_cmd(cmd: &str, args: &[&str]) -> Option<CommandOutput> {
         _ => format!("{} {}", cmd, args.join(" ")),
     };
     match command.as_str() {
+        "buf --version" => Some(CommandOutput {
+            stdout: String::from("0.24.0\n"),
+            stderr: String::default(),
+        }),
+        "cobc -version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+cobc (GnuCOBOL) 3.1-rc1.0
+Built     Aug 02 2020 20:04:12
+Packaged  Jul 01 2020 00:39:43 UTC
+C version \"10.1.0\"\n",
+            ),
+            stderr: String::default(),
+        }),
         "crystal --version" => Some(CommandOutput {
             stdout: String::from("Crystal 0.32.1 (2019-12-18)"),
             stderr: String::default(),
         }),
+        "dart --version" => Some(CommandOutput {
+            stdout: String::default(),
+            stderr: String::from(
+                "Dart VM version: 2.8.4 (stable) (Wed Jun 3 12:26:04 2020 +0200) on \"macos_x64\"",
+            ),
+        }),
+        "direnv status --json" => Some(CommandOutput {
+            stdout: String::from(
+                r#"{"config":{"ConfigDir":"/home/user/.config/direnv"},"state":{"foundRC":{"allowed":0,"path":"/home/user/project/.envrc"},"loadedRC":{"allowed":0,"path":"/home/user/project/.envrc"}}}"#,
+            ),
+            stderr: String::default(),
+        }),
         "dummy_command" => Some(CommandOutput {
             stdout: String::from("stdout ok!"),
             stderr: String::from("stderr ok!"),
@@ -60,14 +100,64 @@ Elixir 1.10 (compiled with Erlang/OTP 22)",
             stdout: String::from("0.19.1"),
             stderr: String::default(),
         }),
+        "fennel --version" => Some(CommandOutput {
+            stdout: String::from("Fennel 1.2.1 on PUC Lua 5.4\n"),
+            stderr: String::default(),
+        }),
+        "flutter --version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+Flutter 3.13.9 • channel stable • https://github.com/flutter/flutter.git
+Framework • revision d211f42860 (2 weeks ago) • 2023-10-25 13:42:25 -0700
+Engine • revision 0545f8705d
+Tools • Dart 3.1.5 • DevTools 2.25.0\n",
+            ),
+            stderr: String::default(),
+        }),
+        "gfortran --version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+GNU Fortran (GCC) 10.2.0
+Copyright (C) 2020 Free Software Foundation, Inc.\n",
+            ),
+            stderr: String::default(),
+        }),
+        "ghc --numeric-version" => Some(CommandOutput {
+            stdout: String::from("8.10.2\n"),
+            stderr: String::default(),
+        }),
+        "gleam --version" => Some(CommandOutput {
+            stdout: String::from("gleam 0.12.1\n"),
+            stderr: String::default(),
+        }),
         "go version" => Some(CommandOutput {
             stdout: String::from("go version go1.12.1 linux/amd64"),
             stderr: String::default(),
         }),
+        "haxe --version" => Some(CommandOutput {
+            stdout: String::from("4.1.4\n"),
+            stderr: String::default(),
+        }),
+        "ifort --version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+ifort (IFORT) 19.1.2.254 20200623
+Copyright (C) 1985-2020 Intel Corporation.  All rights reserved.\n",
+            ),
+            stderr: String::default(),
+        }),
         "julia --version" => Some(CommandOutput {
             stdout: String::from("julia version 1.4.0"),
             stderr: String::default(),
         }),
+        "meson --version" => Some(CommandOutput {
+            stdout: String::from("0.55.3\n"),
+            stderr: String::default(),
+        }),
+        "mojo --version" => Some(CommandOutput {
+            stdout: String::from("mojo 0.3.0 (f64f9601)\n"),
+            stderr: String::default(),
+        }),
         "nim --version" => Some(CommandOutput {
             stdout: String::from(
                 "\
@@ -91,6 +181,10 @@ active boot switches: -d:release\n",
             stdout: String::from("4.08.1"),
             stderr: String::default(),
         }),
+        "opam switch show --safe" => Some(CommandOutput {
+            stdout: String::from("default\n"),
+            stderr: String::default(),
+        }),
         "php -nr echo PHP_MAJOR_VERSION.'.'.PHP_MINOR_VERSION.'.'.PHP_RELEASE_VERSION;" => {
             Some(CommandOutput {
                 stdout: String::from("7.3.8"),
@@ -101,6 +195,10 @@ active boot switches: -d:release\n",
             stdout: String::from("0.13.5"),
             stderr: String::default(),
         }),
+        "pyenv version-name" => Some(CommandOutput {
+            stdout: String::from("system\n"),
+            stderr: String::default(),
+        }),
         "python --version" => Some(CommandOutput {
             stdout: String::from("Python 2.7.17"),
             stderr: String::default(),
@@ -109,16 +207,66 @@ active boot switches: -d:release\n",
             stdout: String::from("Python 3.8.0"),
             stderr: String::default(),
         }),
+        "raku --version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+This is Rakudo version 2020.07 built on MoarVM version 2020.07
+implementing Raku 6.d.\n",
+            ),
+            stderr: String::default(),
+        }),
+        "red --version" => Some(CommandOutput {
+            stdout: String::from("0.6.4 for Linux built 26-Nov-2020/9:08:06+01:00\n"),
+            stderr: String::default(),
+        }),
+        "R --version" => Some(CommandOutput {
+            stdout: String::default(),
+            stderr: String::from(
+                r#"R version 4.0.2 (2020-06-22) -- "Taking Off Again"
+Copyright (C) 2020 The R Foundation for Statistical Computing
+Platform: x86_64-pc-linux-gnu (64-bit)
+
+R is free software and comes with ABSOLUTELY NO WARRANTY.
+You are welcome to redistribute it under the terms of the
+GNU General Public License versions 2 or 3.
+For more information about these matters see
+https://www.gnu.org/licenses/."#,
+            ),
+        }),
         "ruby -v" => Some(CommandOutput {
             stdout: String::from("ruby 2.5.1p57 (2018-03-29 revision 63029) [x86_64-linux-gnu]"),
             stderr: String::default(),
         }),
+        "solc --version" => Some(CommandOutput {
+            stdout: String::from(
+                "\
+solc, the solidity compiler commandline interface
+Version: 0.8.16+commit.07a7930e.Linux.g++\n",
+            ),
+            stderr: String::default(),
+        }),
+        "solcjs --version" => Some(CommandOutput {
+            stdout: String::from("0.8.15+commit.e14f2714.Emscripten.clang\n"),
+            stderr: String::default(),
+        }),
         "stack --no-install-ghc --lock-file read-only ghc -- --numeric-version" => {
             Some(CommandOutput {
                 stdout: String::from("8.6.5"),
                 stderr: String::default(),
             })
         }
+        "typst --version" => Some(CommandOutput {
+            stdout: String::from("typst 0.10.0 (70ca0d25)\n"),
+            stderr: String::default(),
+        }),
+        "vagrant --version" => Some(CommandOutput {
+            stdout: String::from("Vagrant 2.2.10\n"),
+            stderr: String::default(),
+        }),
+        "v version" => Some(CommandOutput {
+            stdout: String::from("V 0.1.29 41b6ca5\n"),
+            stderr: String::default(),
+        }),
         "zig version" => Some(CommandOutput {
             stdout: String::from("0.6.0"),
             stderr: String::default(),
@@ -132,6 +280,10 @@ CMake suite maintained and supported by Kitware (kitware.com/cmake).\n",
             ),
             stderr: String::default(),
         }),
+        "stack ghc -- --numeric-version --no-install-ghc" => Some(CommandOutput {
+            stdout: String::from("8.8.4\n"),
+            stderr: String::default(),
+        }),
         "terraform version" => Some(CommandOutput {
             stdout: String::from("Terraform v0.12.14"),
             stderr: String::default(),
@@ -145,6 +297,32 @@ CMake suite maintained and supported by Kitware (kitware.com/cmake).\n",
     }
 }
 
+/// The number of columns taken by `c` in a terminal. Characters of ambiguous East Asian
+/// width take `ambiguous_width` columns, which is 2 in most CJK locales.
+pub fn char_width(c: char, ambiguous_width: usize) -> usize {
+    let width = if ambiguous_width == 2 {
+        c.width_cjk()
+    } else {
+        c.width()
+    };
+    width.unwrap_or(0)
+}
+
+const BASH_BEG: &str = "\u{5c}\u{5b}"; // \[
+const BASH_END: &str = "\u{5c}\u{5d}"; // \]
+const ZSH_BEG: &str = "\u{25}\u{7b}"; // %{
+const ZSH_END: &str = "\u{25}\u{7d}"; // %}
+
+/// Wraps an entire sequence of unprintable characters in the shell-appropriate wrappers,
+/// for sequences containing several escapes which `wrap_seq_for_shell` can't tell apart.
+pub fn wrap_unprintable_for_shell(seq: &str, shell: Shell) -> String {
+    match shell {
+        Shell::Bash => format!("{}{}{}", BASH_BEG, seq, BASH_END),
+        Shell::Zsh => format!("{}{}{}", ZSH_BEG, seq, ZSH_END),
+        _ => seq.to_string(),
+    }
+}
+
 /// Wraps ANSI color escape sequences in the shell-appropriate wrappers.
 pub fn wrap_colorseq_for_shell(ansi: String, shell: Shell) -> String {
     const ESCAPE_BEGIN: char = '\u{1b}';
@@ -161,11 +339,6 @@ pub fn wrap_seq_for_shell(
     escape_begin: char,
     escape_end: char,
 ) -> String {
-    const BASH_BEG: &str = "\u{5c}\u{5b}"; // \[
-    const BASH_END: &str = "\u{5c}\u{5d}"; // \]
-    const ZSH_BEG: &str = "\u{25}\u{7b}"; // %{
-    const ZSH_END: &str = "\u{25}\u{7d}"; // %}
-
     // ANSI escape codes cannot be nested, so we can keep track of whether we're
     // in an escape or not with a single boolean variable
     let mut escaped = false;
@@ -221,11 +394,69 @@ fn internal_exec_cmd(cmd: &str, args: &[&str]) -> Option<CommandOutput> {
     }
 }
 
+fn internal_is_program_available(program: &str) -> bool {
+    find_program(program).is_some()
+}
+
+/// Find the path of `program` in `$PATH`, or check the given path if it has one
+pub fn find_program(program: &str) -> Option<PathBuf> {
+    let program = Path::new(program);
+    if program.components().count() > 1 {
+        return Some(program.to_path_buf()).filter(|program| is_executable(program));
+    }
+
+    let paths = env::var_os("PATH")?;
+    env::split_paths(&paths).find_map(|dir| {
+        let candidate = dir.join(program);
+        if is_executable(&candidate) {
+            return Some(candidate);
+        }
+
+        // Windows finds programs without their extension, e.g. `node` for `node.exe`
+        if !cfg!(windows) {
+            return None;
+        }
+        env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into())
+            .split(';')
+            .map(|extension| {
+                let mut candidate = candidate.clone().into_os_string();
+                candidate.push(extension);
+                PathBuf::from(candidate)
+            })
+            .find(|candidate| is_executable(candidate))
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path)
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
 #[cfg(test)]
 #[cfg(not(windows))] // While the exec_cmd should work on Windows these tests assume a Unix-like environment.
 mod tests {
     use super::*;
 
+    #[test]
+    fn ambiguous_char_width() {
+        // Box drawing characters are ambiguous, CJK ideographs are always wide
+        assert_eq!(char_width('─', 1), 1);
+        assert_eq!(char_width('─', 2), 2);
+        assert_eq!(char_width('分', 1), 2);
+        assert_eq!(char_width('分', 2), 2);
+        assert_eq!(char_width('a', 2), 1);
+    }
+
     #[test]
     fn exec_mocked_command() {
         let result = exec_cmd("dummy_command", &[]);
@@ -237,6 +468,22 @@ mod tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn program_available() {
+        assert!(internal_is_program_available("sh"));
+        assert!(internal_is_program_available("/bin/sh"));
+    }
+
+    #[test]
+    fn program_not_available() {
+        assert!(!internal_is_program_available(
+            "this_command_does_not_exist"
+        ));
+        assert!(!internal_is_program_available(
+            "/this/command/does_not_exist"
+        ));
+    }
+
     #[test]
     fn exec_no_output() {
         let result = internal_exec_cmd("true", &[]);
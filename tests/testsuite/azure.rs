@@ -0,0 +1,85 @@
+use std::fs::File;
+use std::io::{self, Write};
+
+use ansi_term::Color;
+use tempfile::TempDir;
+
+use crate::common::{self, TestCommand};
+
+const AZURE_PROFILE: &str = "\u{feff}{
+  \"installationId\": \"3deacd2a-b9db-77e1-aa42-23e2f8dfffc3\",
+  \"subscriptions\": [
+    {
+      \"id\": \"f568c543-d12e-de0b-3d85-69843598b565\",
+      \"name\": \"Contoso Corporation - Engineering - Pay-As-You-Go\",
+      \"state\": \"Enabled\",
+      \"user\": { \"name\": \"user@contoso.com\", \"type\": \"user\" },
+      \"isDefault\": true
+    },
+    {
+      \"id\": \"2b2b2bd1-c5ed-4c3e-9b12-8c2d0d5eb1b4\",
+      \"name\": \"Sandbox\",
+      \"state\": \"Enabled\",
+      \"user\": { \"name\": \"user@contoso.com\", \"type\": \"user\" },
+      \"isDefault\": false
+    }
+  ]
+}";
+
+fn azure_config_dir(profile: &str) -> io::Result<TempDir> {
+    let dir = tempfile::tempdir()?;
+    let mut file = File::create(dir.path().join("azureProfile.json"))?;
+    file.write_all(profile.as_bytes())?;
+    file.sync_all()?;
+    Ok(dir)
+}
+
+#[test]
+fn subscription_set() -> io::Result<()> {
+    let dir = azure_config_dir(AZURE_PROFILE)?;
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", dir.path())
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        Color::Blue
+            .bold()
+            .paint("ﴃ Contoso Corporation - Engineering - Pay-As-You-Go")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn subscription_with_alias_and_username() -> io::Result<()> {
+    let dir = azure_config_dir(AZURE_PROFILE)?;
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", dir.path())
+        .use_config(toml::toml! {
+            [azure]
+            format = "on [$symbol$subscription as $username]($style) "
+            [azure.subscription_aliases]
+            "Contoso Corporation - Engineering - Pay-As-You-Go" = "eng"
+        })
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        Color::Blue.bold().paint("ﴃ eng as user@contoso.com")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn no_default_subscription() -> io::Result<()> {
+    let dir = azure_config_dir("{\"subscriptions\": []}")?;
+    let output = common::render_module("azure")
+        .env("AZURE_CONFIG_DIR", dir.path())
+        .output()?;
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
@@ -38,6 +38,49 @@ fn failure_status() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn powershell_cmdlet_failure() -> io::Result<()> {
+    let expected = format!("{} ", Color::Yellow.bold().paint("❯"));
+
+    let output = common::render_module("character")
+        .arg("--status=0")
+        .arg("--cmd-success=False")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn powershell_native_failure() -> io::Result<()> {
+    let expected = format!("{} ", Color::Red.bold().paint("❯"));
+
+    let output = common::render_module("character")
+        .arg("--status=1")
+        .arg("--cmd-success=False")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn powershell_success_after_native_failure() -> io::Result<()> {
+    let expected = format!("{} ", Color::Green.bold().paint("❯"));
+
+    // `$LASTEXITCODE` is left over from an earlier native command
+    let output = common::render_module("character")
+        .arg("--status=1")
+        .arg("--cmd-success=True")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
 #[test]
 fn custom_symbol() -> io::Result<()> {
     let expected_fail = format!("{} ", Color::Red.bold().paint("✖"));
@@ -76,6 +119,52 @@ fn custom_symbol() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn ssh_symbol() -> io::Result<()> {
+    // The remote root symbol is used instead when the tests run as root
+    let is_root = std::process::Command::new("id")
+        .arg("-u")
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "0")
+        .unwrap_or(false);
+    let expected = if is_root {
+        format!("{} ", Color::Red.bold().paint("#"))
+    } else {
+        format!("{} ", Color::Yellow.bold().paint("»"))
+    };
+
+    let output = common::render_module("character")
+        .use_config(toml::toml! {
+            [character]
+            ssh_symbol = "[»](bold yellow)"
+            ssh_root_symbol = "[#](bold red)"
+        })
+        .env("SSH_CONNECTION", "192.168.223.17 36673 192.168.223.229 22")
+        .arg("--status=1")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn ssh_symbol_without_ssh_session() -> io::Result<()> {
+    let expected = format!("{} ", Color::Green.bold().paint("❯"));
+
+    let output = common::render_module("character")
+        .use_config(toml::toml! {
+            [character]
+            ssh_symbol = "[»](bold yellow)"
+        })
+        .arg("--status=0")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
 #[test]
 fn zsh_keymap() -> io::Result<()> {
     let expected_vicmd = format!("{} ", Color::Green.bold().paint("❮"));
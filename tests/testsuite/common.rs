@@ -16,7 +16,7 @@ const EXE_PATH: &str = "./target/debug/starship.exe";
 const EXE_PATH: &str = "./target/debug/starship";
 
 /// Render the full starship prompt
-pub fn _render_prompt() -> process::Command {
+pub fn render_prompt() -> process::Command {
     let mut command = process::Command::new(EXE_PATH);
 
     command
@@ -43,6 +43,22 @@ pub fn render_module(module_name: &str) -> process::Command {
     command
 }
 
+/// Diff the prompts rendered with two configuration files
+pub fn render_diff(old_config: &Path, new_config: &Path) -> process::Command {
+    let mut command = process::Command::new(EXE_PATH);
+
+    command
+        .arg("diff")
+        .arg("--config")
+        .arg(old_config)
+        .arg("--config")
+        .arg(new_config)
+        .env_clear()
+        .env("PATH", env!("PATH")); // Provide the $PATH variable so that external programs are runnable
+
+    command
+}
+
 /// Create a repo from the fixture to be used in git module tests
 /// Please delete the returned directory manually after usage with `remove_dir_all::remove_dir_all`
 pub fn create_fixture_repo() -> io::Result<PathBuf> {
@@ -1,7 +1,7 @@
 use ansi_term::Color;
 use std::io;
 
-use crate::common;
+use crate::common::{self, TestCommand};
 
 #[test]
 fn not_in_env() -> io::Result<()> {
@@ -39,3 +39,46 @@ fn truncate() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+fn ignore_base() -> io::Result<()> {
+    let output = common::render_module("conda")
+        .env("CONDA_DEFAULT_ENV", "base")
+        .output()?;
+
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn show_base() -> io::Result<()> {
+    let output = common::render_module("conda")
+        .env("CONDA_DEFAULT_ENV", "base")
+        .use_config(toml::toml! {
+            [conda]
+            ignore_base = false
+        })
+        .output()?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🅒 base"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn truncate_windows_path() -> io::Result<()> {
+    let output = common::render_module("conda")
+        .env("CONDA_DEFAULT_ENV", "C:\\Users\\astronaut\\envs\\my_env")
+        .output()?;
+
+    let expected = format!("via {} ", Color::Green.bold().paint("🅒 my_env"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
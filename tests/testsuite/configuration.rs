@@ -1,5 +1,5 @@
-use ansi_term::Color;
-use std::io;
+use ansi_term::{Color, Style};
+use std::{fs, io, process};
 
 use crate::common::{self, TestCommand};
 
@@ -18,3 +18,406 @@ fn char_symbol_configuration() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn screen_reader_character() -> io::Result<()> {
+    let output = common::render_module("character")
+        .arg("--status=1")
+        .use_config(toml::toml! {
+            screen_reader = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("last command failed > ", actual);
+
+    Ok(())
+}
+
+#[test]
+fn screen_reader_jobs() -> io::Result<()> {
+    let output = common::render_module("jobs")
+        .arg("--jobs=1")
+        .use_config(toml::toml! {
+            screen_reader = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("1 jobs, ", actual);
+
+    Ok(())
+}
+
+#[test]
+fn screen_reader_keeps_other_options() -> io::Result<()> {
+    let output = common::render_module("cmd_duration")
+        .arg("--cmd-duration=5000")
+        .use_config(toml::toml! {
+            screen_reader = true
+            [cmd_duration]
+            min_time = 10000
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+
+    let output = common::render_module("cmd_duration")
+        .arg("--cmd-duration=5000")
+        .use_config(toml::toml! {
+            screen_reader = true
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("took 5s, ", actual);
+
+    Ok(())
+}
+
+#[test]
+fn collapse_empty_lines() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--jobs=0")
+        .use_config(toml::toml! {
+            collapse_empty_lines = true
+            format = "\nfirst\n$jobs $cmd_duration\nlast"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("\nfirst\nlast", actual);
+
+    Ok(())
+}
+
+#[test]
+fn keep_empty_lines_by_default() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--jobs=0")
+        .use_config(toml::toml! {
+            format = "\nfirst\n$jobs $cmd_duration\nlast"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("\nfirst\n \nlast", actual);
+
+    Ok(())
+}
+
+#[test]
+fn state_symbols_character() -> io::Result<()> {
+    let output = common::render_module("character")
+        .arg("--status=1")
+        .use_config(toml::toml! {
+            [state_symbols]
+            success = ">"
+            error = "x"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("{} ", Color::Red.bold().paint("x"));
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn config_references() -> io::Result<()> {
+    let output = common::render_module("character")
+        .arg("--status=0")
+        .use_config(toml::toml! {
+            [base]
+            symbol = ">"
+
+            [character]
+            success_symbol = "[${config:base.symbol}](bold green)"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("{} ", Color::Green.bold().paint(">"));
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn format_aliases() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--cmd-duration=5000")
+        .use_config(toml::toml! {
+            format = "$status|"
+            [aliases]
+            status = "[took $duration](bold)"
+            duration = "$cmd_duration"
+            [cmd_duration]
+            format = "$duration"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("{}|", Style::new().bold().paint("took 5s"));
+    assert_eq!(expected, actual);
+
+    Ok(())
+}
+
+#[test]
+fn format_alias_cycle() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "$lang"
+            [aliases]
+            lang = "$rust$tools"
+            tools = "$lang"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(">", actual);
+
+    Ok(())
+}
+
+#[test]
+fn error_style_overlay() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--status=1")
+        .use_config(toml::toml! {
+            format = "[a](red)"
+            error_style_overlay = "bg:blue"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(Color::Red.on(Color::Blue).paint("a").to_string(), actual);
+
+    Ok(())
+}
+
+#[test]
+fn error_style_overlay_after_success() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--status=0")
+        .use_config(toml::toml! {
+            format = "[a](red)"
+            error_style_overlay = "bg:blue"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(Color::Red.paint("a").to_string(), actual);
+
+    Ok(())
+}
+
+#[test]
+fn group_with_members() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--jobs=0")
+        .arg("--cmd-duration=5000")
+        .use_config(toml::toml! {
+            format = "$[info](jobs cmd_duration)|"
+            [groups.info]
+            prefix = "<"
+            separator = ","
+            suffix = ">"
+            [cmd_duration]
+            format = "$duration"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("<5s>|", actual);
+
+    Ok(())
+}
+
+#[test]
+fn group_without_members() -> io::Result<()> {
+    let output = common::render_prompt()
+        .arg("--jobs=0")
+        .arg("--cmd-duration=0")
+        .use_config(toml::toml! {
+            format = "$[info](jobs cmd_duration)|"
+            [groups.info]
+            prefix = "<"
+            suffix = ">"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("|", actual);
+
+    Ok(())
+}
+
+#[test]
+fn metrics_file_is_appended() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let metrics_file = dir.path().join("metrics.prom");
+
+    for _ in 0..2 {
+        common::render_prompt()
+            .arg("--cmd-duration=5000")
+            .arg(format!("--metrics-file={}", metrics_file.display()))
+            .use_config(toml::toml! {
+                format = "$cmd_duration"
+            })
+            .output()?;
+    }
+
+    let metrics = std::fs::read_to_string(&metrics_file)?;
+    let count = |prefix: &str| metrics.lines().filter(|l| l.starts_with(prefix)).count();
+    assert_eq!(
+        count("starship_module_duration_seconds{module=\"cmd_duration\"} "),
+        2
+    );
+    assert_eq!(count("starship_prompt_duration_seconds "), 2);
+    dir.close()
+}
+
+#[test]
+fn min_contrast_adjusts_foreground() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "[x](fg:blue bg:black)"
+            min_contrast = 4.5
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_ne!(Color::Blue.on(Color::Black).paint("x").to_string(), actual);
+    assert!(actual.contains("\u{1b}[40;38;2;"));
+
+    Ok(())
+}
+
+#[test]
+fn symbol_images() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let image = dir.path().join("logo.png");
+    std::fs::write(&image, b"png")?;
+
+    let config = |protocol: &str| -> toml::Value {
+        toml::from_str(&format!(
+            "format = \"[X ](bold)\"\ngraphics_protocol = \"{}\"\n[symbol_images]\nX = '{}'\n",
+            protocol,
+            image.display()
+        ))
+        .unwrap()
+    };
+
+    let output = common::render_prompt()
+        .use_config(config("iterm2"))
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!(
+        "\u{1b}7\u{1b}]1337;File=inline=1;width=2;height=1;preserveAspectRatio=1:cG5n\u{7}\u{1b}8{}",
+        Style::new().bold().paint("   ")
+    );
+    assert_eq!(expected, actual);
+
+    // Without a protocol, the symbol is shown as text
+    let output = common::render_prompt()
+        .use_config(config("none"))
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(Style::new().bold().paint("X ").to_string(), actual);
+
+    dir.close()
+}
+
+#[test]
+fn escape_encodings() -> io::Result<()> {
+    let render = |escape: Option<&str>| -> io::Result<String> {
+        let mut command = common::render_prompt();
+        command
+            .env("STARSHIP_SHELL", "bash")
+            .use_config(toml::toml! {
+                format = "[>](bold green)"
+            });
+        if let Some(escape) = escape {
+            command.arg(format!("--escape={}", escape));
+        }
+        let output = command.output()?;
+        Ok(String::from_utf8(output.stdout).unwrap())
+    };
+
+    let raw = Color::Green.bold().paint(">").to_string();
+    let ps1 = raw.replace("\u{1b}", "\\[\u{1b}").replace("m", "m\\]");
+    assert_eq!(ps1, render(None)?);
+    assert_eq!(ps1, render(Some("ps1"))?);
+    assert_eq!(raw, render(Some("raw"))?);
+    assert_eq!(">", render(Some("none"))?);
+
+    Ok(())
+}
+
+#[test]
+fn ambiguous_width_alignment() -> io::Result<()> {
+    let render = |ambiguous_width: i64| -> io::Result<String> {
+        let output = common::render_prompt()
+            .env("STARSHIP_TEST_VALUE", "─§")
+            .use_config(toml::toml! {
+                format = "${env_var:4}|"
+                ambiguous_width = ambiguous_width
+                [env_var]
+                variable = "STARSHIP_TEST_VALUE"
+                format = "$env_value"
+            })
+            .output()?;
+        Ok(String::from_utf8(output.stdout).unwrap())
+    };
+
+    assert_eq!("─§  |", render(1)?);
+    assert_eq!("─§|", render(2)?);
+
+    Ok(())
+}
+
+#[test]
+fn diff_configs() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let old_config = dir.path().join("old.toml");
+    let new_config = dir.path().join("new.toml");
+    fs::write(&old_config, "format = \"$character\"")?;
+    fs::write(
+        &new_config,
+        "format = \"$character\"\n[character]\nerror_symbol = \"[x](bold red)\"",
+    )?;
+
+    let output = common::render_diff(&old_config, &new_config).output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let success = Color::Green.bold().paint("❯");
+    let expected = format!(
+        "--- {}\n+++ {}\n@@ success @@\n {} \n@@ error @@\n-{} \n+{} \n",
+        old_config.display(),
+        new_config.display(),
+        success,
+        Color::Red.bold().paint("❯"),
+        Color::Red.bold().paint("x"),
+    );
+    assert!(actual.starts_with(&expected), "{}", actual);
+
+    dir.close()
+}
+
+#[test]
+#[cfg(unix)]
+fn fallback_prompt_when_context_panics() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let config_path = dir.path().join("starship.toml");
+    let log_path = dir.path().join("session.log");
+    let deleted_dir = dir.path().join("deleted");
+    fs::write(&config_path, "")?;
+    fs::create_dir(&deleted_dir)?;
+    let starship = fs::canonicalize("./target/debug/starship")?;
+
+    // Without $PWD, the current directory can't be found once it has been removed
+    let output = process::Command::new("sh")
+        .arg("-c")
+        .arg("cd \"$1\" && rmdir \"$1\" && unset PWD && exec \"$2\" prompt")
+        .arg("sh")
+        .arg(&deleted_dir)
+        .arg(&starship)
+        .env_clear()
+        .env("PATH", env!("PATH"))
+        .env("STARSHIP_CONFIG", &config_path)
+        .env("STARSHIP_SESSION_LOG", &log_path)
+        .output()?;
+
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("> ", actual);
+    assert!(fs::read_to_string(&log_path)?.contains("Unable to identify current directory"));
+    dir.close()
+}
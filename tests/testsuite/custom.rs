@@ -0,0 +1,109 @@
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn when_output_in_command() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${custom.test}"
+            [custom.test]
+            when = "echo world"
+            command = "echo hello $STARSHIP_WHEN_OUTPUT"
+            format = "$output"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("hello world", actual);
+
+    Ok(())
+}
+
+#[test]
+fn when_output_is_not_run_as_command() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${custom.test}"
+            [custom.test]
+            when = "echo '; echo pwned'"
+            command = "echo \"hello $STARSHIP_WHEN_OUTPUT\""
+            format = "$output"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("hello ; echo pwned", actual);
+
+    Ok(())
+}
+
+#[test]
+fn use_when_output() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${custom.test}"
+            [custom.test]
+            when = "echo world"
+            use_when_output = true
+            format = "$output"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("world", actual);
+
+    Ok(())
+}
+
+#[test]
+fn failing_when_hides_module() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${custom.test}"
+            [custom.test]
+            when = "exit 1"
+            use_when_output = true
+            format = "$output"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+
+    Ok(())
+}
+
+#[test]
+fn when_exit_codes() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${custom.vpn}"
+            [custom.vpn]
+            when = "exit 2"
+            when_exit_codes = [0, 2]
+            command = "echo vpn"
+            format = "$output:$when_status"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("vpn:2", actual);
+
+    Ok(())
+}
+
+#[test]
+#[cfg(not(windows))]
+fn sandbox_strips_environment() -> io::Result<()> {
+    let output = common::render_prompt()
+        .env("API_TOKEN", "secret")
+        .use_config(toml::toml! {
+            format = "${custom.test}"
+            [custom.test]
+            when = "true"
+            command = "echo [$API_TOKEN]"
+            sandbox = true
+            format = "$output"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("[]", actual);
+
+    Ok(())
+}
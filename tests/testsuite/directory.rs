@@ -47,6 +47,45 @@ fn substituted_truncated_path() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn repo_is_only_looked_up_when_truncating_to_repo() -> io::Result<()> {
+    let tmp_dir = tempfile::tempdir()?;
+    let metrics_file = tmp_dir.path().join("metrics.prom");
+
+    let configs = vec![
+        (
+            false,
+            toml::toml! {
+                format = "$directory"
+                [directory]
+                truncate_to_repo = false
+            },
+        ),
+        (
+            true,
+            toml::toml! {
+                format = "$directory"
+                [directory]
+                truncate_to_repo = true
+            },
+        ),
+    ];
+
+    for (truncate_to_repo, config) in configs {
+        common::render_prompt()
+            .arg("--path=/")
+            .arg(format!("--metrics-file={}", metrics_file.display()))
+            .use_config(config)
+            .output()?;
+
+        let metrics = fs::read_to_string(&metrics_file)?;
+        fs::remove_file(&metrics_file)?;
+        let looked_up = metrics.contains("{cache=\"repo\"}");
+        assert_eq!(truncate_to_repo, looked_up);
+    }
+    tmp_dir.close()
+}
+
 #[test]
 fn strange_substitution() -> io::Result<()> {
     let strange_sub = "/\\/;,!";
@@ -320,6 +359,32 @@ fn directory_in_git_repo() -> io::Result<()> {
     tmp_dir.close()
 }
 
+#[test]
+#[ignore]
+fn repo_name_in_git_repo() -> io::Result<()> {
+    let tmp_dir = TempDir::new_in(home_dir().unwrap())?;
+    let repo_dir = tmp_dir.path().join("rocket-controls");
+    let dir = repo_dir.join("src");
+    fs::create_dir_all(&dir)?;
+    let repo = Repository::init(&repo_dir).unwrap();
+    repo.remote("origin", "git@github.com:starship/rocket-launcher.git")
+        .unwrap();
+
+    let output = common::render_module("directory")
+        .use_config(toml::toml! {
+            [directory]
+            format = "$repo_name:$path "
+        })
+        .arg("--path")
+        .arg(dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "rocket-launcher:rocket-controls/src ";
+    assert_eq!(expected, actual);
+    tmp_dir.close()
+}
+
 #[test]
 #[ignore]
 fn truncated_directory_in_git_repo() -> io::Result<()> {
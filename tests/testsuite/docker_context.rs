@@ -0,0 +1,74 @@
+use ansi_term::Color;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::common::{self, TestCommand};
+
+fn write_docker_config(dir: &Path, context: &str) -> io::Result<()> {
+    let mut file = File::create(dir.join("config.json"))?;
+    write!(file, "{{\"currentContext\": \"{}\"}}", context)?;
+    file.sync_all()
+}
+
+#[test]
+fn folder_without_docker_files() -> io::Result<()> {
+    let config_dir = tempfile::tempdir()?;
+    write_docker_config(config_dir.path(), "starship")?;
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("docker_context")
+        .env("DOCKER_CONFIG", config_dir.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()?;
+    config_dir.close()
+}
+
+#[test]
+fn folder_with_compose_yaml() -> io::Result<()> {
+    let config_dir = tempfile::tempdir()?;
+    write_docker_config(config_dir.path(), "starship")?;
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("compose.yaml"))?.sync_all()?;
+
+    let output = common::render_module("docker_context")
+        .env("DOCKER_CONFIG", config_dir.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("🐳 starship"));
+    assert_eq!(expected, actual);
+    dir.close()?;
+    config_dir.close()
+}
+
+#[test]
+fn default_context() -> io::Result<()> {
+    let config_dir = tempfile::tempdir()?;
+    write_docker_config(config_dir.path(), "default")?;
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("docker_context")
+        .env("DOCKER_CONFIG", config_dir.path())
+        .use_config(toml::toml! {
+            [docker_context]
+            only_with_files = false
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()?;
+    config_dir.close()
+}
@@ -1,4 +1,4 @@
-use super::common;
+use crate::common::{self, TestCommand};
 use regex::Regex;
 use std::fs::{DirBuilder, OpenOptions};
 use std::io::{self, Error, ErrorKind, Write};
@@ -161,6 +161,54 @@ fn shows_multiple_tfms() -> io::Result<()> {
     workspace.close()
 }
 
+#[test]
+fn shows_latest_sdk_from_dotnet_root() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    touch_path(&workspace, "project.csproj", None)?;
+    let dotnet_root = tempfile::tempdir()?;
+    for sdk in &["3.1.402", "5.0.100-rc.2.20479.15", "2.1.811"] {
+        DirBuilder::new()
+            .recursive(true)
+            .create(dotnet_root.path().join("sdk").join(sdk))?;
+    }
+
+    let output = common::render_module("dotnet")
+        .env("DOTNET_ROOT", dotnet_root.path())
+        .current_dir(workspace.path())
+        .use_config(toml::toml! {
+            [dotnet]
+            format = "$version"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(actual, "v5.0.100-rc.2.20479.15");
+    dotnet_root.close()?;
+    workspace.close()
+}
+
+#[test]
+fn shows_pinned_symbol_with_global_json() -> io::Result<()> {
+    let workspace = create_workspace(false)?;
+    touch_path(
+        &workspace,
+        "global.json",
+        Some(&make_pinned_sdk_json(DOTNET_PINNED_VERSION)),
+    )?;
+
+    let output = common::render_module("dotnet")
+        .current_dir(workspace.path())
+        .use_config(toml::toml! {
+            [dotnet]
+            format = "$pinned$version"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(actual, "📌 v1.2.3");
+    workspace.close()
+}
+
 fn create_workspace(is_repo: bool) -> io::Result<TempDir> {
     let repo_dir = tempfile::tempdir()?;
 
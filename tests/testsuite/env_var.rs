@@ -135,6 +135,94 @@ fn suffix() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn instance_with_own_options() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "$env_var"
+            [env_var.STAGE]
+            symbol = "▲ "
+            style = "red"
+            format = "on [$symbol$env_value]($style) "
+        })
+        .env("STAGE", "production")
+        .output()?;
+    let expected = format!("on {} ", Color::Red.paint("▲ production"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn instance_default_takes_effect() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "$env_var"
+            [env_var.KUBECONFIG]
+            default = "no cluster"
+        })
+        .output()?;
+    let expected = format!("with {} ", style().paint("no cluster"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn instance_with_variable_and_top_level_module() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "$env_var"
+            [env_var]
+            variable = "TEST_VAR"
+            format = "[$env_value]($style) "
+            [env_var.stage]
+            variable = "STAGE"
+            format = "[$env_value]($style) "
+        })
+        .env("TEST_VAR", TEST_VAR_VALUE)
+        .env("STAGE", "production")
+        .output()?;
+    let expected = format!(
+        "{} {} ",
+        style().paint(TEST_VAR_VALUE),
+        style().paint("production")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn explicit_instance_is_not_repeated() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${env_var.STAGE}|$env_var"
+            [env_var.STAGE]
+            format = "$env_value"
+        })
+        .env("STAGE", "production")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("production|", actual);
+    Ok(())
+}
+
+#[test]
+fn disabled_instance() -> io::Result<()> {
+    let output = common::render_prompt()
+        .use_config(toml::toml! {
+            format = "${env_var.STAGE}|$env_var"
+            [env_var.STAGE]
+            disabled = true
+        })
+        .env("STAGE", "production")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("|", actual);
+    Ok(())
+}
+
 fn style() -> Style {
     // default style
     Color::Black.bold().dimmed()
@@ -0,0 +1,94 @@
+use std::fs::{self, File};
+use std::io::{self, Write};
+
+use ansi_term::Color;
+use tempfile::TempDir;
+
+use crate::common::{self, TestCommand};
+
+/// Create a gcloud configuration directory with `config` as the active configuration
+fn gcloud_config_dir(config: &str) -> io::Result<TempDir> {
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join("configurations"))?;
+
+    let mut active_config = File::create(dir.path().join("active_config"))?;
+    active_config.write_all(b"work")?;
+    active_config.sync_all()?;
+
+    let mut config_file = File::create(dir.path().join("configurations/config_work"))?;
+    config_file.write_all(config.as_bytes())?;
+    config_file.sync_all()?;
+
+    Ok(dir)
+}
+
+#[test]
+fn account_project_and_region_set() -> io::Result<()> {
+    let dir = gcloud_config_dir(
+        "[core]\naccount = foo@example.com\nproject = abc\n\n[compute]\nregion = us-central1\n",
+    )?;
+    let output = common::render_module("gcloud")
+        .env("CLOUDSDK_CONFIG", dir.path())
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        Color::Blue
+            .bold()
+            .paint("☁️  foo@example.com@abc(us-central1)")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn project_and_region_with_aliases() -> io::Result<()> {
+    let dir = gcloud_config_dir(
+        "[core]\naccount = foo@example.com\nproject = acme-corp-production-4821\n\n[compute]\nregion = europe-west1\n",
+    )?;
+    let output = common::render_module("gcloud")
+        .env("CLOUDSDK_CONFIG", dir.path())
+        .use_config(toml::toml! {
+            [gcloud.project_aliases]
+            acme-corp-production-4821 = "prod"
+            [gcloud.region_aliases]
+            europe-west1 = "ew1"
+        })
+        .output()?;
+    let expected = format!(
+        "on {} ",
+        Color::Blue.bold().paint("☁️  foo@example.com@prod(ew1)")
+    );
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn active_config_from_env() -> io::Result<()> {
+    let dir = gcloud_config_dir("[core]\naccount = foo@example.com\n")?;
+    let mut config_file = File::create(dir.path().join("configurations/config_personal"))?;
+    config_file.write_all(b"[core]\naccount = me@example.com\n")?;
+    config_file.sync_all()?;
+
+    let output = common::render_module("gcloud")
+        .env("CLOUDSDK_CONFIG", dir.path())
+        .env("CLOUDSDK_ACTIVE_CONFIG_NAME", "personal")
+        .output()?;
+    let expected = format!("on {} ", Color::Blue.bold().paint("☁️  me@example.com"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn no_active_config() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = common::render_module("gcloud")
+        .env("CLOUDSDK_CONFIG", dir.path())
+        .output()?;
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
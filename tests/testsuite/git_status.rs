@@ -503,6 +503,84 @@ fn shows_deleted_file_with_count() -> io::Result<()> {
     remove_dir_all(repo_dir)
 }
 
+#[test]
+#[ignore]
+fn shows_summary_above_threshold() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_untracked(&repo_dir)?;
+    create_modified(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            summarize_above = 1
+        })
+        .arg("--path")
+        .arg(&repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format_output("~2 files");
+
+    assert_eq!(expected, actual);
+
+    remove_dir_all(repo_dir)
+}
+
+#[test]
+#[ignore]
+fn shows_counters_below_threshold() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_untracked(&repo_dir)?;
+    create_modified(&repo_dir)?;
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            summarize_above = 2
+        })
+        .arg("--path")
+        .arg(&repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format_output("!?");
+
+    assert_eq!(expected, actual);
+
+    remove_dir_all(repo_dir)
+}
+
+#[test]
+#[ignore]
+fn summarize_above_can_be_set_per_repository() -> io::Result<()> {
+    let repo_dir = common::create_fixture_repo()?;
+
+    create_untracked(&repo_dir)?;
+    create_modified(&repo_dir)?;
+
+    Command::new("git")
+        .args(&["config", "starship.summarizeAbove", "1"])
+        .current_dir(repo_dir.as_path())
+        .output()?;
+    barrier();
+
+    let output = common::render_module("git_status")
+        .use_config(toml::toml! {
+            [git_status]
+            summary = "$count changes"
+        })
+        .arg("--path")
+        .arg(&repo_dir)
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format_output("2 changes");
+
+    assert_eq!(expected, actual);
+
+    remove_dir_all(repo_dir)
+}
+
 fn ahead(repo_dir: &PathBuf) -> io::Result<()> {
     File::create(repo_dir.join("readme.md"))?.sync_all()?;
 
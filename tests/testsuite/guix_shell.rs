@@ -0,0 +1,27 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common;
+
+#[test]
+fn no_env_variables() -> io::Result<()> {
+    let output = common::render_module("guix_shell").output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    Ok(())
+}
+
+#[test]
+fn guix_environment_set() -> io::Result<()> {
+    let output = common::render_module("guix_shell")
+        .env(
+            "GUIX_ENVIRONMENT",
+            "/gnu/store/7vmfs4khf4fllsh83kqkxssbw3437qsh-profile",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Yellow.bold().paint("🐃 "));
+    assert_eq!(expected, actual);
+    Ok(())
+}
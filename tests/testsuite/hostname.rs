@@ -59,6 +59,28 @@ fn ssh() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn ssh_tty_with_ssh_symbol() -> io::Result<()> {
+    let hostname = match get_hostname() {
+        Some(h) => h,
+        None => return hostname_not_tested(),
+    };
+    let output = common::render_module("hostname")
+        .env_clear()
+        .use_config(toml::toml! {
+            [hostname]
+            ssh_only = true
+            ssh_symbol = "ssh:"
+            trim_at = ""
+        })
+        .env("SSH_TTY", "/dev/pts/0")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    let expected = format!("on {} ", style().paint(format!("ssh:{}", hostname)));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
 #[test]
 fn no_trim_at() -> io::Result<()> {
     let hostname = match get_hostname() {
@@ -0,0 +1,28 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io;
+
+use crate::common;
+
+#[test]
+fn version_from_java_home_release() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    File::create(dir.path().join("deps.edn"))?.sync_all()?;
+    let java_home = tempfile::tempdir()?;
+    fs::write(
+        java_home.path().join("release"),
+        "IMPLEMENTOR=\"AdoptOpenJDK\"\nJAVA_VERSION=\"11.0.4\"\n",
+    )?;
+
+    let output = common::render_module("java")
+        .env("JAVA_HOME", java_home.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Red.dimmed().paint("☕ v11.0.4"));
+    assert_eq!(expected, actual);
+    dir.close()?;
+    java_home.close()
+}
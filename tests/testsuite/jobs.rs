@@ -33,6 +33,55 @@ fn config_blank_job_2() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn config_number_threshold_0_job_0() -> io::Result<()> {
+    let output = common::render_module("jobs")
+        .use_config(toml::toml! {
+            [jobs]
+            number_threshold = 0
+        })
+        .arg("--jobs=0")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Blue.bold().paint("0"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn config_symbol_threshold_2_job_1() -> io::Result<()> {
+    let output = common::render_module("jobs")
+        .use_config(toml::toml! {
+            [jobs]
+            symbol_threshold = 2
+            number_threshold = 1
+        })
+        .arg("--jobs=1")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Blue.bold().paint("1"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn config_number_threshold_3_job_3() -> io::Result<()> {
+    let output = common::render_module("jobs")
+        .use_config(toml::toml! {
+            [jobs]
+            number_threshold = 3
+        })
+        .arg("--jobs=3")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("{} ", Color::Blue.bold().paint("✦3"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
 #[test]
 fn config_2_job_2() -> io::Result<()> {
     let output = common::render_module("jobs")
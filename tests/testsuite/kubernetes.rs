@@ -0,0 +1,104 @@
+use ansi_term::Color;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::common::{self, TestCommand};
+
+const KUBECONFIG: &str = "\
+apiVersion: v1
+clusters: []
+contexts:
+- context:
+    cluster: test_cluster
+    user: test_user
+    namespace: test_namespace
+  name: test_context
+current-context: test_context
+kind: Config
+preferences: {}
+users: []
+";
+
+fn write_kubeconfig(dir: &Path) -> io::Result<()> {
+    let mut file = File::create(dir.join("config"))?;
+    file.write_all(KUBECONFIG.as_bytes())?;
+    file.sync_all()
+}
+
+#[test]
+fn detect_folders_not_matching() -> io::Result<()> {
+    let kube_dir = tempfile::tempdir()?;
+    write_kubeconfig(kube_dir.path())?;
+    let dir = tempfile::tempdir()?;
+
+    let output = common::render_module("kubernetes")
+        .env("KUBECONFIG", kube_dir.path().join("config"))
+        .use_config(toml::toml! {
+            [kubernetes]
+            disabled = false
+            detect_folders = ["k8s"]
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = "";
+    assert_eq!(expected, actual);
+    dir.close()?;
+    kube_dir.close()
+}
+
+#[test]
+fn detect_folders_matching() -> io::Result<()> {
+    let kube_dir = tempfile::tempdir()?;
+    write_kubeconfig(kube_dir.path())?;
+    let dir = tempfile::tempdir()?;
+    fs::create_dir(dir.path().join("k8s"))?;
+
+    let output = common::render_module("kubernetes")
+        .env("KUBECONFIG", kube_dir.path().join("config"))
+        .use_config(toml::toml! {
+            [kubernetes]
+            disabled = false
+            detect_folders = ["k8s"]
+        })
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "on {} ",
+        Color::Cyan.bold().paint("☸ test_context (test_namespace)")
+    );
+    assert_eq!(expected, actual);
+    dir.close()?;
+    kube_dir.close()
+}
+
+#[test]
+fn cluster_and_user() -> io::Result<()> {
+    let kube_dir = tempfile::tempdir()?;
+    write_kubeconfig(kube_dir.path())?;
+
+    let output = common::render_module("kubernetes")
+        .env("KUBECONFIG", kube_dir.path().join("config"))
+        .use_config(toml::toml! {
+            [kubernetes]
+            disabled = false
+            format = "[$user@$cluster(/$namespace)]($style) "
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!(
+        "{} ",
+        Color::Cyan
+            .bold()
+            .paint("test_user@test_cluster/test_namespace")
+    );
+    assert_eq!(expected, actual);
+    kube_dir.close()
+}
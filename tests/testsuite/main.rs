@@ -1,23 +1,32 @@
 mod aws;
+mod azure;
 mod character;
 mod cmd_duration;
 mod common;
 mod conda;
 mod configuration;
+mod custom;
 mod directory;
+mod docker_context;
 mod dotnet;
 mod env_var;
+mod gcloud;
 mod git_branch;
 mod git_commit;
 mod git_state;
 mod git_status;
+mod guix_shell;
 mod hg_branch;
 mod hostname;
+mod java;
 mod jobs;
+mod kubernetes;
 mod modules;
 mod nix_shell;
+mod openstack;
 mod python;
 mod singularity;
+mod spack;
 mod terraform;
 mod time;
 mod username;
@@ -1,7 +1,7 @@
 use ansi_term::Color;
 use std::io;
 
-use crate::common;
+use crate::common::{self, TestCommand};
 
 #[test]
 fn no_env_variables() -> io::Result<()> {
@@ -70,3 +70,37 @@ fn impure_shell_name() -> io::Result<()> {
     assert_eq!(expected, actual);
     Ok(())
 }
+
+#[test]
+fn flake_shell() -> io::Result<()> {
+    let output = common::render_module("nix_shell")
+        .env(
+            "PATH",
+            "/nix/store/v5sv61sszx301i0x6xysaqzla09nksnd-hello-2.10/bin:/usr/bin",
+        )
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("❄️  "));
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn flake_shell_unknown_msg() -> io::Result<()> {
+    let output = common::render_module("nix_shell")
+        .env(
+            "PATH",
+            "/nix/store/v5sv61sszx301i0x6xysaqzla09nksnd-hello-2.10/bin",
+        )
+        .use_config(toml::toml! {
+            [nix_shell]
+            unknown_msg = "flake"
+        })
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("❄️  flake"));
+    assert_eq!(expected, actual);
+    Ok(())
+}
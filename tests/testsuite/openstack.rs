@@ -0,0 +1,73 @@
+use std::fs::File;
+use std::io::{self, Write};
+
+use ansi_term::Color;
+
+use crate::common;
+
+const CLOUDS_YAML: &str = "\
+clouds:
+  corp:
+    auth:
+      auth_url: https://identity.example.com:5000/v3
+      project_name: infra
+";
+
+#[test]
+fn cloud_not_set() -> io::Result<()> {
+    let output = common::render_module("openstack").output()?;
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn cloud_with_project() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let mut file = File::create(dir.path().join("clouds.yaml"))?;
+    file.write_all(CLOUDS_YAML.as_bytes())?;
+    file.sync_all()?;
+
+    let output = common::render_module("openstack")
+        .env("OS_CLOUD", "corp")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let expected = format!("on {} ", Color::Yellow.bold().paint("☁️  corp(infra)"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn cloud_from_client_config_file() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let clouds_yaml = dir.path().join("my-clouds.yaml");
+    let mut file = File::create(&clouds_yaml)?;
+    file.write_all(CLOUDS_YAML.as_bytes())?;
+    file.sync_all()?;
+
+    let output = common::render_module("openstack")
+        .env("OS_CLOUD", "corp")
+        .env("OS_CLIENT_CONFIG_FILE", &clouds_yaml)
+        .output()?;
+    let expected = format!("on {} ", Color::Yellow.bold().paint("☁️  corp(infra)"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
+
+#[test]
+fn cloud_without_clouds_yaml() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let output = common::render_module("openstack")
+        .env("OS_CLOUD", "corp")
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let expected = format!("on {} ", Color::Yellow.bold().paint("☁️  corp"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(expected, actual);
+    dir.close()
+}
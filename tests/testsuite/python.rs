@@ -1,4 +1,4 @@
-use std::fs::File;
+use std::fs::{self, File};
 use std::io;
 
 use crate::common;
@@ -35,3 +35,24 @@ fn with_active_venv() -> io::Result<()> {
     assert!(actual.contains("my_venv"));
     dir.close()
 }
+
+#[test]
+fn with_venv_prompt() -> io::Result<()> {
+    let dir = tempfile::tempdir()?;
+    let venv = tempfile::tempdir()?;
+    fs::write(
+        venv.path().join("pyvenv.cfg"),
+        "home = /usr/bin\nprompt = 'my-project'\n",
+    )?;
+
+    let output = common::render_module("python")
+        .env("VIRTUAL_ENV", venv.path())
+        .arg("--path")
+        .arg(dir.path())
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert!(actual.contains("(my-project)"));
+    dir.close()?;
+    venv.close()
+}
@@ -0,0 +1,48 @@
+use ansi_term::Color;
+use std::io;
+
+use crate::common::{self, TestCommand};
+
+#[test]
+fn not_in_env() -> io::Result<()> {
+    let output = common::render_module("spack").output()?;
+
+    let expected = "";
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn env_set() -> io::Result<()> {
+    let output = common::render_module("spack")
+        .env(
+            "SPACK_ENV",
+            "/home/user/spack/var/spack/environments/astronauts",
+        )
+        .output()?;
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("🅢 astronauts"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
+
+#[test]
+fn truncation_length() -> io::Result<()> {
+    let output = common::render_module("spack")
+        .env("SPACK_ENV", "/home/user/projects/astronauts")
+        .use_config(toml::toml! {
+            [spack]
+            truncation_length = 2
+        })
+        .output()?;
+
+    let expected = format!("via {} ", Color::Blue.bold().paint("🅢 projects/astronauts"));
+    let actual = String::from_utf8(output.stdout).unwrap();
+
+    assert_eq!(expected, actual);
+    Ok(())
+}
@@ -25,6 +25,16 @@ fn logname_equals_user() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn user_without_logname() -> io::Result<()> {
+    let output = common::render_module("username")
+        .env("USER", "astronaut")
+        .output()?;
+    let actual = String::from_utf8(output.stdout).unwrap();
+    assert_eq!("", actual);
+    Ok(())
+}
+
 #[test]
 fn ssh_wo_username() -> io::Result<()> {
     // SSH connection w/o username